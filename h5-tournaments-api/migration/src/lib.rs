@@ -0,0 +1,62 @@
+pub use sea_orm_migration::prelude::*;
+
+mod m20250610_000001_create_users;
+mod m20250610_000002_create_tournament_organizers;
+mod m20250610_000003_create_tournament_operators;
+mod m20250610_000004_create_tournaments;
+mod m20250610_000005_create_tournament_builders;
+mod m20250610_000006_create_participants;
+mod m20250610_000007_create_matches;
+mod m20250610_000008_create_games;
+mod m20250610_000009_create_races;
+mod m20250610_000010_create_heroes;
+mod m20250610_000011_seed_races;
+mod m20250610_000012_seed_heroes;
+mod m20250612_000013_add_games_created_at;
+mod m20250613_000014_create_ratings;
+mod m20250614_000015_create_api_tokens;
+mod m20250615_000016_add_api_tokens_operator;
+mod m20250616_000017_create_legacy_mappings;
+mod m20250617_000018_extend_catalogue;
+mod m20250618_000019_create_mods;
+mod m20250619_000020_create_brackets;
+mod m20250620_000021_create_group_stage_settings;
+mod m20250621_000022_extend_matches;
+mod m20250622_000023_create_swiss_pairings;
+mod m20250623_000024_create_tournament_stage_changes;
+mod m20250624_000025_add_matches_stage;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20250610_000001_create_users::Migration),
+            Box::new(m20250610_000002_create_tournament_organizers::Migration),
+            Box::new(m20250610_000003_create_tournament_operators::Migration),
+            Box::new(m20250610_000004_create_tournaments::Migration),
+            Box::new(m20250610_000005_create_tournament_builders::Migration),
+            Box::new(m20250610_000006_create_participants::Migration),
+            Box::new(m20250610_000007_create_matches::Migration),
+            Box::new(m20250610_000008_create_games::Migration),
+            Box::new(m20250610_000009_create_races::Migration),
+            Box::new(m20250610_000010_create_heroes::Migration),
+            Box::new(m20250610_000011_seed_races::Migration),
+            Box::new(m20250610_000012_seed_heroes::Migration),
+            Box::new(m20250612_000013_add_games_created_at::Migration),
+            Box::new(m20250613_000014_create_ratings::Migration),
+            Box::new(m20250614_000015_create_api_tokens::Migration),
+            Box::new(m20250615_000016_add_api_tokens_operator::Migration),
+            Box::new(m20250616_000017_create_legacy_mappings::Migration),
+            Box::new(m20250617_000018_extend_catalogue::Migration),
+            Box::new(m20250618_000019_create_mods::Migration),
+            Box::new(m20250619_000020_create_brackets::Migration),
+            Box::new(m20250620_000021_create_group_stage_settings::Migration),
+            Box::new(m20250621_000022_extend_matches::Migration),
+            Box::new(m20250622_000023_create_swiss_pairings::Migration),
+            Box::new(m20250623_000024_create_tournament_stage_changes::Migration),
+            Box::new(m20250624_000025_add_matches_stage::Migration),
+        ]
+    }
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Users::Table)
+                    .if_not_exists()
+                    .col(uuid(Users::Id).primary_key())
+                    // create_user relies on this constraint for its upsert
+                    .col(big_integer(Users::DiscordId).unique_key())
+                    .col(string(Users::DiscordNick))
+                    .col(string(Users::Nickname))
+                    .col(boolean(Users::RegisteredManually).default(false))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Users::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Users {
+    Table,
+    Id,
+    DiscordId,
+    DiscordNick,
+    Nickname,
+    RegisteredManually
+}
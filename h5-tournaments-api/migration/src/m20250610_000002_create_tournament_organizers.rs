@@ -0,0 +1,35 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentOrganizers::Table)
+                    .if_not_exists()
+                    .col(uuid(TournamentOrganizers::Id).primary_key())
+                    .col(big_integer(TournamentOrganizers::DiscordId).unique_key())
+                    .col(string(TournamentOrganizers::ChallongeApiKey))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TournamentOrganizers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TournamentOrganizers {
+    Table,
+    Id,
+    DiscordId,
+    ChallongeApiKey
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentOperators::Table)
+                    .if_not_exists()
+                    .col(uuid(TournamentOperators::Id).primary_key())
+                    .col(string(TournamentOperators::Name))
+                    .col(big_integer(TournamentOperators::ServerId).unique_key())
+                    .col(array(TournamentOperators::Heroes, ColumnType::Integer))
+                    .col(big_integer(TournamentOperators::SectionId))
+                    .col(big_integer(TournamentOperators::GeneratedChannel))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TournamentOperators::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TournamentOperators {
+    Table,
+    Id,
+    Name,
+    ServerId,
+    Heroes,
+    SectionId,
+    GeneratedChannel
+}
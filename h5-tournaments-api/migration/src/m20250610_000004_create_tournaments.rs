@@ -0,0 +1,72 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000002_create_tournament_organizers::TournamentOrganizers, m20250610_000003_create_tournament_operators::TournamentOperators};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tournaments::Table)
+                    .if_not_exists()
+                    .col(uuid(Tournaments::Id).primary_key())
+                    .col(uuid(Tournaments::OperatorId))
+                    .col(big_integer(Tournaments::ChannelId).unique_key())
+                    .col(string(Tournaments::Name))
+                    .col(integer_null(Tournaments::Stage))
+                    .col(big_integer(Tournaments::RegisterChannel).unique_key())
+                    .col(boolean(Tournaments::WithBargains).default(false))
+                    .col(boolean(Tournaments::WithBargainsColor).default(false))
+                    .col(boolean(Tournaments::WithForeignHeroes).default(false))
+                    .col(big_integer(Tournaments::RoleId))
+                    .col(string_null(Tournaments::ChallongeId))
+                    .col(uuid(Tournaments::Organizer))
+                    .col(integer(Tournaments::GameType))
+                    .col(integer(Tournaments::ModType).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tournaments_new_operator_id")
+                            .from(Tournaments::Table, Tournaments::OperatorId)
+                            .to(TournamentOperators::Table, TournamentOperators::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tournaments_new_organizer")
+                            .from(Tournaments::Table, Tournaments::Organizer)
+                            .to(TournamentOrganizers::Table, TournamentOrganizers::Id)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Tournaments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Tournaments {
+    #[sea_orm(iden = "tournaments_new")]
+    Table,
+    Id,
+    OperatorId,
+    ChannelId,
+    Name,
+    Stage,
+    RegisterChannel,
+    WithBargains,
+    WithBargainsColor,
+    WithForeignHeroes,
+    RoleId,
+    ChallongeId,
+    Organizer,
+    GameType,
+    ModType
+}
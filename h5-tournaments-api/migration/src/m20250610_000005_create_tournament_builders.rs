@@ -0,0 +1,49 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentBuilders::Table)
+                    .if_not_exists()
+                    .col(uuid(TournamentBuilders::Id).primary_key())
+                    .col(big_integer(TournamentBuilders::MessageId).unique_key())
+                    .col(string_null(TournamentBuilders::Name))
+                    .col(integer_null(TournamentBuilders::EditState))
+                    .col(big_integer_null(TournamentBuilders::RegisterChannel))
+                    .col(big_integer_null(TournamentBuilders::ReportsChannel))
+                    .col(big_integer_null(TournamentBuilders::Role))
+                    .col(boolean_null(TournamentBuilders::UseBargains))
+                    .col(boolean_null(TournamentBuilders::UseBargainsColor))
+                    .col(boolean_null(TournamentBuilders::UseForeignHeroes))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TournamentBuilders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TournamentBuilders {
+    Table,
+    Id,
+    MessageId,
+    Name,
+    EditState,
+    RegisterChannel,
+    ReportsChannel,
+    Role,
+    UseBargains,
+    UseBargainsColor,
+    UseForeignHeroes
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000001_create_users::Users, m20250610_000004_create_tournaments::Tournaments};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Participants::Table)
+                    .if_not_exists()
+                    .col(uuid(Participants::Id).primary_key())
+                    .col(uuid(Participants::TournamentId))
+                    .col(uuid(Participants::UserId))
+                    .col(integer(Participants::GroupNumber).default(0))
+                    .col(string_null(Participants::ChallongeId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_participants_tournament_id")
+                            .from(Participants::Table, Participants::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_participants_user_id")
+                            .from(Participants::Table, Participants::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_participants_tournament_user")
+                            .col(Participants::TournamentId)
+                            .col(Participants::UserId)
+                            .unique()
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Participants::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Participants {
+    Table,
+    Id,
+    TournamentId,
+    UserId,
+    GroupNumber,
+    ChallongeId
+}
@@ -0,0 +1,76 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000001_create_users::Users, m20250610_000004_create_tournaments::Tournaments};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Matches::Table)
+                    .if_not_exists()
+                    .col(uuid(Matches::Id).primary_key())
+                    .col(uuid(Matches::TournamentId))
+                    .col(big_integer(Matches::MessageId))
+                    .col(uuid(Matches::FirstPlayer))
+                    .col(uuid(Matches::SecondPlayer))
+                    .col(string(Matches::ChallongeId))
+                    .col(string_null(Matches::ReportLink))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_matches_tournament_id")
+                            .from(Matches::Table, Matches::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_matches_first_player")
+                            .from(Matches::Table, Matches::FirstPlayer)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_matches_second_player")
+                            .from(Matches::Table, Matches::SecondPlayer)
+                            .to(Users::Table, Users::Id)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        // create_match looks existing matches up by their challonge id
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_matches_challonge_id")
+                    .table(Matches::Table)
+                    .col(Matches::ChallongeId)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Matches::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Matches {
+    Table,
+    Id,
+    TournamentId,
+    MessageId,
+    FirstPlayer,
+    SecondPlayer,
+    ChallongeId,
+    ReportLink
+}
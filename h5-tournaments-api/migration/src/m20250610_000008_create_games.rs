@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000007_create_matches::Matches;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Games::Table)
+                    .if_not_exists()
+                    .col(uuid(Games::Id).primary_key())
+                    .col(uuid(Games::MatchId))
+                    .col(integer_null(Games::FirstPlayerRace))
+                    .col(integer_null(Games::FirstPlayerHero))
+                    .col(integer_null(Games::SecondPlayerRace))
+                    .col(integer_null(Games::SecondPlayerHero))
+                    .col(integer_null(Games::BargainsColor))
+                    .col(integer_null(Games::BargainsAmount))
+                    .col(integer(Games::Result).default(0))
+                    .col(integer(Games::Outcome).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_games_match_id")
+                            .from(Games::Table, Games::MatchId)
+                            .to(Matches::Table, Matches::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_games_match_id")
+                    .table(Games::Table)
+                    .col(Games::MatchId)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Games::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Games {
+    Table,
+    Id,
+    MatchId,
+    FirstPlayerRace,
+    FirstPlayerHero,
+    SecondPlayerRace,
+    SecondPlayerHero,
+    BargainsColor,
+    BargainsAmount,
+    Result,
+    Outcome
+}
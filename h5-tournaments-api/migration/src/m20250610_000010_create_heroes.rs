@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Legacy per-hero table, still read by the REST routes and the `hero`/`heroes` queries
+        manager
+            .create_table(
+                Table::create()
+                    .table(Heroes::Table)
+                    .if_not_exists()
+                    .col(integer(Heroes::Id).primary_key())
+                    .col(integer(Heroes::Race))
+                    .col(string(Heroes::ActualName))
+                    .col(json_binary(Heroes::NameVariants).default(Expr::cust("'{\"variants\": []}'::jsonb")))
+                    .col(small_integer(Heroes::ModType).default(0))
+                    .to_owned()
+            )
+            .await?;
+
+        // One catalogue of heroes per mod, stored as a single json document
+        manager
+            .create_table(
+                Table::create()
+                    .table(HeroesNew::Table)
+                    .if_not_exists()
+                    .col(pk_auto(HeroesNew::Id))
+                    .col(integer(HeroesNew::ModType).unique_key())
+                    .col(json_binary(HeroesNew::Heroes))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HeroesNew::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Heroes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Heroes {
+    Table,
+    Id,
+    Race,
+    ActualName,
+    NameVariants,
+    ModType
+}
+
+#[derive(DeriveIden)]
+pub enum HeroesNew {
+    Table,
+    Id,
+    ModType,
+    Heroes
+}
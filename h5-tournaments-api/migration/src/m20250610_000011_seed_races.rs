@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250610_000009_create_races::Races;
+
+/// Race ids are shared with the bot, which keeps the same list in `H5TournamentsService`.
+pub(crate) const RACES: [(i32, &str); 8] = [
+    (1, "Орден порядка"),
+    (2, "Инферно"),
+    (3, "Некрополис"),
+    (4, "Лесной союз"),
+    (5, "Лига теней"),
+    (6, "Академия волшебства"),
+    (7, "Северные кланы"),
+    (8, "Великая орда")
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut insert = Query::insert()
+            .into_table(Races::Table)
+            .columns([Races::Id, Races::Name])
+            .on_conflict(OnConflict::column(Races::Id).do_nothing().to_owned())
+            .to_owned();
+
+        for (id, name) in RACES {
+            insert.values_panic([id.into(), name.into()]);
+        }
+
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(Races::Table)
+            .and_where(Expr::col(Races::Id).is_in(RACES.map(|(id, _)| id)))
+            .to_owned();
+
+        manager.exec_stmt(delete).await
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+use serde_json::json;
+
+use super::m20250610_000010_create_heroes::HeroesNew;
+
+/// Heroes of Universe mod as (race, name) pairs. Hero ids are assigned in this order.
+const BASE_HEROES: [(i32, &str); 86] = [
+    (1, "Dougal"), (1, "Ellaine"), (1, "Freyda"), (1, "Irina"), (1, "Klaus"), (1, "Laszlo"),
+    (1, "Maeve"), (1, "Rutger"), (1, "Alaric"), (1, "Gabrielle"), (1, "Vittorio"),
+    (2, "Alastor"), (2, "Biara"), (2, "Deleb"), (2, "Grawl"), (2, "Grok"), (2, "Jezebeth"),
+    (2, "Marbas"), (2, "Nebiros"), (2, "Nymus"), (2, "Calh"), (2, "Orlando"),
+    (3, "Deirdre"), (3, "Kaspar"), (3, "Lucretia"), (3, "Naadir"), (3, "Orson"), (3, "Ornella"),
+    (3, "Raven"), (3, "Vladimir"), (3, "Zoltan"), (3, "Thant"),
+    (4, "Anwen"), (4, "Dirael"), (4, "Gilraen"), (4, "Ossir"), (4, "Talanar"), (4, "Vinrael"),
+    (4, "Wyngaal"), (4, "Ylthin"), (4, "Itil"), (4, "Arniel"),
+    (5, "Eruina"), (5, "Ferigl"), (5, "Inagost"), (5, "Kythra"), (5, "Lethos"), (5, "Raelag"),
+    (5, "Shadya"), (5, "Sorgal"), (5, "Yrbeth"), (5, "Yrwanna"), (5, "Vayshan"),
+    (6, "Cyrus"), (6, "Faiz"), (6, "Havez"), (6, "Josephine"), (6, "Maahir"), (6, "Nathir"),
+    (6, "Nur"), (6, "Razzak"), (6, "Rissa"), (6, "Davius"), (6, "Galib"),
+    (7, "Bersy"), (7, "Brand"), (7, "Ebba"), (7, "Egil"), (7, "Erling"), (7, "Hangvul"),
+    (7, "Helmar"), (7, "Ingvar"), (7, "Karli"), (7, "Rolf"), (7, "Una"),
+    (8, "Crag Hack"), (8, "Gorshak"), (8, "Gottai"), (8, "Haggash"), (8, "Kilghan"), (8, "Quroq"),
+    (8, "Shak'Karukat"), (8, "Telsek"), (8, "Urghat"), (8, "Kujin"), (8, "Garuna")
+];
+
+/// Mods seeded with the catalogue above. HRTA has its own roster, so its catalogue
+/// is filled through catalogue mutations instead of copying this one.
+const MOD_TYPES: [i32; 1] = [0];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let entities = BASE_HEROES.iter()
+            .enumerate()
+            .map(|(index, (race, name))| json!({ "id": index as i32 + 1, "race": race, "name": name }))
+            .collect::<Vec<serde_json::Value>>();
+        let catalogue = json!({ "entities": entities });
+
+        let mut insert = Query::insert()
+            .into_table(HeroesNew::Table)
+            .columns([HeroesNew::ModType, HeroesNew::Heroes])
+            .on_conflict(OnConflict::column(HeroesNew::ModType).do_nothing().to_owned())
+            .to_owned();
+
+        for mod_type in MOD_TYPES {
+            insert.values_panic([mod_type.into(), Expr::val(catalogue.to_string()).cast_as(Alias::new("jsonb"))]);
+        }
+
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let delete = Query::delete()
+            .from_table(HeroesNew::Table)
+            .and_where(Expr::col(HeroesNew::ModType).is_in(MOD_TYPES))
+            .to_owned();
+
+        manager.exec_stmt(delete).await
+    }
+}
@@ -0,0 +1,158 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000001_create_users::Users, m20250610_000007_create_matches::Matches};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Null mod type means overall rating across all mods
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ratings::Table)
+                    .if_not_exists()
+                    .col(uuid(Ratings::Id).primary_key())
+                    .col(uuid(Ratings::UserId))
+                    .col(integer_null(Ratings::ModType))
+                    .col(double(Ratings::Rating))
+                    .col(double(Ratings::Deviation))
+                    .col(integer(Ratings::Matches).default(0))
+                    .col(integer(Ratings::Games).default(0))
+                    .col(integer(Ratings::Wins).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ratings_user_id")
+                            .from(Ratings::Table, Ratings::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_ratings_user_mod_type")
+                            .col(Ratings::UserId)
+                            .col(Ratings::ModType)
+                            .unique()
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        // nulls are distinct in unique indexes, so overall ratings need their own one
+        manager
+            .get_connection()
+            .execute_unprepared("CREATE UNIQUE INDEX IF NOT EXISTS idx_ratings_user_overall ON ratings (user_id) WHERE mod_type IS NULL")
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RatingHistory::Table)
+                    .if_not_exists()
+                    .col(uuid(RatingHistory::Id).primary_key())
+                    .col(big_integer(RatingHistory::Sequence))
+                    .col(uuid(RatingHistory::UserId))
+                    .col(integer_null(RatingHistory::ModType))
+                    .col(uuid(RatingHistory::MatchId))
+                    .col(uuid(RatingHistory::OpponentId))
+                    .col(integer(RatingHistory::GamesWon))
+                    .col(integer(RatingHistory::GamesLost))
+                    .col(double(RatingHistory::RatingBefore))
+                    .col(double(RatingHistory::RatingAfter))
+                    .col(double(RatingHistory::DeviationBefore))
+                    .col(double(RatingHistory::DeviationAfter))
+                    .col(timestamp_with_time_zone(RatingHistory::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rating_history_user_id")
+                            .from(RatingHistory::Table, RatingHistory::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rating_history_opponent_id")
+                            .from(RatingHistory::Table, RatingHistory::OpponentId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_rating_history_match_id")
+                            .from(RatingHistory::Table, RatingHistory::MatchId)
+                            .to(Matches::Table, Matches::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_rating_history_user_mod_type")
+                    .table(RatingHistory::Table)
+                    .col(RatingHistory::UserId)
+                    .col(RatingHistory::ModType)
+                    .col(RatingHistory::Sequence)
+                    .to_owned()
+            )
+            .await?;
+
+        // rating updates look for matches that weren't processed yet
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_rating_history_match_id")
+                    .table(RatingHistory::Table)
+                    .col(RatingHistory::MatchId)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RatingHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Ratings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Ratings {
+    Table,
+    Id,
+    UserId,
+    ModType,
+    Rating,
+    Deviation,
+    Matches,
+    Games,
+    Wins
+}
+
+#[derive(DeriveIden)]
+pub enum RatingHistory {
+    Table,
+    Id,
+    Sequence,
+    UserId,
+    ModType,
+    MatchId,
+    OpponentId,
+    GamesWon,
+    GamesLost,
+    RatingBefore,
+    RatingAfter,
+    DeviationBefore,
+    DeviationAfter,
+    CreatedAt
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000002_create_tournament_organizers::TournamentOrganizers;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only sha256 hashes of tokens are stored, tokens themselves are shown once when issued
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiTokens::Table)
+                    .if_not_exists()
+                    .col(uuid(ApiTokens::Id).primary_key())
+                    .col(string(ApiTokens::Name))
+                    .col(string(ApiTokens::TokenHash).unique_key())
+                    .col(integer(ApiTokens::Role))
+                    .col(uuid_null(ApiTokens::OrganizerId))
+                    .col(boolean(ApiTokens::Revoked).default(false))
+                    .col(timestamp_with_time_zone(ApiTokens::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_api_tokens_organizer_id")
+                            .from(ApiTokens::Table, ApiTokens::OrganizerId)
+                            .to(TournamentOrganizers::Table, TournamentOrganizers::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ApiTokens::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum ApiTokens {
+    Table,
+    Id,
+    Name,
+    TokenHash,
+    Role,
+    OrganizerId,
+    Revoked,
+    CreatedAt
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000003_create_tournament_operators::TournamentOperators, m20250614_000015_create_api_tokens::ApiTokens};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Bot tokens are scoped to discord server they serve, tokens without operator stay global
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiTokens::Table)
+                    .add_column_if_not_exists(uuid_null(ApiTokenOperator::OperatorId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_api_tokens_operator_id")
+                            .from_tbl(ApiTokens::Table)
+                            .from_col(ApiTokenOperator::OperatorId)
+                            .to_tbl(TournamentOperators::Table)
+                            .to_col(TournamentOperators::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiTokens::Table)
+                    .drop_foreign_key(Alias::new("fk_api_tokens_operator_id"))
+                    .drop_column(ApiTokenOperator::OperatorId)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ApiTokenOperator {
+    OperatorId
+}
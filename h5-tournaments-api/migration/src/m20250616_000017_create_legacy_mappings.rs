@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000004_create_tournaments::Tournaments;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Links rows of legacy tables (tournaments, matches_old, player names) with rows they were migrated into
+        manager
+            .create_table(
+                Table::create()
+                    .table(LegacyMappings::Table)
+                    .if_not_exists()
+                    .col(integer(LegacyMappings::Kind))
+                    .col(string(LegacyMappings::LegacyKey))
+                    .col(uuid(LegacyMappings::NewId))
+                    .col(timestamp_with_time_zone(LegacyMappings::CreatedAt).default(Expr::current_timestamp()))
+                    .primary_key(
+                        Index::create()
+                            .col(LegacyMappings::Kind)
+                            .col(LegacyMappings::LegacyKey)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        // Legacy tournaments of the same channel can't share it in unique columns, so migrated ones are left without channels
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournaments::Table)
+                    .modify_column(ColumnDef::new(Tournaments::ChannelId).big_integer().null())
+                    .modify_column(ColumnDef::new(Tournaments::RegisterChannel).big_integer().null())
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // fails while migrated tournaments without channels exist, they must be removed first
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournaments::Table)
+                    .modify_column(ColumnDef::new(Tournaments::ChannelId).big_integer().not_null())
+                    .modify_column(ColumnDef::new(Tournaments::RegisterChannel).big_integer().not_null())
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(LegacyMappings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum LegacyMappings {
+    Table,
+    Kind,
+    LegacyKey,
+    NewId,
+    CreatedAt
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000009_create_races::Races, m20250610_000010_create_heroes::HeroesNew};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Display names are stored as {locale: name}, variants are alternative spellings the report parser accepts
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Races::Table)
+                    .add_column_if_not_exists(json_binary(CatalogueNames::DisplayNames).default(Expr::cust("'{}'::jsonb")))
+                    .add_column_if_not_exists(json_binary(CatalogueNames::NameVariants).default(Expr::cust("'[]'::jsonb")))
+                    .to_owned()
+            )
+            .await?;
+
+        // Increased on every change of heroes of the mod or of races, so clients know when cached catalogue is stale
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(HeroesNew::Table)
+                    .add_column_if_not_exists(integer(CatalogueNames::Version).default(1))
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(HeroesNew::Table)
+                    .drop_column(CatalogueNames::Version)
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Races::Table)
+                    .drop_column(CatalogueNames::DisplayNames)
+                    .drop_column(CatalogueNames::NameVariants)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CatalogueNames {
+    DisplayNames,
+    NameVariants,
+    Version
+}
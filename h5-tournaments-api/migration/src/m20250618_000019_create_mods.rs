@@ -0,0 +1,116 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000004_create_tournaments::Tournaments, m20250610_000009_create_races::Races, m20250610_000010_create_heroes::HeroesNew, m20250613_000014_create_ratings::{RatingHistory, Ratings}};
+
+/// Ids match values of former `ModType` enum, so existing rows keep pointing to the same mods.
+const MODS: [(i32, &str); 2] = [
+    (0, "Universe"),
+    (1, "HRTA")
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Mods::Table)
+                    .if_not_exists()
+                    .col(integer(Mods::Id).primary_key())
+                    .col(string_uniq(Mods::Name))
+                    .col(string_null(Mods::Version))
+                    .col(boolean(Mods::Active).default(true))
+                    .to_owned()
+            )
+            .await?;
+
+        let mut insert = Query::insert()
+            .into_table(Mods::Table)
+            .columns([Mods::Id, Mods::Name])
+            .on_conflict(OnConflict::column(Mods::Id).do_nothing().to_owned())
+            .to_owned();
+        for (id, name) in MODS {
+            insert.values_panic([id.into(), name.into()]);
+        }
+        manager.exec_stmt(insert).await?;
+
+        for (name, table, column) in mod_references() {
+            manager
+                .create_foreign_key(
+                    ForeignKey::create()
+                        .name(name)
+                        .from(table, column)
+                        .to(Mods::Table, Mods::Id)
+                        .to_owned()
+                )
+                .await?;
+        }
+
+        // Races without mod are shared by all mods
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Races::Table)
+                    .add_column_if_not_exists(integer_null(RaceMod::ModId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_races_mod_id")
+                            .from_tbl(Races::Table)
+                            .from_col(RaceMod::ModId)
+                            .to_tbl(Mods::Table)
+                            .to_col(Mods::Id)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Races::Table)
+                    .drop_foreign_key(Alias::new("fk_races_mod_id"))
+                    .drop_column(RaceMod::ModId)
+                    .to_owned()
+            )
+            .await?;
+
+        for (name, table, _) in mod_references() {
+            manager
+                .drop_foreign_key(ForeignKey::drop().name(name).table(table).to_owned())
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(Mods::Table).to_owned())
+            .await
+    }
+}
+
+/// Columns that used to store `ModType` enum and now reference mods.
+fn mod_references() -> [(&'static str, DynIden, DynIden); 4] {
+    [
+        ("fk_tournaments_new_mod_type", Tournaments::Table.into_iden(), Tournaments::ModType.into_iden()),
+        ("fk_heroes_new_mod_type", HeroesNew::Table.into_iden(), HeroesNew::ModType.into_iden()),
+        ("fk_ratings_mod_type", Ratings::Table.into_iden(), Ratings::ModType.into_iden()),
+        ("fk_rating_history_mod_type", RatingHistory::Table.into_iden(), RatingHistory::ModType.into_iden())
+    ]
+}
+
+#[derive(DeriveIden)]
+pub enum Mods {
+    Table,
+    Id,
+    Name,
+    Version,
+    Active
+}
+
+#[derive(DeriveIden)]
+enum RaceMod {
+    ModId
+}
@@ -0,0 +1,129 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000004_create_tournaments::Tournaments, m20250610_000007_create_matches::Matches};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Brackets::Table)
+                    .if_not_exists()
+                    .col(uuid(Brackets::Id).primary_key())
+                    .col(uuid(Brackets::TournamentId))
+                    .col(integer(Brackets::Format))
+                    .col(integer_null(Brackets::GroupNumber))
+                    .col(integer(Brackets::BestOf).default(1))
+                    .col(timestamp_with_time_zone(Brackets::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_brackets_tournament_id")
+                            .from(Brackets::Table, Brackets::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        // Whole structure is created ahead of time, players of later rounds are filled as winners advance.
+        // Links to next matches aren't foreign keys, since they point into the same bracket deleted as a whole.
+        manager
+            .create_table(
+                Table::create()
+                    .table(BracketMatches::Table)
+                    .if_not_exists()
+                    .col(uuid(BracketMatches::Id).primary_key())
+                    .col(uuid(BracketMatches::BracketId))
+                    .col(integer(BracketMatches::Section))
+                    .col(integer(BracketMatches::Round))
+                    .col(integer(BracketMatches::Slot))
+                    .col(uuid_null(BracketMatches::FirstPlayer))
+                    .col(uuid_null(BracketMatches::SecondPlayer))
+                    .col(uuid_null(BracketMatches::Winner))
+                    .col(uuid_null(BracketMatches::MatchId))
+                    .col(uuid_null(BracketMatches::WinnerNext))
+                    .col(integer_null(BracketMatches::WinnerNextPosition))
+                    .col(uuid_null(BracketMatches::LoserNext))
+                    .col(integer_null(BracketMatches::LoserNextPosition))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bracket_matches_bracket_id")
+                            .from(BracketMatches::Table, BracketMatches::BracketId)
+                            .to(Brackets::Table, Brackets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_bracket_matches_match_id")
+                            .from(BracketMatches::Table, BracketMatches::MatchId)
+                            .to(Matches::Table, Matches::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_bracket_matches_position")
+                            .unique()
+                            .col(BracketMatches::BracketId)
+                            .col(BracketMatches::Section)
+                            .col(BracketMatches::Round)
+                            .col(BracketMatches::Slot)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bracket_matches_match_id")
+                    .table(BracketMatches::Table)
+                    .col(BracketMatches::MatchId)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BracketMatches::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Brackets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Brackets {
+    Table,
+    Id,
+    TournamentId,
+    Format,
+    GroupNumber,
+    BestOf,
+    CreatedAt
+}
+
+#[derive(DeriveIden)]
+pub enum BracketMatches {
+    Table,
+    Id,
+    BracketId,
+    Section,
+    Round,
+    Slot,
+    FirstPlayer,
+    SecondPlayer,
+    Winner,
+    MatchId,
+    WinnerNext,
+    WinnerNextPosition,
+    LoserNext,
+    LoserNextPosition
+}
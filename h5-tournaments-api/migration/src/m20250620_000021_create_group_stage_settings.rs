@@ -0,0 +1,45 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000004_create_tournaments::Tournaments;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Tournaments without settings row use defaults, so it's only created once organizer changes something
+        manager
+            .create_table(
+                Table::create()
+                    .table(GroupStageSettings::Table)
+                    .if_not_exists()
+                    .col(uuid(GroupStageSettings::TournamentId).primary_key())
+                    .col(integer(GroupStageSettings::QualifiersPerGroup).default(2))
+                    .col(json_binary(GroupStageSettings::Tiebreakers).default(Expr::cust(r#"'["MATCH_WINS", "GAME_DIFFERENCE", "HEAD_TO_HEAD"]'::jsonb"#)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_group_stage_settings_tournament_id")
+                            .from(GroupStageSettings::Table, GroupStageSettings::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GroupStageSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroupStageSettings {
+    Table,
+    TournamentId,
+    QualifiersPerGroup,
+    Tiebreakers
+}
@@ -0,0 +1,86 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000007_create_matches::Matches;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Matches created by api ahead of time or migrated from legacy tables have no challonge counterpart,
+        // ones created ahead of time have no report message yet either
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Matches::Table)
+                    .modify_column(ColumnDef::new(Matches::ChallongeId).string().null())
+                    .modify_column(ColumnDef::new(Matches::MessageId).big_integer().null())
+                    .add_column_if_not_exists(integer_null(MatchSchedule::Round))
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(MatchSchedule::Deadline))
+                    .add_column_if_not_exists(timestamp_with_time_zone(MatchSchedule::CreatedAt).default(Expr::current_timestamp()))
+                    .to_owned()
+            )
+            .await?;
+
+        let clear_empty = Query::update()
+            .table(Matches::Table)
+            .value(Matches::ChallongeId, Option::<String>::None)
+            .and_where(Expr::col(Matches::ChallongeId).eq(""))
+            .to_owned();
+        manager.exec_stmt(clear_empty).await?;
+
+        let connection = manager.get_connection();
+        // messages are discord snowflakes, milliseconds since discord epoch are kept in their upper bits
+        connection
+            .execute_unprepared("UPDATE matches SET created_at = to_timestamp(((message_id >> 22) + 1420070400000) / 1000.0) WHERE message_id IS NOT NULL")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_matches_created_at")
+                    .table(Matches::Table)
+                    .col(MatchSchedule::CreatedAt)
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let restore_empty = Query::update()
+            .table(Matches::Table)
+            .value(Matches::ChallongeId, "")
+            .and_where(Expr::col(Matches::ChallongeId).is_null())
+            .to_owned();
+        manager.exec_stmt(restore_empty).await?;
+
+        let restore_message = Query::update()
+            .table(Matches::Table)
+            .value(Matches::MessageId, 0)
+            .and_where(Expr::col(Matches::MessageId).is_null())
+            .to_owned();
+        manager.exec_stmt(restore_message).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Matches::Table)
+                    .modify_column(ColumnDef::new(Matches::ChallongeId).string().not_null())
+                    .modify_column(ColumnDef::new(Matches::MessageId).big_integer().not_null())
+                    .drop_column(MatchSchedule::Round)
+                    .drop_column(MatchSchedule::Deadline)
+                    .drop_column(MatchSchedule::CreatedAt)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MatchSchedule {
+    Round,
+    Deadline,
+    CreatedAt
+}
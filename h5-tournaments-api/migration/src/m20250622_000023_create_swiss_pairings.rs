@@ -0,0 +1,68 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::{m20250610_000004_create_tournaments::Tournaments, m20250610_000007_create_matches::Matches};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Pairing without second player is a bye, it has no tournament match.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SwissPairings::Table)
+                    .if_not_exists()
+                    .col(uuid(SwissPairings::Id).primary_key())
+                    .col(uuid(SwissPairings::TournamentId))
+                    .col(integer(SwissPairings::Round))
+                    .col(integer(SwissPairings::Board))
+                    .col(uuid(SwissPairings::FirstPlayer))
+                    .col(uuid_null(SwissPairings::SecondPlayer))
+                    .col(uuid_null(SwissPairings::MatchId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_swiss_pairings_tournament_id")
+                            .from(SwissPairings::Table, SwissPairings::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_swiss_pairings_match_id")
+                            .from(SwissPairings::Table, SwissPairings::MatchId)
+                            .to(Matches::Table, Matches::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_swiss_pairings_board")
+                            .unique()
+                            .col(SwissPairings::TournamentId)
+                            .col(SwissPairings::Round)
+                            .col(SwissPairings::Board)
+                    )
+                    .to_owned()
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SwissPairings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SwissPairings {
+    Table,
+    Id,
+    TournamentId,
+    Round,
+    Board,
+    FirstPlayer,
+    SecondPlayer,
+    MatchId
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000004_create_tournaments::Tournaments;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing tournaments keep their stages without history, stage isn't checked for ones that are still unknown.
+        manager
+            .create_table(
+                Table::create()
+                    .table(TournamentStageChanges::Table)
+                    .if_not_exists()
+                    .col(uuid(TournamentStageChanges::Id).primary_key())
+                    .col(uuid(TournamentStageChanges::TournamentId))
+                    .col(integer_null(TournamentStageChanges::PreviousStage))
+                    .col(integer(TournamentStageChanges::Stage))
+                    .col(timestamp_with_time_zone(TournamentStageChanges::ChangedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tournament_stage_changes_tournament_id")
+                            .from(TournamentStageChanges::Table, TournamentStageChanges::TournamentId)
+                            .to(Tournaments::Table, Tournaments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned()
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tournament_stage_changes_tournament_id")
+                    .table(TournamentStageChanges::Table)
+                    .col(TournamentStageChanges::TournamentId)
+                    .col(TournamentStageChanges::ChangedAt)
+                    .to_owned()
+            )
+            .await?;
+
+        // stages are numbered in order of tournament lifecycle now, group stage and play-off were 1 and 2 before
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE tournaments_new SET stage = CASE stage WHEN 1 THEN 4 WHEN 2 THEN 5 ELSE stage END WHERE stage IS NOT NULL")
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // stages that didn't exist before become unknown
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE tournaments_new SET stage = CASE stage WHEN 4 THEN 1 WHEN 5 THEN 2 ELSE 0 END WHERE stage IS NOT NULL")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TournamentStageChanges::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum TournamentStageChanges {
+    Table,
+    Id,
+    TournamentId,
+    PreviousStage,
+    Stage,
+    ChangedAt
+}
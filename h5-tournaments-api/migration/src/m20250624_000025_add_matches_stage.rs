@@ -0,0 +1,43 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20250610_000007_create_matches::Matches;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Matches::Table)
+                    .add_column_if_not_exists(integer_null(MatchStage::Stage))
+                    .to_owned()
+            )
+            .await?;
+
+        // stage of matches reported before it was recorded is unknown, current stage of their tournament is the closest guess
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE matches SET stage = tournaments_new.stage FROM tournaments_new WHERE tournaments_new.id = matches.tournament_id")
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Matches::Table)
+                    .drop_column(MatchStage::Stage)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MatchStage {
+    Stage
+}
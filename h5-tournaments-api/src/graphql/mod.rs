@@ -1,2 +1,50 @@
+use async_graphql::{dataloader::DataLoader, BatchRequest, BatchResponse, Schema};
+use axum::{Extension, Json};
+use sea_orm::DatabaseConnection;
+
+use crate::prelude::{AuthContext, AuthService, BracketService, CatalogueService, ExportService, GamesLoader, GroupDrawService, ImportService, LegacyMigrationService, HeroesLoader, MatchLoader, ParticipantsLoader, RatingService, StandingsService, StatisticsService, SwissService, TournamentLoader, TournamentService, UserLoader};
+
+use self::{mutation::Mutation, query::Query, subscription::{EventBroker, Subscription}};
+
 pub mod query;
-pub mod mutation;
\ No newline at end of file
+pub mod mutation;
+pub mod subscription;
+pub(crate) mod pagination;
+
+pub type H5Schema = Schema<Query, Mutation, Subscription>;
+
+/// Schema with all services, loaders and event broker registered.
+pub fn build_schema(db: DatabaseConnection) -> H5Schema {
+    Schema::build(Query, Mutation, Subscription)
+        .data(DataLoader::new(UserLoader { db: db.clone() }, tokio::spawn))
+        .data(DataLoader::new(TournamentLoader { db: db.clone() }, tokio::spawn))
+        .data(DataLoader::new(MatchLoader { db: db.clone() }, tokio::spawn))
+        .data(DataLoader::new(GamesLoader { db: db.clone() }, tokio::spawn))
+        .data(DataLoader::new(ParticipantsLoader { db: db.clone() }, tokio::spawn))
+        .data(DataLoader::new(HeroesLoader { db: db.clone() }, tokio::spawn))
+        .data(db)
+        .data(TournamentService {})
+        .data(StatisticsService {})
+        .data(RatingService {})
+        .data(AuthService {})
+        .data(ExportService {})
+        .data(ImportService {})
+        .data(LegacyMigrationService {})
+        .data(CatalogueService {})
+        .data(BracketService {})
+        .data(StandingsService {})
+        .data(GroupDrawService {})
+        .data(SwissService {})
+        .data(EventBroker::default())
+        .finish()
+}
+
+/// Executes graphql request on behalf of api token resolved by `authenticate` middleware.
+pub async fn graphql_handler(
+    Extension(schema): Extension<H5Schema>,
+    auth: Option<Extension<AuthContext>>,
+    Json(request): Json<BatchRequest>
+) -> Json<BatchResponse> {
+    let auth = auth.map(|Extension(auth)| auth).unwrap_or_default();
+    Json(schema.execute_batch(request.data(auth)).await)
+}
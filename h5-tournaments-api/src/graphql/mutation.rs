@@ -1,9 +1,11 @@
-use async_graphql::Context;
-use sea_orm::DatabaseConnection;
+use std::collections::{hash_map::Entry, HashMap};
+
+use async_graphql::{Context, ErrorExtensions};
+use sea_orm::{prelude::DateTimeWithTimeZone, DatabaseConnection};
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 
-use crate::{prelude::{ModType, TournamentService}, services::tournament::models::{game_builder::{BargainsColor, CreateGameModel, GameOutcome, GameResult}, organizer::OrganizerModel, tournament::{self, GameType}, tournament_builder::{TournamentBuilderModel, TournamentEditState}, user::{UserBulkUpdatePayload, UserModel}}};
+use crate::{graphql::subscription::{publish_event, TournamentEvent}, prelude::{ApiRole, ApiTokenModel, AuthContext, AuthService, BracketFormat, BracketModel, BracketService, CatalogueService, GroupDrawInput, GroupDrawModel, GroupDrawService, GroupMatchesInput, GroupStageSettingsInput, GroupStageSettingsModel, HeroUpdateInput, LocalizedNameInput, ModUpdateInput, RaceUpdateInput, ImportReportModel, ImportService, IssuedApiTokenModel, LegacyMigrationReportModel, LegacyMigrationService, TournamentExportModel, ModId, RatingService, RoleGuard, StandingsService, SwissPairingModel, SwissService, TournamentService}, services::tournament::models::{game_builder::{BargainsColor, CreateGameModel, GameOutcome, GameResult}, game_mod::GameModModel, heroes::HeroNew, match_structure::MatchModel, race::RaceModel, tournament::{self, GameType}, tournament_builder::{TournamentBuilderModel, TournamentEditState}, user::{UserBulkUpdatePayload, UserModel}}};
 
 pub struct Mutation;
 
@@ -11,11 +13,13 @@ pub struct Mutation;
 pub struct UpdateParticipant {
     pub user_id: Uuid,
     pub tournament_id: Uuid,
-    pub challonge_id: String
+    pub challonge_id: Option<String>,
+    pub group_number: Option<i32>
 }
 
 #[async_graphql::Object]
 impl Mutation {
+    #[graphql(guard = "RoleGuard::new(ApiRole::Bot)")]
     async fn create_user<'a>(
         &self,
         context: &Context<'a>,
@@ -27,7 +31,7 @@ impl Mutation {
         // confirm_register: bool
         #[graphql(desc = "User's discord nickname")]
         discord_nick: String
-    ) -> Result<UserModel, String> {
+    ) -> Result<UserModel, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         match service.create_user(db, name, discord_id, discord_nick).await {
@@ -35,18 +39,19 @@ impl Mutation {
                 Ok(model)
             },
             Err(db_error) => {
-                Err(db_error.to_string())
+                Err(db_error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Bot)")]
     async fn update_user<'a>(
         &self,
         context: &Context<'a>,
         id: Uuid,
         nickname: Option<String>,
         registered: Option<bool>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.update_user(db, id, nickname, registered).await;
@@ -55,11 +60,12 @@ impl Mutation {
                 Ok("User updated successfully".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
     
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn create_tournament<'a>(
         &self,
         context: &Context<'a>,
@@ -73,10 +79,11 @@ impl Mutation {
         role: String,
         organizer: Uuid,
         game_type: GameType,
-        mod_type: ModType
-    ) -> Result<String, String> {
+        mod_type: ModId
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        AuthContext::of(context).require_scope(organizer, operator_id).map_err(|error| error.extend())?;
         let res = service.create_tournament(
             db, 
             name, 
@@ -96,31 +103,39 @@ impl Mutation {
                 Ok(res)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_tournament<'a>(
         &self,
         context: &Context<'a>,
         id: Uuid,
+        #[graphql(desc = "Next stage of tournament lifecycle, stages can't be skipped or reverted")]
         stage: Option<tournament::TournamentStage>,
         challonge_id: Option<String>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [id]).await.map_err(|error| error.extend())?;
         let res = service.update_tournament(db, id, stage, challonge_id).await;
         match res {
-            Ok(_res) => {
+            Ok(tournament) => {
+                if stage.is_some() {
+                    publish_event(context, TournamentEvent::StageChanged(tournament));
+                }
                 Ok("Tournament was updated.".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn create_match<'a>(
         &self,
         context: &Context<'a>,
@@ -128,36 +143,65 @@ impl Mutation {
         message: i64,
         first_player: Uuid,
         second_player: Uuid,
-        challonge_id: String
-    ) -> Result<Uuid, String> {
+        #[graphql(desc = "Match with this challonge id is returned if it exists already")]
+        challonge_id: Option<String>
+    ) -> Result<Uuid, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
         let res = service.create_match(db, tournament_id, message, first_player, second_player, challonge_id).await;
         match res {
             Ok(res) => {
                 Ok(res)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Creates round robin matches of tournament groups with their rounds and deadlines.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn generate_group_matches<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        input: GroupMatchesInput
+    ) -> Result<Vec<MatchModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
+        match service.generate_group_matches(db, tournament_id, input).await {
+            Ok(matches) => {
+                Ok(matches)
+            },
+            Err(error) => {
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_match<'a>(
         &self,
         context: &Context<'a>,
         id: Uuid,
         report_link: String
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_matches(db, &AuthContext::of(context), [id]).await.map_err(|error| error.extend())?;
         let res = service.update_match(db, id, report_link).await;
         match res {
-            Ok(_res) => {
+            Ok(match_model) => {
+                publish_event(context, TournamentEvent::MatchReported(match_model));
                 Ok("Match updated".to_string())
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -181,6 +225,7 @@ impl Mutation {
     //     }
     // }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_game<'a>(
         &self,
         context: &Context<'a>,
@@ -193,9 +238,11 @@ impl Mutation {
         bargains_amount: Option<i32>,
         result: Option<GameResult>,
         outcome: Option<GameOutcome>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_game(db, &AuthContext::of(context), id).await.map_err(|error| error.extend())?;
         let res = service.update_game(
             db, 
             id, 
@@ -210,61 +257,207 @@ impl Mutation {
         ).await;
         match res {
             Ok(_res) => {
+                let bracket_service = context.data::<BracketService>().unwrap();
+                if let Err(error) = bracket_service.record_game_result(db, id).await {
+                    tracing::error!("Failed to advance bracket after game {} was updated: {}", id, error);
+                }
                 Ok(_res)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn create_games_bulk<'a>(
         &self,
         context: &Context<'a>,
         games: Vec<CreateGameModel>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_matches(db, &AuthContext::of(context), games.iter().map(|game| game.match_id)).await.map_err(|error| error.extend())?;
         let res = service.create_games_bulk(db, games).await;
 
         match res {
-            Ok(_res) => {
+            Ok(games) => {
+                let bracket_service = context.data::<BracketService>().unwrap();
+                let mut tournaments = HashMap::new();
+                for game in games {
+                    if let Entry::Vacant(entry) = tournaments.entry(game.match_id) {
+                        entry.insert(service.get_match(db, game.match_id).await.ok().flatten().map(|match_model| match_model.tournament_id));
+                        if let Err(error) = bracket_service.record_match_result(db, game.match_id).await {
+                            tracing::error!("Failed to advance bracket after games of match {} were created: {}", game.match_id, error);
+                        }
+                    }
+                    if let Some(tournament_id) = tournaments[&game.match_id] {
+                        publish_event(context, TournamentEvent::GameCreated { tournament_id, game });
+                    }
+                }
+                let rating_service = context.data::<RatingService>().unwrap();
+                if let Err(error) = rating_service.update_ratings(db).await {
+                    tracing::error!("Failed to rate reported matches: {}", error);
+                }
                 Ok("Games bulk inserted ok".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Builds bracket of tournament participants or of a single group of them.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn generate_bracket<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        format: BracketFormat,
+        #[graphql(desc = "Games every match is played to, must be odd", default = 1)]
+        best_of: i32,
+        #[graphql(desc = "Group of participants to build bracket for, all participants are used if not set")]
+        group_number: Option<i32>,
+        #[graphql(desc = "Users ordered from the best seed, participants that aren't listed are seeded by rating")]
+        seeds: Option<Vec<Uuid>>
+    ) -> Result<BracketModel, async_graphql::Error> {
+        let service = context.data::<BracketService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
+        match service.generate_bracket(db, tournament_id, format, best_of, group_number, seeds.unwrap_or_default()).await {
+            Ok(bracket) => {
+                Ok(bracket)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Removes bracket, its matches that have no games yet are removed too.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn delete_bracket<'a>(
+        &self,
+        context: &Context<'a>,
+        id: Uuid
+    ) -> Result<String, async_graphql::Error> {
+        let service = context.data::<BracketService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_bracket(db, &AuthContext::of(context), id).await.map_err(|error| error.extend())?;
+        match service.delete_bracket(db, id).await {
+            Ok(()) => {
+                Ok("Bracket deleted".to_string())
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Splits all participants of tournament into groups, previous group numbers are overwritten.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn draw_groups<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        input: GroupDrawInput
+    ) -> Result<GroupDrawModel, async_graphql::Error> {
+        let service = context.data::<GroupDrawService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
+        match service.draw_groups(db, tournament_id, input).await {
+            Ok(draw) => {
+                Ok(draw)
+            },
+            Err(error) => {
+                Err(error.extend())
             }
         }
     }
 
+    /// Pairs the next swiss round of all tournament participants and creates its matches.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn pair_swiss_round<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        #[graphql(desc = "Deadline of every match of the round")]
+        deadline: Option<DateTimeWithTimeZone>
+    ) -> Result<Vec<SwissPairingModel>, async_graphql::Error> {
+        let service = context.data::<SwissService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
+        match service.pair_next_round(db, tournament_id, deadline).await {
+            Ok(pairings) => {
+                Ok(pairings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn update_group_stage_settings<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        changes: GroupStageSettingsInput
+    ) -> Result<GroupStageSettingsModel, async_graphql::Error> {
+        let service = context.data::<StandingsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
+        match service.update_settings(db, tournament_id, changes).await {
+            Ok(settings) => {
+                Ok(settings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn create_participant<'a>(
         &self,
         context: &Context<'a>,
         tournament_id: Uuid,
         user_id: Uuid,
         challonge_id: String
-    ) -> Result<u64, String> {
+    ) -> Result<u64, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
         match service.create_participant(db, tournament_id, user_id, challonge_id).await {
-            Ok(participants_count) => {
+            Ok((participant, participants_count)) => {
+                publish_event(context, TournamentEvent::ParticipantRegistered(participant));
                 Ok(participants_count)
             },
             Err(db_error) => {
-                Err(db_error.to_string())
+                Err(db_error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_participant<'a>(
         &self,
         context: &Context<'a>,
         id: Uuid,
         group: Option<i32>,
         challonge_id: Option<String>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_participant(db, &AuthContext::of(context), id).await.map_err(|error| error.extend())?;
         let res = service.update_participant(db, id, group, challonge_id).await;
 
         match res {
@@ -272,11 +465,12 @@ impl Mutation {
                 Ok("Participant updated".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn delete_participant<'a>(
         &self,
         context: &Context<'a>,
@@ -284,26 +478,30 @@ impl Mutation {
         id: Option<Uuid>,
         user_id: Option<Uuid>,
         challonge_id: Option<String>
-    ) -> Result<u64, String> {
+    ) -> Result<u64, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), [tournament_id]).await.map_err(|error| error.extend())?;
         let res = service.delete_participant(db, tournament_id, id, user_id, challonge_id).await;
 
         match res {
-            Ok(res) => {
-                Ok(res)
+            Ok((participant, participants_count)) => {
+                publish_event(context, TournamentEvent::ParticipantRemoved(participant));
+                Ok(participants_count)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn create_tournament_builder<'a>(
         &self,
         context: &Context<'a>,
         message_id: String
-    ) -> Result<TournamentBuilderModel, String> {
+    ) -> Result<TournamentBuilderModel, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.create_tournament_builder(db, message_id).await;
@@ -313,17 +511,18 @@ impl Mutation {
                 Ok(_res)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
     async fn create_organizer<'a>(
         &self,
         context: &Context<'a>,
         discord_id: String,
         challonge_key: String
-    ) -> Result<Uuid, String> {
+    ) -> Result<Uuid, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.create_organizer(db, discord_id, challonge_key).await;
@@ -333,11 +532,12 @@ impl Mutation {
                 Ok(_res)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_tournament_builder<'a>(
         &self,
         context: &Context<'a>,
@@ -350,7 +550,7 @@ impl Mutation {
         use_bargains: Option<bool>,
         use_bargains_color: Option<bool>,
         use_foreign_heroes: Option<bool>
-    ) -> Result<TournamentBuilderModel, String> {
+    ) -> Result<TournamentBuilderModel, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.update_tournament_builder(
@@ -371,18 +571,21 @@ impl Mutation {
                 Ok(_res)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
     async fn update_participants_bulk<'a>(
         &self,
         context: &Context<'a>,
         participants: Vec<UpdateParticipant>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
+        let auth_service = context.data::<AuthService>().unwrap();
+        auth_service.authorize_tournaments(db, &AuthContext::of(context), participants.iter().map(|participant| participant.tournament_id)).await.map_err(|error| error.extend())?;
         let res = service.participants_bulk_update(db, participants).await;
 
         match res {
@@ -390,16 +593,17 @@ impl Mutation {
                 Ok("Participants were updated successfully".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
 
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
     async fn update_users_bulk<'a>(
         &self,
         context: &Context<'a>,
         users: Vec<UserBulkUpdatePayload>
-    ) -> Result<String, String> {
+    ) -> Result<String, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.users_bulk_update(db, users).await;
@@ -409,7 +613,309 @@ impl Mutation {
                 Ok("Users were updated successfully".to_string())
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Rates reported matches that weren't rated yet, returns count of rated matches.
+    /// Matches are rated when their games are reported, so it's only needed if that failed.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn update_ratings<'a>(
+        &self,
+        context: &Context<'a>
+    ) -> Result<u64, async_graphql::Error> {
+        let service = context.data::<RatingService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.update_ratings(db).await {
+            Ok(processed) => {
+                Ok(processed)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Rebuilds all ratings from scratch, must be called after games of already rated matches were corrected.
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn recompute_ratings<'a>(
+        &self,
+        context: &Context<'a>
+    ) -> Result<u64, async_graphql::Error> {
+        let service = context.data::<RatingService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.recompute_ratings(db).await {
+            Ok(processed) => {
+                Ok(processed)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Recreates tournament from export document. Document is only checked for conflicts if `dry_run` is set.
+    #[graphql(guard = "RoleGuard::new(ApiRole::Organizer)")]
+    async fn import_tournament<'a>(
+        &self,
+        context: &Context<'a>,
+        document: async_graphql::Json<TournamentExportModel>,
+        dry_run: bool
+    ) -> Result<ImportReportModel, async_graphql::Error> {
+        let service = context.data::<ImportService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        AuthContext::of(context).require_scope(document.tournament.organizer_id, document.tournament.operator_id).map_err(|error| error.extend())?;
+        match service.import_tournament(db, document.0, dry_run).await {
+            Ok(report) => {
+                Ok(report)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Moves legacy tournament into current tables, ratings should be recomputed afterwards.
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn migrate_legacy_tournament<'a>(
+        &self,
+        context: &Context<'a>,
+        legacy_tournament_id: Uuid,
+        #[graphql(desc = "Organizer migrated tournament will belong to")]
+        organizer_id: Uuid
+    ) -> Result<LegacyMigrationReportModel, async_graphql::Error> {
+        let service = context.data::<LegacyMigrationService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.migrate_tournament(db, legacy_tournament_id, organizer_id).await {
+            Ok(report) => {
+                Ok(report)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Moves every legacy tournament into current tables, ratings should be recomputed afterwards.
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn migrate_legacy_tournaments<'a>(
+        &self,
+        context: &Context<'a>,
+        #[graphql(desc = "Organizer migrated tournaments will belong to")]
+        organizer_id: Uuid
+    ) -> Result<Vec<LegacyMigrationReportModel>, async_graphql::Error> {
+        let service = context.data::<LegacyMigrationService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.migrate_all(db, organizer_id).await {
+            Ok(reports) => {
+                Ok(reports)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn create_mod<'a>(
+        &self,
+        context: &Context<'a>,
+        name: String,
+        version: Option<String>
+    ) -> Result<GameModModel, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.create_mod(db, name, version).await {
+            Ok(game_mod) => {
+                Ok(game_mod)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Mods are never deleted since tournaments refer to them, deactivate them instead.
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn update_mod<'a>(
+        &self,
+        context: &Context<'a>,
+        id: ModId,
+        changes: ModUpdateInput
+    ) -> Result<GameModModel, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.update_mod(db, id, changes).await {
+            Ok(game_mod) => {
+                Ok(game_mod)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn create_race<'a>(
+        &self,
+        context: &Context<'a>,
+        name: String,
+        #[graphql(desc = "Mod race belongs to, race is shared by all mods if not set")]
+        mod_type: Option<ModId>,
+        display_names: Option<Vec<LocalizedNameInput>>,
+        #[graphql(desc = "Alternative spellings report parser should accept")]
+        name_variants: Option<Vec<String>>
+    ) -> Result<RaceModel, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.create_race(db, name, mod_type, display_names.unwrap_or_default(), name_variants.unwrap_or_default()).await {
+            Ok(race) => {
+                Ok(race)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn update_race<'a>(
+        &self,
+        context: &Context<'a>,
+        id: i32,
+        changes: RaceUpdateInput
+    ) -> Result<RaceModel, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.update_race(db, id, changes).await {
+            Ok(race) => {
+                Ok(race)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn delete_race<'a>(
+        &self,
+        context: &Context<'a>,
+        id: i32
+    ) -> Result<String, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.delete_race(db, id).await {
+            Ok(()) => {
+                Ok("Race deleted".to_string())
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn create_hero<'a>(
+        &self,
+        context: &Context<'a>,
+        mod_type: ModId,
+        race: i32,
+        name: String,
+        display_names: Option<Vec<LocalizedNameInput>>,
+        #[graphql(desc = "Alternative spellings report parser should accept")]
+        name_variants: Option<Vec<String>>
+    ) -> Result<HeroNew, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.create_hero(db, mod_type, race, name, display_names.unwrap_or_default(), name_variants.unwrap_or_default()).await {
+            Ok(hero) => {
+                Ok(hero)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn update_hero<'a>(
+        &self,
+        context: &Context<'a>,
+        mod_type: ModId,
+        id: i32,
+        changes: HeroUpdateInput
+    ) -> Result<HeroNew, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.update_hero(db, mod_type, id, changes).await {
+            Ok(hero) => {
+                Ok(hero)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn delete_hero<'a>(
+        &self,
+        context: &Context<'a>,
+        mod_type: ModId,
+        id: i32
+    ) -> Result<String, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.delete_hero(db, mod_type, id).await {
+            Ok(()) => {
+                Ok("Hero deleted".to_string())
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Issues new api token, the token itself is returned only by this mutation.
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn issue_api_token<'a>(
+        &self,
+        context: &Context<'a>,
+        name: String,
+        role: ApiRole,
+        #[graphql(desc = "Organizer token is issued to, required for organizer tokens")]
+        organizer_id: Option<Uuid>,
+        #[graphql(desc = "Operator bot token is limited to, bot tokens without it can access every tournament")]
+        operator_id: Option<Uuid>
+    ) -> Result<IssuedApiTokenModel, async_graphql::Error> {
+        let service = context.data::<AuthService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.issue_token(db, name, role, organizer_id, operator_id).await {
+            Ok(token) => {
+                Ok(token)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn revoke_api_token<'a>(
+        &self,
+        context: &Context<'a>,
+        id: Uuid
+    ) -> Result<ApiTokenModel, async_graphql::Error> {
+        let service = context.data::<AuthService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.revoke_token(db, id).await {
+            Ok(token) => {
+                Ok(token)
+            },
+            Err(error) => {
+                Err(error.extend())
             }
         }
     }
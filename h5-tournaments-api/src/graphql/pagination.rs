@@ -0,0 +1,54 @@
+use std::future::Future;
+
+use async_graphql::{connection::{query, Connection, CursorType, Edge, EmptyFields}, Context, ErrorExtensions, OutputType};
+
+use crate::services::{pagination::{Cursor, Page, PageArguments}, tournament::error::Error};
+
+#[derive(Debug, async_graphql::SimpleObject)]
+pub struct ConnectionTotal {
+    /// Count of all items matching filter
+    pub total_count: usize
+}
+
+pub type CountedConnection<T> = Connection<Cursor, T, ConnectionTotal, EmptyFields>;
+
+impl CursorType for Cursor {
+    type Error = Error;
+
+    fn decode_cursor(cursor: &str) -> Result<Self, Self::Error> {
+        Cursor::decode(cursor)
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.encode()
+    }
+}
+
+/// Resolves relay connection arguments, cursors are opaque positions of items in the ordered list.
+/// Items are only counted if `totalCount` is selected.
+pub(crate) async fn connection<T, F, Fut>(
+    context: &Context<'_>,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    fetch: F
+) -> Result<CountedConnection<T>, async_graphql::Error>
+where
+    T: OutputType,
+    F: FnOnce(PageArguments) -> Fut,
+    Fut: Future<Output = Result<Page<T>, Error>>
+{
+    let with_total = context.look_ahead().field("totalCount").exists();
+    query(after, before, first, last, |after, before, first, last| async move {
+        let page = fetch(PageArguments { after, before, first, last, with_total }).await.map_err(|error| error.extend())?;
+        let mut connection = Connection::with_additional_fields(
+            page.has_previous,
+            page.has_next,
+            ConnectionTotal { total_count: page.total }
+        );
+        connection.edges.extend(page.items.into_iter().map(|(cursor, item)| Edge::new(cursor, item)));
+        Ok::<_, async_graphql::Error>(connection)
+    })
+    .await
+}
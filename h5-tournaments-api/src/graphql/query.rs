@@ -1,8 +1,8 @@
-use async_graphql::Context;
-use sea_orm::{error, DatabaseConnection};
+use async_graphql::{Context, ErrorExtensions};
+use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::{prelude::{ModType, TournamentService}, routes::tournament, services::tournament::models::{game_builder::GameModel, hero::HeroModel, heroes::HeroesModel, match_structure::MatchModel, operator::TournamentOperatorModel, organizer::OrganizerModel, participant, tournament::TournamentModel, tournament_builder::TournamentBuilderModel, user::UserModel}};
+use crate::{graphql::pagination::{connection, CountedConnection}, prelude::{ApiRole, ApiTokenModel, AuthService, RoleGuard, BargainsStatisticsModel, BracketModel, BracketService, CatalogueModel, CatalogueService, ExportService, GamesFilter, GroupStageSettingsModel, GroupStandingsModel, HeadToHeadModel, LegacyMappingKind, LegacyMappingModel, LegacyMigrationService, HeroStatisticsModel, MatchesFilter, MatchupsStatisticsModel, ModId, ParticipantsFilter, PlayerProfileModel, RatingHistoryModel, RatingModel, RatingService, SortDirection, StandingsService, StatisticsFilter, StatisticsService, SwissPairingModel, SwissService, SwissStandingsEntryModel, TournamentExportModel, TournamentService, TournamentsFilter, TournamentsOrderField, UsersFilter, UsersOrderField}, services::tournament::models::{game_builder::GameModel, game_mod::GameModModel, hero::HeroModel, heroes::HeroesModel, match_structure::MatchModel, race::RaceModel, operator::TournamentOperatorModel, organizer::OrganizerModel, participant, tournament::TournamentModel, tournament_builder::TournamentBuilderModel, user::UserModel}};
 
 pub struct Query;
 
@@ -13,7 +13,7 @@ impl Query {
         context: &Context<'a>,
         id: Option<Uuid>,
         server_id: Option<i64>
-    ) -> Result<Option<TournamentOperatorModel>, String> {
+    ) -> Result<Option<TournamentOperatorModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         match service.get_operator(db, id, server_id).await {
@@ -21,7 +21,7 @@ impl Query {
                 Ok(operator)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -35,7 +35,7 @@ impl Query {
         reports_channel_id: Option<String>,
         #[graphql(desc = "Unique register channel of tournament")]
         register_channel_id: Option<String>
-    ) -> Result<Option<TournamentModel>, String> {
+    ) -> Result<Option<TournamentModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_tournament(db, id, reports_channel_id, register_channel_id).await;
@@ -45,7 +45,7 @@ impl Query {
                 Ok(tournament)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
@@ -53,7 +53,7 @@ impl Query {
     async fn tournaments_all<'a>(
         &self,
         context: &Context<'a>
-    ) -> Result<Vec<TournamentModel>, String> {
+    ) -> Result<Vec<TournamentModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_tournaments(db).await;
@@ -63,7 +63,7 @@ impl Query {
                 Ok(tournaments)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -75,7 +75,7 @@ impl Query {
         id: Option<Uuid>,
         #[graphql(desc = "User's discord id")]
         discord_id: Option<String>
-    ) -> Result<Option<UserModel>, String> {
+    ) -> Result<Option<UserModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_user(db, id, discord_id).await;
@@ -85,7 +85,7 @@ impl Query {
                 Ok(user)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
@@ -94,7 +94,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         id: Uuid
-    ) -> Result<Option<MatchModel>, String> {
+    ) -> Result<Option<MatchModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_match(db, id).await;
@@ -104,7 +104,7 @@ impl Query {
                 Ok(model)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
@@ -114,7 +114,7 @@ impl Query {
         context: &Context<'a>,
         tournament_id: Uuid,
         user_id: Option<Uuid>
-    ) -> Result<Vec<MatchModel>, String> {
+    ) -> Result<Vec<MatchModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_matches(db, tournament_id, user_id).await;
@@ -123,7 +123,7 @@ impl Query {
                 Ok(matches)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -132,7 +132,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         tournament_id: Uuid
-    ) -> Result<Vec<UserModel>, String> {
+    ) -> Result<Vec<UserModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_users(db, tournament_id).await;
@@ -142,7 +142,7 @@ impl Query {
                 Ok(users)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -171,7 +171,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         race: i32
-    ) -> Result<Vec<HeroModel>, String> {
+    ) -> Result<Vec<HeroModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_heroes(db, race).await;
@@ -181,7 +181,7 @@ impl Query {
                 Ok(heroes)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
@@ -190,7 +190,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         id: i32
-    ) -> Result<Option<HeroModel>, String> {
+    ) -> Result<Option<HeroModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_hero(db, id).await;
@@ -200,7 +200,7 @@ impl Query {
                 Ok(hero)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         }
     }
@@ -209,7 +209,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         match_id: Uuid
-    ) -> Result<Vec<GameModel>, String> {
+    ) -> Result<Vec<GameModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_games(db, match_id).await;
@@ -219,7 +219,7 @@ impl Query {
                 Ok(games)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -229,7 +229,7 @@ impl Query {
         context: &Context<'a>,
         tournament_id: Uuid,
         group: i32
-    ) -> Result<Vec<UserModel>, String> {
+    ) -> Result<Vec<UserModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_participants(db, tournament_id, group).await;
@@ -239,7 +239,7 @@ impl Query {
                 Ok(users)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -250,7 +250,7 @@ impl Query {
         tournament_id: Option<Uuid>,
         user_id: Option<Uuid>,
         challonge: Option<String>
-    ) -> Result<Option<participant::Model>, String> {
+    ) -> Result<Option<participant::Model>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_participant(db, user_id, tournament_id, challonge).await;
@@ -260,7 +260,7 @@ impl Query {
                 Ok(user)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -271,7 +271,7 @@ impl Query {
         id: Option<Uuid>,
         discord_id: Option<i64>,
         challonge_key: Option<String> 
-    ) -> Result<Option<OrganizerModel>, String> {
+    ) -> Result<Option<OrganizerModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_organizer(db, id, discord_id, challonge_key).await;
@@ -281,7 +281,7 @@ impl Query {
                 Ok(model)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -291,7 +291,7 @@ impl Query {
         context: &Context<'a>,
         id: Option<Uuid>,
         message: Option<i64>
-    ) -> Result<Option<TournamentBuilderModel>, String> {
+    ) -> Result<Option<TournamentBuilderModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_tournament_builder(db, id, message).await;
@@ -301,7 +301,7 @@ impl Query {
                 Ok(model)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -310,7 +310,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         organizer_id: Uuid
-    ) -> Result<Vec<TournamentModel>, String> {
+    ) -> Result<Vec<TournamentModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_tournaments_by_organizer(db, organizer_id).await;
@@ -320,7 +320,7 @@ impl Query {
                 Ok(models)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -329,7 +329,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         tournament_id: Uuid
-    ) -> Result<Vec<UserModel>, String> {
+    ) -> Result<Vec<UserModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_users_by_tournament(db, tournament_id).await;
@@ -339,7 +339,7 @@ impl Query {
                 Ok(models)
             },
             Err(error) => {
-                Err(error)
+                Err(error.extend())
             }
         } 
     }
@@ -348,7 +348,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         match_id: Uuid
-    ) -> Result<u64, String> {
+    ) -> Result<u64, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         let res = service.get_games_count(db, match_id).await;
@@ -358,7 +358,7 @@ impl Query {
                 Ok(res)
             },
             Err(error) => {
-                Err(error.to_string())
+                Err(error.extend())
             }
         }
     }
@@ -366,8 +366,8 @@ impl Query {
     async fn heroes_new<'a>(
         &self,
         context: &Context<'a>,
-        mod_type: ModType
-    ) -> Result<HeroesModel, String> {
+        mod_type: ModId
+    ) -> Result<HeroesModel, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         match service.get_heroes_new(db, mod_type).await {
@@ -375,7 +375,7 @@ impl Query {
                 Ok(heroes)
             },
             Err(db_error) => {
-                Err(db_error.to_string())
+                Err(db_error.extend())
             }
         }
     }
@@ -384,7 +384,7 @@ impl Query {
         &self,
         context: &Context<'a>,
         tournament_id: Uuid
-    ) -> Result<Vec<GameModel>, String> {
+    ) -> Result<Vec<GameModel>, async_graphql::Error> {
         let service = context.data::<TournamentService>().unwrap();
         let db = context.data::<DatabaseConnection>().unwrap();
         match service.get_all_games(db, tournament_id).await {
@@ -392,7 +392,464 @@ impl Query {
                 Ok(games)
             },
             Err(db_error) => {
-                Err(db_error.to_string())
+                Err(db_error.extend())
+            }
+        }
+    }
+
+    async fn race_matchups<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<StatisticsFilter>
+    ) -> Result<MatchupsStatisticsModel, async_graphql::Error> {
+        let service = context.data::<StatisticsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_race_matchups(db, filter.unwrap_or_default()).await {
+            Ok(statistics) => {
+                Ok(statistics)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn hero_stats<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<StatisticsFilter>,
+        #[graphql(desc = "Count only picks of heroes of foreign (true) or own (false) race")]
+        foreign_heroes: Option<bool>
+    ) -> Result<Vec<HeroStatisticsModel>, async_graphql::Error> {
+        let service = context.data::<StatisticsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_hero_statistics(db, filter.unwrap_or_default(), foreign_heroes).await {
+            Ok(statistics) => {
+                Ok(statistics)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn bargains_stats<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<StatisticsFilter>
+    ) -> Result<BargainsStatisticsModel, async_graphql::Error> {
+        let service = context.data::<StatisticsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_bargains_statistics(db, filter.unwrap_or_default()).await {
+            Ok(statistics) => {
+                Ok(statistics)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn player_profile<'a>(
+        &self,
+        context: &Context<'a>,
+        user_id: Uuid
+    ) -> Result<PlayerProfileModel, async_graphql::Error> {
+        let service = context.data::<StatisticsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_player_profile(db, user_id).await {
+            Ok(profile) => {
+                Ok(profile)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn mods<'a>(
+        &self,
+        context: &Context<'a>,
+        #[graphql(desc = "List only mods new tournaments can be created for")]
+        active_only: Option<bool>
+    ) -> Result<Vec<GameModModel>, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_mods(db, active_only.unwrap_or(false)).await {
+            Ok(mods) => {
+                Ok(mods)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn races<'a>(
+        &self,
+        context: &Context<'a>
+    ) -> Result<Vec<RaceModel>, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_races(db).await {
+            Ok(races) => {
+                Ok(races)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Races and heroes of mod with version of catalogue.
+    async fn catalogue<'a>(
+        &self,
+        context: &Context<'a>,
+        mod_type: ModId
+    ) -> Result<CatalogueModel, async_graphql::Error> {
+        let service = context.data::<CatalogueService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_catalogue(db, mod_type).await {
+            Ok(catalogue) => {
+                Ok(catalogue)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Links between legacy rows and rows they were migrated into.
+    async fn legacy_mappings<'a>(
+        &self,
+        context: &Context<'a>,
+        kind: Option<LegacyMappingKind>
+    ) -> Result<Vec<LegacyMappingModel>, async_graphql::Error> {
+        let service = context.data::<LegacyMigrationService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_mappings(db, kind).await {
+            Ok(mappings) => {
+                Ok(mappings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Whole tournament with participants, matches and games in a single versioned document.
+    async fn tournament_export<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid
+    ) -> Result<TournamentExportModel, async_graphql::Error> {
+        let service = context.data::<ExportService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.export_tournament(db, tournament_id).await {
+            Ok(export) => {
+                Ok(export)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn brackets<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid
+    ) -> Result<Vec<BracketModel>, async_graphql::Error> {
+        let service = context.data::<BracketService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_brackets(db, tournament_id).await {
+            Ok(brackets) => {
+                Ok(brackets)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn bracket<'a>(
+        &self,
+        context: &Context<'a>,
+        id: Uuid
+    ) -> Result<Option<BracketModel>, async_graphql::Error> {
+        let service = context.data::<BracketService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_bracket(db, id).await {
+            Ok(bracket) => {
+                Ok(bracket)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Tables of tournament groups ordered by group stage tiebreakers.
+    async fn group_standings<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        #[graphql(desc = "Group to build table for, tables of all groups are returned if not set")]
+        group: Option<i32>
+    ) -> Result<Vec<GroupStandingsModel>, async_graphql::Error> {
+        let service = context.data::<StandingsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_standings(db, tournament_id, group).await {
+            Ok(standings) => {
+                Ok(standings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn group_stage_settings<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid
+    ) -> Result<GroupStageSettingsModel, async_graphql::Error> {
+        let service = context.data::<StandingsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_settings(db, tournament_id).await {
+            Ok(settings) => {
+                Ok(settings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn swiss_pairings<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid,
+        #[graphql(desc = "Round to get pairings of, pairings of all rounds are returned if not set")]
+        round: Option<i32>
+    ) -> Result<Vec<SwissPairingModel>, async_graphql::Error> {
+        let service = context.data::<SwissService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_pairings(db, tournament_id, round).await {
+            Ok(pairings) => {
+                Ok(pairings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    /// Swiss table ordered by points, Buchholz and Sonneborn-Berger.
+    async fn swiss_standings<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Uuid
+    ) -> Result<Vec<SwissStandingsEntryModel>, async_graphql::Error> {
+        let service = context.data::<SwissService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_standings(db, tournament_id).await {
+            Ok(standings) => {
+                Ok(standings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn ratings<'a>(
+        &self,
+        context: &Context<'a>,
+        #[graphql(desc = "Mod to get ratings for, overall ratings are returned if not set")]
+        mod_type: Option<ModId>,
+        limit: Option<u64>
+    ) -> Result<Vec<RatingModel>, async_graphql::Error> {
+        let service = context.data::<RatingService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_ratings(db, mod_type, limit).await {
+            Ok(ratings) => {
+                Ok(ratings)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn rating<'a>(
+        &self,
+        context: &Context<'a>,
+        user_id: Uuid,
+        #[graphql(desc = "Mod to get rating for, overall rating is returned if not set")]
+        mod_type: Option<ModId>
+    ) -> Result<Option<RatingModel>, async_graphql::Error> {
+        let service = context.data::<RatingService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_rating(db, user_id, mod_type).await {
+            Ok(rating) => {
+                Ok(rating)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn rating_history<'a>(
+        &self,
+        context: &Context<'a>,
+        user_id: Uuid,
+        #[graphql(desc = "Mod to get rating history for, overall history is returned if not set")]
+        mod_type: Option<ModId>
+    ) -> Result<Vec<RatingHistoryModel>, async_graphql::Error> {
+        let service = context.data::<RatingService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_rating_history(db, user_id, mod_type).await {
+            Ok(history) => {
+                Ok(history)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    async fn head_to_head<'a>(
+        &self,
+        context: &Context<'a>,
+        user_a: Uuid,
+        user_b: Uuid
+    ) -> Result<HeadToHeadModel, async_graphql::Error> {
+        let service = context.data::<StatisticsService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_head_to_head(db, user_a, user_b).await {
+            Ok(head_to_head) => {
+                Ok(head_to_head)
+            },
+            Err(error) => {
+                Err(error.extend())
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn tournaments_connection<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<TournamentsFilter>,
+        #[graphql(default)]
+        order_by: TournamentsOrderField,
+        #[graphql(default)]
+        direction: SortDirection,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>
+    ) -> Result<CountedConnection<TournamentModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        connection(context, after, before, first, last, |page| {
+            service.get_tournaments_page(db, filter.unwrap_or_default(), order_by, direction, page)
+        }).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn users_connection<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<UsersFilter>,
+        #[graphql(default)]
+        order_by: UsersOrderField,
+        #[graphql(default)]
+        direction: SortDirection,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>
+    ) -> Result<CountedConnection<UserModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        connection(context, after, before, first, last, |page| {
+            service.get_users_page(db, filter.unwrap_or_default(), order_by, direction, page)
+        }).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn matches_connection<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<MatchesFilter>,
+        #[graphql(default)]
+        direction: SortDirection,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>
+    ) -> Result<CountedConnection<MatchModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        connection(context, after, before, first, last, |page| {
+            service.get_matches_page(db, filter.unwrap_or_default(), direction, page)
+        }).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn games_connection<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<GamesFilter>,
+        #[graphql(default)]
+        direction: SortDirection,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>
+    ) -> Result<CountedConnection<GameModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        connection(context, after, before, first, last, |page| {
+            service.get_games_page(db, filter.unwrap_or_default(), direction, page)
+        }).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn participants_connection<'a>(
+        &self,
+        context: &Context<'a>,
+        filter: Option<ParticipantsFilter>,
+        #[graphql(default)]
+        direction: SortDirection,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>
+    ) -> Result<CountedConnection<participant::TournamentParticipantModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        connection(context, after, before, first, last, |page| {
+            service.get_participants_page(db, filter.unwrap_or_default(), direction, page)
+        }).await
+    }
+
+    #[graphql(guard = "RoleGuard::unscoped(ApiRole::Bot)")]
+    async fn api_tokens<'a>(
+        &self,
+        context: &Context<'a>
+    ) -> Result<Vec<ApiTokenModel>, async_graphql::Error> {
+        let service = context.data::<AuthService>().unwrap();
+        let db = context.data::<DatabaseConnection>().unwrap();
+        match service.get_tokens(db).await {
+            Ok(tokens) => {
+                Ok(tokens)
+            },
+            Err(error) => {
+                Err(error.extend())
             }
         }
     }
@@ -0,0 +1,136 @@
+use async_graphql::{futures_util::{Stream, StreamExt}, Context};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use uuid::Uuid;
+
+use crate::services::tournament::models::{game_builder::GameModel, match_structure::MatchModel, participant::TournamentParticipantModel, tournament::TournamentModel};
+
+// Subscribers that fall behind this many events skip the missed ones
+const EVENTS_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum TournamentEvent {
+    MatchReported(MatchModel),
+    GameCreated {
+        tournament_id: Uuid,
+        game: GameModel
+    },
+    ParticipantRegistered(TournamentParticipantModel),
+    ParticipantRemoved(TournamentParticipantModel),
+    StageChanged(TournamentModel)
+}
+
+/// Delivers events published by mutations to all active subscriptions.
+#[derive(Clone)]
+pub struct EventBroker {
+    sender: broadcast::Sender<TournamentEvent>
+}
+
+impl Default for EventBroker {
+    fn default() -> Self {
+        EventBroker {
+            sender: broadcast::channel(EVENTS_CAPACITY).0
+        }
+    }
+}
+
+impl EventBroker {
+    pub fn publish(&self, event: TournamentEvent) {
+        // sending only fails if nobody is subscribed
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> impl Stream<Item = TournamentEvent> + use<> {
+        BroadcastStream::new(self.sender.subscribe())
+            .filter_map(|event| async move { event.ok() })
+    }
+}
+
+/// Publishes event if schema has a broker, mutations must not fail because nobody listens.
+pub(crate) fn publish_event(context: &Context<'_>, event: TournamentEvent) {
+    if let Some(broker) = context.data_opt::<EventBroker>() {
+        broker.publish(event);
+    }
+}
+
+fn matches_filter(filter: Option<Uuid>, id: Uuid) -> bool {
+    filter.is_none_or(|filter| filter == id)
+}
+
+pub struct Subscription;
+
+#[async_graphql::Subscription]
+impl Subscription {
+    /// Report link was attached to a match
+    async fn match_reported<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Option<Uuid>
+    ) -> impl Stream<Item = MatchModel> + use<> {
+        let broker = context.data::<EventBroker>().unwrap();
+        broker.subscribe().filter_map(move |event| async move {
+            match event {
+                TournamentEvent::MatchReported(match_model) if matches_filter(tournament_id, match_model.tournament_id) => Some(match_model),
+                _ => None
+            }
+        })
+    }
+
+    async fn game_created<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Option<Uuid>,
+        match_id: Option<Uuid>
+    ) -> impl Stream<Item = GameModel> + use<> {
+        let broker = context.data::<EventBroker>().unwrap();
+        broker.subscribe().filter_map(move |event| async move {
+            match event {
+                TournamentEvent::GameCreated { tournament_id: game_tournament_id, game }
+                    if matches_filter(tournament_id, game_tournament_id) && matches_filter(match_id, game.match_id) => Some(game),
+                _ => None
+            }
+        })
+    }
+
+    async fn participant_registered<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Option<Uuid>
+    ) -> impl Stream<Item = TournamentParticipantModel> + use<> {
+        let broker = context.data::<EventBroker>().unwrap();
+        broker.subscribe().filter_map(move |event| async move {
+            match event {
+                TournamentEvent::ParticipantRegistered(participant) if matches_filter(tournament_id, participant.tournament_id) => Some(participant),
+                _ => None
+            }
+        })
+    }
+
+    async fn participant_removed<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Option<Uuid>
+    ) -> impl Stream<Item = TournamentParticipantModel> + use<> {
+        let broker = context.data::<EventBroker>().unwrap();
+        broker.subscribe().filter_map(move |event| async move {
+            match event {
+                TournamentEvent::ParticipantRemoved(participant) if matches_filter(tournament_id, participant.tournament_id) => Some(participant),
+                _ => None
+            }
+        })
+    }
+
+    async fn tournament_stage_changed<'a>(
+        &self,
+        context: &Context<'a>,
+        tournament_id: Option<Uuid>
+    ) -> impl Stream<Item = TournamentModel> + use<> {
+        let broker = context.data::<EventBroker>().unwrap();
+        broker.subscribe().filter_map(move |event| async move {
+            match event {
+                TournamentEvent::StageChanged(tournament) if matches_filter(tournament_id, tournament.id) => Some(tournament),
+                _ => None
+            }
+        })
+    }
+}
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
-use async_graphql::{http::GraphiQLSource, EmptySubscription, Schema};
-use async_graphql_axum::GraphQL;
-use axum::{response::{Html, IntoResponse}, routing::get, Router};
-use h5_tournaments_api::{graphql::{mutation::Mutation, query::Query}, prelude::*};
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::GraphQLSubscription;
+use axum::{middleware, response::{Html, IntoResponse}, routing::get, Extension, Router};
+use h5_tournaments_api::{graphql::{build_schema, graphql_handler}, prelude::*};
+use migration::{Migrator, MigratorTrait};
 use sea_orm::SqlxPostgresConnector;
+use shuttle_runtime::SecretStore;
 use sqlx::PgPool;
 
 
@@ -21,20 +23,32 @@ pub struct Services {
 
 #[shuttle_runtime::main]
 async fn main(
-    #[shuttle_shared_db::Postgres] pool: PgPool
+    #[shuttle_shared_db::Postgres] pool: PgPool,
+    #[shuttle_runtime::Secrets] secrets: SecretStore
 ) -> shuttle_axum::ShuttleAxum {
 
     let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool.clone());
-    let schema = Schema::build(Query, Mutation, EmptySubscription)
-        .data(db)
-        .data(TournamentService {})
-        .finish();
+    Migrator::up(&db, None).await
+        .map_err(|error| shuttle_runtime::Error::Database(error.to_string()))?;
+
+    if let Some(token) = secrets.get("BOOTSTRAP_API_TOKEN") {
+        AuthService.ensure_bootstrap_token(&db, &token).await
+            .map_err(|error| shuttle_runtime::Error::Database(error.to_string()))?;
+    }
+
+    let schema = build_schema(db.clone());
 
     let router = Router::new()
-        .route("/", get(graphiql).post_service(GraphQL::new(schema.clone())))
-        .merge(tournament_routes())
+        .route("/", get(graphiql).post(graphql_handler))
+        .route_service("/ws", GraphQLSubscription::new(schema.clone()))
+        .merge(tournament_routes().route_layer(middleware::from_fn(require_write_access)))
+        .merge(export_routes().with_state(db.clone()))
+        // import checks access to tournament of imported document itself
+        .merge(import_routes().with_state(db.clone()))
         //.merge(statistics_routes())
-        .with_state(LegacyTournamentService {pool: pool.clone()});
+        .with_state(LegacyTournamentService {pool: pool.clone()})
+        .layer(Extension(schema))
+        .layer(middleware::from_fn_with_state(db, authenticate));
 
     Ok(router.into())
 }
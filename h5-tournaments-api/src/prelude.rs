@@ -1,2 +1,13 @@
 pub use crate::services::tournament::prelude::*;
+pub use crate::services::statistics::prelude::*;
+pub use crate::services::rating::prelude::*;
+pub use crate::services::auth::prelude::*;
+pub use crate::services::export::prelude::*;
+pub use crate::services::import::prelude::*;
+pub use crate::services::legacy::prelude::*;
+pub use crate::services::catalogue::prelude::*;
+pub use crate::services::bracket::prelude::*;
+pub use crate::services::standings::prelude::*;
+pub use crate::services::group_draw::prelude::*;
+pub use crate::services::swiss::prelude::*;
 pub use crate::routes::prelude::*;
\ No newline at end of file
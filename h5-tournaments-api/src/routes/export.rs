@@ -0,0 +1,34 @@
+use axum::{extract::{Path, State}, http::header, response::IntoResponse, routing::get, Json, Router};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::services::{export::prelude::*, tournament::error::Error};
+
+pub fn export_routes() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/tournament/export/{tournament_id}/json", get(export_json))
+        .route("/tournament/export/{tournament_id}/csv", get(export_csv))
+}
+
+async fn export_json(
+    State(db): State<DatabaseConnection>,
+    Path(tournament_id): Path<Uuid>
+) -> Result<Json<TournamentExportModel>, Error> {
+    let export = ExportService.export_tournament(&db, tournament_id).await?;
+    Ok(Json(export))
+}
+
+async fn export_csv(
+    State(db): State<DatabaseConnection>,
+    Path(tournament_id): Path<Uuid>
+) -> Result<impl IntoResponse, Error> {
+    let export = ExportService.export_tournament(&db, tournament_id).await?;
+    let archive = ExportService.export_csv_bundle(&export)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"tournament-{}.zip\"", tournament_id))
+        ],
+        archive
+    ))
+}
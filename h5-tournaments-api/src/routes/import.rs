@@ -0,0 +1,31 @@
+use axum::{extract::{Query, State}, http::StatusCode, routing::post, Extension, Json, Router};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+
+use crate::services::{auth::prelude::AuthContext, export::prelude::TournamentExportModel, import::prelude::*, tournament::error::Error};
+
+#[derive(Debug, Deserialize)]
+pub struct ImportParams {
+    #[serde(default)]
+    pub dry_run: bool
+}
+
+pub fn import_routes() -> Router<DatabaseConnection> {
+    Router::new()
+        .route("/tournament/import", post(import_tournament))
+}
+
+/// Responds with conflict status if document wasn't applied because of conflicts.
+async fn import_tournament(
+    State(db): State<DatabaseConnection>,
+    auth: Option<Extension<AuthContext>>,
+    Query(params): Query<ImportParams>,
+    Json(document): Json<TournamentExportModel>
+) -> Result<(StatusCode, Json<ImportReportModel>), Error> {
+    let auth = auth.map(|Extension(auth)| auth).unwrap_or_default();
+    auth.require_scope(document.tournament.organizer_id, document.tournament.operator_id)?;
+
+    let report = ImportService.import_tournament(&db, document, params.dry_run).await?;
+    let status = if report.dry_run || report.applied { StatusCode::OK } else { StatusCode::CONFLICT };
+    Ok((status, Json(report)))
+}
@@ -1,3 +1,5 @@
 pub(crate) mod tournament;
+pub(crate) mod export;
+pub(crate) mod import;
 pub(crate) mod models;
 pub mod prelude;
\ No newline at end of file
@@ -1 +1,3 @@
-pub use super::tournament::tournament_routes;
\ No newline at end of file
+pub use super::tournament::tournament_routes;
+pub use super::export::export_routes;
+pub use super::import::import_routes;
\ No newline at end of file
@@ -27,7 +27,7 @@ pub fn tournament_routes() -> Router<LegacyTournamentService> {
 async fn create_tournament(
     State(tournament_service): State<LegacyTournamentService>,
     Json(creation_model): Json<TournamentCreationModel>
-) -> Result<String, ()> {
+) -> Result<String, Error> {
 
     tracing::info!("Trying to access create_tournament route");
     tracing::info!("Got info: {:?}", &creation_model);
@@ -49,7 +49,7 @@ async fn create_tournament(
         },
         Err(error) => {
             tracing::error!("Failed to create tournament: {:?}", error);
-            Err(())
+            Err(error)
         }
     }
 }
@@ -57,7 +57,7 @@ async fn create_tournament(
 async fn get_tournament(
     State(tournament_service): State<LegacyTournamentService>,
     Path(tournament_id): Path<Uuid>
-) -> Result<Json<Tournament>, ()> {
+) -> Result<Json<Tournament>, Error> {
 
     tracing::info!("Got id of tournament: {}", tournament_id);
     let tournament = tournament_service.get_tournament_by_id(tournament_id).await;
@@ -67,14 +67,14 @@ async fn get_tournament(
         },
         Err(error) => {
             tracing::info!("Failed to get tournament with id {}: {}", tournament_id, error.to_string());
-            Err(())
+            Err(error)
         }
     }
 }
 
 async fn load_races(
     State(tournament_service): State<LegacyTournamentService>
-) -> Result<Json<Vec<Race>>, ()> {
+) -> Result<Json<Vec<Race>>, Error> {
 
     let races_data = tournament_service.load_races().await;
     
@@ -82,28 +82,28 @@ async fn load_races(
         Ok(races) => {
             Ok(Json(races))
         },
-        Err(_error) => {
-            tracing::info!("Failed to get races: {}", _error.to_string());
-            Err(())
+        Err(error) => {
+            tracing::info!("Failed to get races: {}", error.to_string());
+            Err(error)
         }
     }
 }
 
 async fn load_heroes(
     State(tournament_service): State<LegacyTournamentService>,
-    Path(mod_type): Path<i16>
-) -> Result<Json<Vec<Hero>>, ()> {
+    Path(mod_type): Path<i32>
+) -> Result<Json<Vec<Hero>>, Error> {
     
-    let heroes_data = tournament_service.load_heroes_for_mod(ModType::from_repr(mod_type).unwrap()).await;
+    let heroes_data = tournament_service.load_heroes_for_mod(ModId(mod_type)).await;
     
     match heroes_data {
         Ok(heroes) => {
             tracing::info!("Heroes fetched correctly for mod {}: {:?}", mod_type, &heroes);
             Ok(Json(heroes))
         },
-        Err(_error) => {
-            tracing::error!("Failed to fetch heroes for mod {}: {}", mod_type, _error.to_string());
-            Err(())
+        Err(error) => {
+            tracing::error!("Failed to fetch heroes for mod {}: {}", mod_type, error.to_string());
+            Err(error)
         }
     }
 }
@@ -111,7 +111,7 @@ async fn load_heroes(
 async fn register_match(
     State(tournament_service): State<LegacyTournamentService>,
     Query(registration_form): Query<MatchRegistrationForm>
-) -> Result<Json<i32>, ()> {
+) -> Result<Json<i32>, Error> {
 
     let registration_result = tournament_service.register_match(&registration_form).await;
 
@@ -120,9 +120,9 @@ async fn register_match(
             tracing::info!("Match registered with id {}", success);
             Ok(Json(success))
         },
-        Err(_error) => {
-            tracing::error!("Failed to register match: {}", _error.to_string());
-            Err(())
+        Err(error) => {
+            tracing::error!("Failed to register match: {}", error.to_string());
+            Err(error)
         }
     }
 
@@ -131,68 +131,68 @@ async fn register_match(
 async fn upload_games(
     State(tournament_service): State<LegacyTournamentService>,
     Json(games): Json<Vec<Game>>
-) -> Result<(), ()> {
+) -> Result<(), Error> {
 
-    tournament_service.upload_games(&games).await.unwrap();
+    tournament_service.upload_games(&games).await?;
 
     Ok(())
 }
 
 async fn load_tournaments(
     State(tournament_service): State<LegacyTournamentService>
-) -> Result<Json<Vec<Tournament>>, ()> {
-    Ok(Json(tournament_service.load_existing_tournaments().await.unwrap()))
+) -> Result<Json<Vec<Tournament>>, Error> {
+    Ok(Json(tournament_service.load_existing_tournaments().await?))
 }
 
 async fn load_matches_for_tournament(
     State(tournament_service): State<LegacyTournamentService>,
     Path(tournament_id): Path<Uuid>
-) -> Result<Json<Vec<Match>>, ()> {
-    Ok(Json(tournament_service.load_matches_for_tournament(tournament_id).await.unwrap()))
+) -> Result<Json<Vec<Match>>, Error> {
+    Ok(Json(tournament_service.load_matches_for_tournament(tournament_id).await?))
 }
 
 async fn load_games_for_match(
     State(tournament_service): State<LegacyTournamentService>,
     Path(match_id): Path<Uuid>
-) -> Result<Json<Vec<Game>>, ()> {
-    Ok(Json(tournament_service.load_games_for_match(match_id).await.unwrap()))
+) -> Result<Json<Vec<Game>>, Error> {
+    Ok(Json(tournament_service.load_games_for_match(match_id).await?))
 }
 
 async fn create_game(
     State(tournament_service): State<LegacyTournamentService>,
     Json(game): Json<Game>
-) -> Result<(), ()> {
-    tournament_service.create_game(game).await.unwrap();
+) -> Result<(), Error> {
+    tournament_service.create_game(game).await?;
     Ok(())
 }
 
 async fn update_game(
     State(tournament_service): State<LegacyTournamentService>,
     Json(game): Json<Game>
-) -> Result<(), ()> {
-    tournament_service.update_game(game).await.unwrap();
+) -> Result<(), Error> {
+    tournament_service.update_game(game).await?;
     Ok(())
 }
 
 async fn update_match(
     State(tournament_service): State<LegacyTournamentService>,
     Json(match_to_update): Json<Match>
-) -> Result<(), ()> {
-    tournament_service.update_match(match_to_update).await.unwrap();
+) -> Result<(), Error> {
+    tournament_service.update_match(match_to_update).await?;
     Ok(())
 }
 
 async fn load_all_games_for_tournament(
     State(tournament_service): State<LegacyTournamentService>,
     Path(tournament_id): Path<Uuid>
-) -> Result<Json<Vec<Game>>, ()> {
-    Ok(Json(tournament_service.get_all_games_for_tournament(tournament_id).await.unwrap()))
+) -> Result<Json<Vec<Game>>, Error> {
+    Ok(Json(tournament_service.get_all_games_for_tournament(tournament_id).await?))
 }
 
 async fn load_messages(
     State(tournament_service): State<LegacyTournamentService>,
     Json(messages): Json<Vec<TempMessageModel>>
-) -> Result<(), ()> {
+) -> Result<(), Error> {
     match tournament_service.load_messages(messages).await {
         Ok(_success) => {
             tracing::info!("Messages loaded");
@@ -200,7 +200,7 @@ async fn load_messages(
         },
         Err(error) => {
             tracing::error!("Failed to load messages: {}", error.to_string());
-            Err(())
+            Err(error)
         }
     }
 }
@@ -208,14 +208,14 @@ async fn load_messages(
 async fn get_messages(
     State(tournament_service): State<LegacyTournamentService>,
     Path(tournament_id): Path<Uuid>
-) -> Result<Json<Vec<TempMessageModel>>, ()> {
+) -> Result<Json<Vec<TempMessageModel>>, Error> {
     match tournament_service.get_messages(tournament_id).await {
         Ok(messages) => {
             Ok(Json(messages))
         },
         Err(error) => {
             tracing::error!("Failed to get messages: {}", error.to_string());
-            Err(())
+            Err(error)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+use async_graphql::{Context, ErrorExtensions, Guard};
+use uuid::Uuid;
+
+use crate::services::tournament::error::Error;
+
+use super::models::api_token::{ApiRole, ApiTokenModel};
+
+/// Api token request was made with, requests without token are anonymous.
+#[derive(Debug, Clone, Default)]
+pub struct AuthContext {
+    pub token: Option<ApiTokenModel>
+}
+
+impl AuthContext {
+    /// Auth context of graphql request, requests executed without it are anonymous.
+    pub fn of(context: &Context<'_>) -> AuthContext {
+        context.data_opt::<AuthContext>().cloned().unwrap_or_default()
+    }
+
+    pub fn role(&self) -> Option<ApiRole> {
+        self.token.as_ref().map(|token| token.role)
+    }
+
+    /// Checks that request was made with token of given role or higher one.
+    pub fn require(&self, role: ApiRole) -> Result<&ApiTokenModel, Error> {
+        match &self.token {
+            None => Err(Error::Unauthenticated("Api token is required".to_string())),
+            Some(token) if token.role < role => Err(Error::Forbidden(format!("{:?} role is required", role))),
+            Some(token) => Ok(token)
+        }
+    }
+
+    /// Checks that request was made with token of given role or higher one that isn't bound to organizer or operator.
+    /// Changes affecting every tournament, like catalogue, users or api tokens, require such tokens.
+    pub fn require_unscoped(&self, role: ApiRole) -> Result<&ApiTokenModel, Error> {
+        let token = self.require(role)?;
+        if token.organizer_id.is_some() || token.operator_id.is_some() {
+            return Err(Error::Forbidden(format!("Api token {} is bound to organizer or operator and can't make global changes", token.name)));
+        }
+        Ok(token)
+    }
+
+    /// Checks that request may change tournament of given organizer and operator.
+    /// Tokens bound to neither organizer nor operator are service ones and may change any tournament.
+    pub fn require_scope(&self, organizer_id: Uuid, operator_id: Uuid) -> Result<&ApiTokenModel, Error> {
+        let token = self.require(ApiRole::Organizer)?;
+        let allowed = match (token.organizer_id, token.operator_id) {
+            (None, None) => true,
+            (organizer, operator) => organizer == Some(organizer_id) || operator == Some(operator_id)
+        };
+        if allowed {
+            Ok(token)
+        } else {
+            Err(Error::Forbidden(format!("Api token {} has no access to tournaments of this organizer or operator", token.name)))
+        }
+    }
+}
+
+/// Allows field only to requests with token of given role or higher one.
+pub struct RoleGuard {
+    role: ApiRole,
+    unscoped: bool
+}
+
+impl RoleGuard {
+    pub fn new(role: ApiRole) -> Self {
+        RoleGuard { role, unscoped: false }
+    }
+
+    /// Also refuses tokens bound to organizer or operator.
+    pub fn unscoped(role: ApiRole) -> Self {
+        RoleGuard { role, unscoped: true }
+    }
+}
+
+impl Guard for RoleGuard {
+    async fn check(&self, context: &Context<'_>) -> Result<(), async_graphql::Error> {
+        let auth = AuthContext::of(context);
+        let result = if self.unscoped {
+            auth.require_unscoped(self.role)
+        } else {
+            auth.require(self.role)
+        };
+        result
+            .map(|_| ())
+            .map_err(|error| error.extend())
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{extract::{Request, State}, http::{header::AUTHORIZATION, Method}, middleware::Next, response::Response};
+use sea_orm::DatabaseConnection;
+
+use crate::services::tournament::error::Error;
+
+use super::{guard::AuthContext, models::api_token::ApiRole, service::AuthService};
+
+/// Resolves `Authorization: Bearer` token of request into [`AuthContext`] extension.
+/// Requests without token pass as anonymous, requests with unknown or revoked token are rejected.
+pub async fn authenticate(
+    State(db): State<DatabaseConnection>,
+    mut request: Request,
+    next: Next
+) -> Result<Response, Error> {
+    let token = match request.headers().get(AUTHORIZATION) {
+        Some(header) => {
+            let token = header.to_str().ok()
+                .and_then(|header| header.strip_prefix("Bearer "))
+                .ok_or_else(|| Error::Unauthenticated("Authorization header must be a bearer token".to_string()))?;
+            let token = AuthService.authenticate(&db, token.trim()).await?
+                .ok_or_else(|| Error::Unauthenticated("Api token is invalid or revoked".to_string()))?;
+            Some(token)
+        },
+        None => None
+    };
+
+    request.extensions_mut().insert(AuthContext { token });
+    Ok(next.run(request).await)
+}
+
+/// Lets only organizers and bot change data through legacy rest routes, reading stays open.
+/// Legacy tables know nothing about organizers and operators, so tokens bound to them can't change it.
+pub async fn require_write_access(
+    request: Request,
+    next: Next
+) -> Result<Response, Error> {
+    if !matches!(*request.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        request.extensions()
+            .get::<AuthContext>()
+            .cloned()
+            .unwrap_or_default()
+            .require_unscoped(ApiRole::Organizer)?;
+    }
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,5 @@
+mod service;
+pub(crate) mod guard;
+pub(crate) mod middleware;
+pub(crate) mod prelude;
+pub mod models;
@@ -0,0 +1,72 @@
+use sea_orm::prelude::*;
+
+pub type ApiTokenModel = Model;
+
+/// Roles are ordered, every role is allowed to do everything lower roles can.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, async_graphql::Enum)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum ApiRole {
+    ReadOnly = 0,
+    Organizer = 1,
+    Bot = 2
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "api_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub name: String,
+    pub token_hash: String,
+    pub role: ApiRole,
+    // Organizer this token was issued to, only set for organizer tokens
+    pub organizer_id: Option<Uuid>,
+    // Discord server this bot token serves, bot tokens without it can access every tournament
+    pub operator_id: Option<Uuid>,
+    pub revoked: bool,
+    pub created_at: DateTimeWithTimeZone
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl ApiTokenModel {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn role(&self) -> ApiRole {
+        self.role
+    }
+
+    async fn organizer(&self) -> Option<Uuid> {
+        self.organizer_id
+    }
+
+    async fn operator(&self) -> Option<Uuid> {
+        self.operator_id
+    }
+
+    async fn revoked(&self) -> bool {
+        self.revoked
+    }
+
+    async fn created_at(&self) -> DateTimeWithTimeZone {
+        self.created_at
+    }
+}
+
+/// Token returned once when it's issued.
+#[derive(Debug, async_graphql::SimpleObject)]
+pub struct IssuedApiTokenModel {
+    /// Secret to send in `Authorization: Bearer` header, it can't be recovered later
+    pub token: String,
+    pub info: ApiTokenModel
+}
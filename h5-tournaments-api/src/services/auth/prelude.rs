@@ -0,0 +1,4 @@
+pub use super::service::AuthService;
+pub use super::guard::{AuthContext, RoleGuard};
+pub use super::middleware::{authenticate, require_write_access};
+pub use super::models::api_token::{ApiRole, ApiTokenModel, IssuedApiTokenModel};
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, NotSet, QueryFilter, QueryOrder, QuerySelect, Set};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::services::{bracket::models::bracket, tournament::{error::Error, models::{game_builder, match_structure, participant, tournament}}};
+
+use super::{guard::AuthContext, models::api_token::{self, ApiRole, ApiTokenModel, IssuedApiTokenModel}};
+
+const TOKEN_PREFIX: &str = "h5t_";
+
+#[derive(Clone)]
+pub struct AuthService;
+
+pub(crate) fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+impl AuthService {
+    /// Finds active token, returns None for unknown and revoked tokens.
+    pub async fn authenticate(
+        &self,
+        db: &DatabaseConnection,
+        token: &str
+    ) -> Result<Option<ApiTokenModel>, Error> {
+        Ok(api_token::Entity::find()
+            .filter(api_token::Column::TokenHash.eq(hash_token(token)))
+            .filter(api_token::Column::Revoked.eq(false))
+            .one(db)
+            .await?)
+    }
+
+    pub async fn issue_token(
+        &self,
+        db: &DatabaseConnection,
+        name: String,
+        role: ApiRole,
+        organizer_id: Option<Uuid>,
+        operator_id: Option<Uuid>
+    ) -> Result<IssuedApiTokenModel, Error> {
+        if (role == ApiRole::Organizer) != organizer_id.is_some() {
+            return Err(Error::Validation("Organizer must be set for organizer tokens and only for them".to_string()));
+        }
+        if operator_id.is_some() && role != ApiRole::Bot {
+            return Err(Error::Validation("Operator can be set only for bot tokens".to_string()));
+        }
+
+        let token = format!("{}{}{}", TOKEN_PREFIX, Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let info = self.insert_token(db, name, &token, role, organizer_id, operator_id).await?;
+        Ok(IssuedApiTokenModel { token, info })
+    }
+
+    /// Makes sure token configured outside of api exists, so the first tokens can be issued with it.
+    pub async fn ensure_bootstrap_token(
+        &self,
+        db: &DatabaseConnection,
+        token: &str
+    ) -> Result<(), Error> {
+        let existing_token = api_token::Entity::find()
+            .filter(api_token::Column::TokenHash.eq(hash_token(token)))
+            .one(db)
+            .await?;
+        if existing_token.is_none() {
+            self.insert_token(db, "bootstrap".to_string(), token, ApiRole::Bot, None, None).await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_token(
+        &self,
+        db: &DatabaseConnection,
+        name: String,
+        token: &str,
+        role: ApiRole,
+        organizer_id: Option<Uuid>,
+        operator_id: Option<Uuid>
+    ) -> Result<ApiTokenModel, Error> {
+        let token_to_insert = api_token::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            name: Set(name),
+            token_hash: Set(hash_token(token)),
+            role: Set(role),
+            organizer_id: Set(organizer_id),
+            operator_id: Set(operator_id),
+            revoked: Set(false),
+            created_at: NotSet
+        };
+        Ok(token_to_insert.insert(db).await?)
+    }
+
+    pub async fn revoke_token(
+        &self,
+        db: &DatabaseConnection,
+        id: Uuid
+    ) -> Result<ApiTokenModel, Error> {
+        let token = api_token::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No api token found with id {}", id)))?;
+        let mut token_to_update: api_token::ActiveModel = token.into();
+        token_to_update.revoked = Set(true);
+        Ok(token_to_update.update(db).await?)
+    }
+
+    pub async fn get_tokens(
+        &self,
+        db: &DatabaseConnection
+    ) -> Result<Vec<ApiTokenModel>, Error> {
+        Ok(api_token::Entity::find()
+            .order_by_asc(api_token::Column::CreatedAt)
+            .all(db)
+            .await?)
+    }
+
+    /// Checks that request may change every one of given tournaments.
+    pub async fn authorize_tournaments(
+        &self,
+        db: &DatabaseConnection,
+        auth: &AuthContext,
+        tournament_ids: impl IntoIterator<Item = Uuid>
+    ) -> Result<(), Error> {
+        let tournament_ids = tournament_ids.into_iter().collect::<HashSet<Uuid>>();
+        let tournaments = tournament::Entity::find()
+            .filter(tournament::Column::Id.is_in(tournament_ids.iter().copied()))
+            .all(db)
+            .await?;
+        if tournaments.len() != tournament_ids.len() {
+            return Err(Error::NotFound("Some of requested tournaments don't exist".to_string()));
+        }
+        for tournament in &tournaments {
+            auth.require_scope(tournament.organizer, tournament.operator_id)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that request may change tournaments given matches belong to.
+    pub async fn authorize_matches(
+        &self,
+        db: &DatabaseConnection,
+        auth: &AuthContext,
+        match_ids: impl IntoIterator<Item = Uuid>
+    ) -> Result<(), Error> {
+        let match_ids = match_ids.into_iter().collect::<HashSet<Uuid>>();
+        let tournament_ids: Vec<(Uuid, Uuid)> = match_structure::Entity::find()
+            .select_only()
+            .column(match_structure::Column::Id)
+            .column(match_structure::Column::TournamentId)
+            .filter(match_structure::Column::Id.is_in(match_ids.iter().copied()))
+            .into_tuple()
+            .all(db)
+            .await?;
+        if tournament_ids.len() != match_ids.len() {
+            return Err(Error::NotFound("Some of requested matches don't exist".to_string()));
+        }
+        self.authorize_tournaments(db, auth, tournament_ids.into_iter().map(|(_, tournament_id)| tournament_id)).await
+    }
+
+    /// Checks that request may change tournament given game belongs to.
+    pub async fn authorize_game(
+        &self,
+        db: &DatabaseConnection,
+        auth: &AuthContext,
+        game_id: Uuid
+    ) -> Result<(), Error> {
+        let game = game_builder::Entity::find_by_id(game_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No game found with id {}", game_id)))?;
+        self.authorize_matches(db, auth, [game.match_id]).await
+    }
+
+    /// Checks that request may change tournament given participant belongs to.
+    pub async fn authorize_participant(
+        &self,
+        db: &DatabaseConnection,
+        auth: &AuthContext,
+        participant_id: Uuid
+    ) -> Result<(), Error> {
+        let participant = participant::Entity::find_by_id(participant_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No participant found with id {}", participant_id)))?;
+        self.authorize_tournaments(db, auth, [participant.tournament_id]).await
+    }
+
+    /// Checks that request may change tournament given bracket belongs to.
+    pub async fn authorize_bracket(
+        &self,
+        db: &DatabaseConnection,
+        auth: &AuthContext,
+        bracket_id: Uuid
+    ) -> Result<(), Error> {
+        let bracket = bracket::Entity::find_by_id(bracket_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No bracket found with id {}", bracket_id)))?;
+        self.authorize_tournaments(db, auth, [bracket.tournament_id]).await
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::models::{bracket::BracketFormat, bracket_match::{self, BracketSection}};
+
+/// Match of bracket before it's saved, links point to indexes of other planned matches.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PlannedMatch {
+    pub section: BracketSection,
+    pub round: i32,
+    pub slot: i32,
+    pub first_player: Option<Uuid>,
+    pub second_player: Option<Uuid>,
+    pub winner_next: Option<(usize, i32)>,
+    pub loser_next: Option<(usize, i32)>
+}
+
+impl PlannedMatch {
+    fn new(section: BracketSection, round: i32, slot: i32) -> Self {
+        PlannedMatch {
+            section,
+            round,
+            slot,
+            first_player: None,
+            second_player: None,
+            winner_next: None,
+            loser_next: None
+        }
+    }
+}
+
+/// Minimal amount of players bracket of given format can be built for.
+pub(crate) fn min_players(format: BracketFormat) -> usize {
+    match format {
+        BracketFormat::DoubleElimination => 3,
+        _ => 2
+    }
+}
+
+/// Builds all matches of bracket, players must be ordered by seed, the best one first.
+pub(crate) fn plan(format: BracketFormat, players: &[Uuid]) -> Vec<PlannedMatch> {
+    match format {
+        BracketFormat::SingleElimination => plan_elimination(players, false),
+        BracketFormat::DoubleElimination => plan_elimination(players, true),
+        BracketFormat::RoundRobin => plan_round_robin(players)
+    }
+}
+
+/// Order of seeds in the first round that keeps best seeds apart until the latest rounds, e.g. 1, 4, 2, 3 for 4 players.
+fn seed_positions(size: usize) -> Vec<usize> {
+    let mut positions = vec![1];
+    while positions.len() < size {
+        let count = positions.len() * 2;
+        positions = positions.into_iter().flat_map(|seed| [seed, count + 1 - seed]).collect();
+    }
+    positions
+}
+
+fn plan_elimination(players: &[Uuid], double: bool) -> Vec<PlannedMatch> {
+    let size = players.len().next_power_of_two().max(2);
+    let rounds = size.trailing_zeros() as usize;
+
+    let mut matches = vec![];
+    // index of the first match of every winners round
+    let mut winners_rounds = vec![];
+    for round in 1..=rounds {
+        winners_rounds.push(matches.len());
+        for slot in 0..size >> round {
+            matches.push(PlannedMatch::new(BracketSection::Winners, round as i32, slot as i32));
+        }
+    }
+
+    let positions = seed_positions(size);
+    for (slot, seeds) in positions.chunks(2).enumerate() {
+        matches[slot].first_player = players.get(seeds[0] - 1).copied();
+        matches[slot].second_player = players.get(seeds[1] - 1).copied();
+    }
+
+    for round in 1..rounds {
+        for slot in 0..size >> round {
+            matches[winners_rounds[round - 1] + slot].winner_next = Some((winners_rounds[round] + slot / 2, (slot % 2) as i32));
+        }
+    }
+
+    if !double {
+        return matches;
+    }
+
+    // losers bracket has two rounds for every winners round except the first one:
+    // odd rounds play survivors against each other, even rounds meet them with losers dropping from winners bracket
+    let losers_rounds_count = 2 * (rounds - 1);
+    let mut losers_rounds = vec![];
+    for round in 1..=losers_rounds_count {
+        losers_rounds.push(matches.len());
+        for slot in 0..losers_round_size(size, round) {
+            matches.push(PlannedMatch::new(BracketSection::Losers, round as i32, slot as i32));
+        }
+    }
+    // second grand final resets the bracket when the losers bracket player wins the first one,
+    // otherwise it's decided without playing, see `resolve`
+    let grand_final = matches.len();
+    matches.push(PlannedMatch::new(BracketSection::GrandFinal, 1, 0));
+    matches.push(PlannedMatch::new(BracketSection::GrandFinal, 2, 0));
+
+    for slot in 0..size / 2 {
+        matches[winners_rounds[0] + slot].loser_next = Some((losers_rounds[0] + slot / 2, (slot % 2) as i32));
+    }
+    for round in 2..=rounds {
+        let count = size >> round;
+        for slot in 0..count {
+            // dropping players are placed in reversed order every other round to postpone rematches
+            let target = if round % 2 == 0 { count - 1 - slot } else { slot };
+            matches[winners_rounds[round - 1] + slot].loser_next = Some((losers_rounds[2 * (round - 1) - 1] + target, 1));
+        }
+    }
+    for round in 1..losers_rounds_count {
+        for slot in 0..losers_round_size(size, round) {
+            let next = if round % 2 == 1 {
+                (losers_rounds[round] + slot, 0)
+            } else {
+                (losers_rounds[round] + slot / 2, (slot % 2) as i32)
+            };
+            matches[losers_rounds[round - 1] + slot].winner_next = Some(next);
+        }
+    }
+
+    matches[winners_rounds[rounds - 1]].winner_next = Some((grand_final, 0));
+    matches[losers_rounds[losers_rounds_count - 1]].winner_next = Some((grand_final, 1));
+    matches[grand_final].winner_next = Some((grand_final + 1, 0));
+    matches[grand_final].loser_next = Some((grand_final + 1, 1));
+    matches
+}
+
+fn losers_round_size(size: usize, round: usize) -> usize {
+    size >> (round.div_ceil(2) + 1)
+}
+
+/// Circle method, every player meets every other one once. Player paired with nobody skips the round.
+fn plan_round_robin(players: &[Uuid]) -> Vec<PlannedMatch> {
+    let mut circle = players.iter().copied().map(Some).collect::<Vec<Option<Uuid>>>();
+    if circle.len() % 2 == 1 {
+        circle.push(None);
+    }
+    let count = circle.len();
+
+    let mut matches = vec![];
+    for round in 1..count {
+        let mut slot = 0;
+        for index in 0..count / 2 {
+            let (mut first, mut second) = (circle[index], circle[count - 1 - index]);
+            // fixed player would always be the first one otherwise
+            if index == 0 && round % 2 == 0 {
+                std::mem::swap(&mut first, &mut second);
+            }
+            if let (Some(first), Some(second)) = (first, second) {
+                let mut planned = PlannedMatch::new(BracketSection::RoundRobin, round as i32, slot);
+                planned.first_player = Some(first);
+                planned.second_player = Some(second);
+                matches.push(planned);
+                slot += 1;
+            }
+        }
+        circle[1..].rotate_right(1);
+    }
+    matches
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Seat {
+    Pending,
+    Player(Uuid),
+    // nobody will ever come to this seat
+    Empty
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Pending,
+    Decided { winner: Uuid, loser: Option<Uuid> },
+    Empty
+}
+
+/// Moves decided players further through the bracket. Players facing nobody advance without playing.
+/// Loser of the first grand final only gets a rematch if they came from the losers bracket.
+/// Returns indexes of matches that were changed, matches must be ordered by section, round and slot.
+pub(crate) fn resolve(matches: &mut [bracket_match::Model]) -> Vec<usize> {
+    let indexes = matches.iter().enumerate().map(|(index, bracket_match)| (bracket_match.id, index)).collect::<HashMap<Uuid, usize>>();
+    // seats are fed by winner or loser of a single previous match
+    let mut sources: HashMap<(usize, i32), (usize, bool)> = HashMap::new();
+    for (index, bracket_match) in matches.iter().enumerate() {
+        if let (Some(next), Some(position)) = (bracket_match.winner_next, bracket_match.winner_next_position) {
+            sources.insert((indexes[&next], position), (index, true));
+        }
+        if let (Some(next), Some(position)) = (bracket_match.loser_next, bracket_match.loser_next_position) {
+            sources.insert((indexes[&next], position), (index, false));
+        }
+    }
+
+    let mut outcomes = vec![Outcome::Pending; matches.len()];
+    let mut changed = vec![];
+    for index in 0..matches.len() {
+        let mut seats = [Seat::Pending; 2];
+        for (position, seat) in seats.iter_mut().enumerate() {
+            let stored = if position == 0 { matches[index].first_player } else { matches[index].second_player };
+            *seat = match (stored, sources.get(&(index, position as i32))) {
+                (Some(player), _) => Seat::Player(player),
+                (None, None) => Seat::Empty,
+                (None, Some((source, by_winner))) => match outcomes[*source] {
+                    Outcome::Pending => Seat::Pending,
+                    Outcome::Empty => Seat::Empty,
+                    // player from winners bracket won the whole bracket with the first grand final
+                    Outcome::Decided { winner, .. } if !*by_winner
+                        && matches[*source].section == BracketSection::GrandFinal
+                        && matches[*source].first_player == Some(winner) => Seat::Empty,
+                    Outcome::Decided { winner, loser } => match if *by_winner { Some(winner) } else { loser } {
+                        Some(player) => Seat::Player(player),
+                        None => Seat::Empty
+                    }
+                }
+            };
+        }
+
+        let bracket_match = &mut matches[index];
+        let mut is_changed = false;
+        if let (None, Seat::Player(player)) = (bracket_match.first_player, seats[0]) {
+            bracket_match.first_player = Some(player);
+            is_changed = true;
+        }
+        if let (None, Seat::Player(player)) = (bracket_match.second_player, seats[1]) {
+            bracket_match.second_player = Some(player);
+            is_changed = true;
+        }
+
+        outcomes[index] = match (bracket_match.winner, seats) {
+            (Some(winner), _) => Outcome::Decided {
+                winner,
+                loser: [bracket_match.first_player, bracket_match.second_player].into_iter().flatten().find(|player| *player != winner)
+            },
+            (None, [Seat::Player(player), Seat::Empty]) | (None, [Seat::Empty, Seat::Player(player)]) => {
+                bracket_match.winner = Some(player);
+                is_changed = true;
+                Outcome::Decided { winner: player, loser: None }
+            },
+            (None, [Seat::Empty, Seat::Empty]) => Outcome::Empty,
+            _ => Outcome::Pending
+        };
+
+        if is_changed {
+            changed.push(index);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn players(count: usize) -> Vec<Uuid> {
+        (1..=count as u128).map(Uuid::from_u128).collect()
+    }
+
+    /// Saves planned matches the way bracket service does.
+    fn to_models(planned: &[PlannedMatch]) -> Vec<bracket_match::Model> {
+        let ids = (0..planned.len()).map(|_| Uuid::new_v4()).collect::<Vec<Uuid>>();
+        planned.iter().enumerate().map(|(index, planned)| bracket_match::Model {
+            id: ids[index],
+            bracket_id: Uuid::nil(),
+            section: planned.section,
+            round: planned.round,
+            slot: planned.slot,
+            first_player: planned.first_player,
+            second_player: planned.second_player,
+            winner: None,
+            match_id: None,
+            winner_next: planned.winner_next.map(|(next, _)| ids[next]),
+            winner_next_position: planned.winner_next.map(|(_, position)| position),
+            loser_next: planned.loser_next.map(|(next, _)| ids[next]),
+            loser_next_position: planned.loser_next.map(|(_, position)| position)
+        }).collect()
+    }
+
+    fn find(matches: &[bracket_match::Model], section: BracketSection, round: i32, slot: i32) -> usize {
+        matches.iter().position(|bracket_match| bracket_match.section == section && bracket_match.round == round && bracket_match.slot == slot).unwrap()
+    }
+
+    /// Decides every ready match in favour of the better seed until the given match is ready.
+    fn play_until(matches: &mut [bracket_match::Model], target: usize) {
+        resolve(matches);
+        while matches[target].winner.is_none() && (matches[target].first_player.is_none() || matches[target].second_player.is_none()) {
+            let index = matches.iter()
+                .position(|bracket_match| bracket_match.winner.is_none() && bracket_match.first_player.is_some() && bracket_match.second_player.is_some())
+                .unwrap();
+            matches[index].winner = matches[index].first_player.min(matches[index].second_player);
+            resolve(matches);
+        }
+    }
+
+    #[test]
+    fn seeds_are_kept_apart() {
+        assert_eq!(seed_positions(4), vec![1, 4, 2, 3]);
+        assert_eq!(seed_positions(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn best_seeds_get_byes_with_odd_counts() {
+        for count in [3, 5, 6, 7] {
+            let players = players(count);
+            let planned = plan(BracketFormat::SingleElimination, &players);
+            let size = count.next_power_of_two();
+            assert_eq!(planned.len(), size - 1);
+
+            let first_round = planned.iter().filter(|planned| planned.round == 1).collect::<Vec<&PlannedMatch>>();
+            let seated = first_round.iter().flat_map(|planned| [planned.first_player, planned.second_player]).flatten().collect::<HashSet<Uuid>>();
+            assert_eq!(seated.len(), count);
+
+            // byes are given to the best seeds and nobody faces an empty seat twice
+            let byes = first_round.iter().filter(|planned| planned.second_player.is_none()).map(|planned| planned.first_player.unwrap()).collect::<Vec<Uuid>>();
+            assert_eq!(byes.len(), size - count);
+            assert!(byes.iter().all(|player| players[..size - count].contains(player)));
+
+            let mut matches = to_models(&planned);
+            resolve(&mut matches);
+            for bracket_match in matches.iter().filter(|bracket_match| bracket_match.round == 1 && bracket_match.second_player.is_none()) {
+                assert_eq!(bracket_match.winner, bracket_match.first_player);
+            }
+            let second_round = matches.iter().filter(|bracket_match| bracket_match.round == 2).flat_map(|bracket_match| [bracket_match.first_player, bracket_match.second_player]).flatten().collect::<HashSet<Uuid>>();
+            assert_eq!(second_round, byes.into_iter().collect());
+        }
+    }
+
+    #[test]
+    fn round_robin_pairs_everyone_once() {
+        for count in [4, 5] {
+            let players = players(count);
+            let planned = plan(BracketFormat::RoundRobin, &players);
+            assert_eq!(planned.len(), count * (count - 1) / 2);
+
+            let pairs = planned.iter()
+                .map(|planned| {
+                    let (first, second) = (planned.first_player.unwrap(), planned.second_player.unwrap());
+                    (first.min(second), first.max(second))
+                })
+                .collect::<HashSet<(Uuid, Uuid)>>();
+            assert_eq!(pairs.len(), planned.len());
+
+            let rounds = planned.iter().map(|planned| planned.round).max().unwrap() as usize;
+            assert_eq!(rounds, if count % 2 == 0 { count - 1 } else { count });
+            for round in 1..=rounds as i32 {
+                let seated = planned.iter()
+                    .filter(|planned| planned.round == round)
+                    .flat_map(|planned| [planned.first_player, planned.second_player])
+                    .collect::<Vec<Option<Uuid>>>();
+                assert_eq!(seated.len(), seated.iter().collect::<HashSet<_>>().len());
+            }
+        }
+    }
+
+    #[test]
+    fn losers_drop_in_reversed_every_other_round() {
+        let planned = plan_elimination(&players(8), true);
+        let index = |section, round, slot| planned.iter().position(|planned| planned.section == section && planned.round == round && planned.slot == slot).unwrap();
+
+        // first round losers meet each other
+        for slot in 0..4 {
+            assert_eq!(planned[index(BracketSection::Winners, 1, slot)].loser_next, Some((index(BracketSection::Losers, 1, slot / 2), slot % 2)));
+        }
+        // second round losers are placed in reversed order against survivors of the first losers round
+        for slot in 0..2 {
+            assert_eq!(planned[index(BracketSection::Winners, 2, slot)].loser_next, Some((index(BracketSection::Losers, 2, 1 - slot), 1)));
+            assert_eq!(planned[index(BracketSection::Losers, 1, slot)].winner_next, Some((index(BracketSection::Losers, 2, slot), 0)));
+        }
+        // winners final loser meets the losers bracket finalist
+        assert_eq!(planned[index(BracketSection::Winners, 3, 0)].loser_next, Some((index(BracketSection::Losers, 4, 0), 1)));
+        assert_eq!(planned[index(BracketSection::Losers, 3, 0)].winner_next, Some((index(BracketSection::Losers, 4, 0), 0)));
+        assert_eq!(planned[index(BracketSection::Losers, 4, 0)].winner_next, Some((index(BracketSection::GrandFinal, 1, 0), 1)));
+        assert_eq!(planned[index(BracketSection::Winners, 3, 0)].winner_next, Some((index(BracketSection::GrandFinal, 1, 0), 0)));
+    }
+
+    #[test]
+    fn everyone_loses_twice_in_double_elimination() {
+        for count in [3, 5, 8] {
+            let players = players(count);
+            let mut matches = to_models(&plan(BracketFormat::DoubleElimination, &players));
+            let reset = find(&matches, BracketSection::GrandFinal, 2, 0);
+            play_until(&mut matches, reset);
+
+            let mut losses = HashMap::new();
+            for bracket_match in matches.iter().filter(|bracket_match| bracket_match.first_player.is_some() && bracket_match.second_player.is_some()) {
+                let loser = [bracket_match.first_player, bracket_match.second_player].into_iter().flatten().find(|player| Some(*player) != bracket_match.winner).unwrap();
+                *losses.entry(loser).or_insert(0) += 1;
+            }
+            assert_eq!(matches[reset].winner, Some(players[0]));
+            assert!(!losses.contains_key(&players[0]));
+            assert_eq!(losses.len(), count - 1);
+            assert!(losses.values().all(|count| *count == 2));
+        }
+    }
+
+    #[test]
+    fn grand_final_is_reset_only_after_losers_bracket_player_wins() {
+        let players = players(4);
+        let mut matches = to_models(&plan(BracketFormat::DoubleElimination, &players));
+        let grand_final = find(&matches, BracketSection::GrandFinal, 1, 0);
+        let reset = find(&matches, BracketSection::GrandFinal, 2, 0);
+        play_until(&mut matches, grand_final);
+        assert_eq!(matches[grand_final].first_player, Some(players[0]));
+        assert_eq!(matches[grand_final].second_player, Some(players[1]));
+
+        let mut kept = matches.clone();
+        kept[grand_final].winner = Some(players[0]);
+        assert_eq!(resolve(&mut kept), vec![reset]);
+        assert_eq!((kept[reset].first_player, kept[reset].second_player, kept[reset].winner), (Some(players[0]), None, Some(players[0])));
+
+        matches[grand_final].winner = Some(players[1]);
+        assert_eq!(resolve(&mut matches), vec![reset]);
+        assert_eq!((matches[reset].first_player, matches[reset].second_player, matches[reset].winner), (Some(players[1]), Some(players[0]), None));
+    }
+}
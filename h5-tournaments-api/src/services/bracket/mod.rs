@@ -0,0 +1,4 @@
+pub(crate) mod engine;
+mod service;
+pub(crate) mod prelude;
+pub mod models;
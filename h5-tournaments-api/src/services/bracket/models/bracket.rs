@@ -0,0 +1,100 @@
+use async_graphql::{Context, ErrorExtensions};
+use sea_orm::prelude::*;
+
+use crate::services::bracket::service::BracketService;
+
+use super::bracket_match::{BracketMatchModel, BracketSection};
+
+pub type BracketModel = Model;
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum BracketFormat {
+    SingleElimination = 0,
+    DoubleElimination = 1,
+    RoundRobin = 2
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "brackets")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub format: BracketFormat,
+    // None if bracket was generated from all participants of tournament
+    pub group_number: Option<i32>,
+    pub best_of: i32,
+    pub created_at: DateTimeWithTimeZone
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Tournament,
+    BracketMatch
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Tournament => Entity::belongs_to(crate::services::tournament::models::tournament::Entity)
+                .from(Column::TournamentId)
+                .to(crate::services::tournament::models::tournament::Column::Id)
+                .into(),
+            Self::BracketMatch => Entity::has_many(super::bracket_match::Entity).into()
+        }
+    }
+}
+
+impl Related<super::bracket_match::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BracketMatch.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl BracketModel {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn tournament(&self) -> Uuid {
+        self.tournament_id
+    }
+
+    async fn format(&self) -> BracketFormat {
+        self.format
+    }
+
+    /// Null if bracket was generated from all participants of tournament
+    async fn group(&self) -> Option<i32> {
+        self.group_number
+    }
+
+    async fn best_of(&self) -> i32 {
+        self.best_of
+    }
+
+    async fn created_at(&self) -> DateTimeWithTimeZone {
+        self.created_at
+    }
+
+    /// Matches of bracket ordered by section, round and slot
+    async fn matches<'a>(&self, context: &Context<'a>) -> Result<Vec<BracketMatchModel>, async_graphql::Error> {
+        let service = context.data::<BracketService>()?;
+        let db = context.data::<DatabaseConnection>()?;
+        service.get_bracket_matches(db, self.id).await.map_err(|error| error.extend())
+    }
+
+    /// Winner of the final match, always null for round robin brackets
+    async fn winner<'a>(&self, context: &Context<'a>) -> Result<Option<Uuid>, async_graphql::Error> {
+        let service = context.data::<BracketService>()?;
+        let db = context.data::<DatabaseConnection>()?;
+        let matches = service.get_bracket_matches(db, self.id).await.map_err(|error| error.extend())?;
+        Ok(matches.into_iter()
+            .find(|bracket_match| bracket_match.section != BracketSection::RoundRobin && bracket_match.winner_next.is_none())
+            .and_then(|bracket_match| bracket_match.winner))
+    }
+}
@@ -0,0 +1,127 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
+use sea_orm::prelude::*;
+
+use crate::services::tournament::{loaders::MatchLoader, models::match_structure::MatchModel};
+
+pub type BracketMatchModel = Model;
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum BracketSection {
+    Winners = 0,
+    Losers = 1,
+    GrandFinal = 2,
+    RoundRobin = 3
+}
+
+/// Single match of bracket. Players of later rounds are unknown until matches leading to it are decided,
+/// tournament match is created once both players are known.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "bracket_matches")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub bracket_id: Uuid,
+    pub section: BracketSection,
+    pub round: i32,
+    pub slot: i32,
+    pub first_player: Option<Uuid>,
+    pub second_player: Option<Uuid>,
+    pub winner: Option<Uuid>,
+    pub match_id: Option<Uuid>,
+    // Bracket match winner advances to and position in it, 0 for first player and 1 for second one
+    pub winner_next: Option<Uuid>,
+    pub winner_next_position: Option<i32>,
+    // Only set for winners section of double elimination brackets
+    pub loser_next: Option<Uuid>,
+    pub loser_next_position: Option<i32>
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Bracket
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Bracket => Entity::belongs_to(super::bracket::Entity)
+                .from(Column::BracketId)
+                .to(super::bracket::Column::Id)
+                .into()
+        }
+    }
+}
+
+impl Related<super::bracket::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Bracket.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl BracketMatchModel {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn bracket(&self) -> Uuid {
+        self.bracket_id
+    }
+
+    async fn section(&self) -> BracketSection {
+        self.section
+    }
+
+    async fn round(&self) -> i32 {
+        self.round
+    }
+
+    async fn slot(&self) -> i32 {
+        self.slot
+    }
+
+    async fn first_player(&self) -> Option<Uuid> {
+        self.first_player
+    }
+
+    async fn second_player(&self) -> Option<Uuid> {
+        self.second_player
+    }
+
+    /// Set once one of players won enough games or advanced without playing
+    async fn winner(&self) -> Option<Uuid> {
+        self.winner
+    }
+
+    /// Tournament match, null until both players are known or if match was a walkover
+    async fn match_id(&self) -> Option<Uuid> {
+        self.match_id
+    }
+
+    async fn winner_next(&self) -> Option<Uuid> {
+        self.winner_next
+    }
+
+    async fn winner_next_position(&self) -> Option<i32> {
+        self.winner_next_position
+    }
+
+    async fn loser_next(&self) -> Option<Uuid> {
+        self.loser_next
+    }
+
+    async fn loser_next_position(&self) -> Option<i32> {
+        self.loser_next_position
+    }
+
+    async fn match_data<'a>(&self, context: &Context<'a>) -> Result<Option<MatchModel>, async_graphql::Error> {
+        let Some(match_id) = self.match_id else {
+            return Ok(None);
+        };
+        let loader = context.data::<DataLoader<MatchLoader>>()?;
+        loader.load_one(match_id).await.map_err(|error| error.extend())
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod bracket;
+pub(crate) mod bracket_match;
@@ -0,0 +1,2 @@
+pub use super::service::BracketService;
+pub use super::models::{bracket::{BracketFormat, BracketModel}, bracket_match::{BracketMatchModel, BracketSection}};
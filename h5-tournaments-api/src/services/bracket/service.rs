@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use sea_orm::{ActiveModelTrait, ActiveValue::{NotSet, Set}, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use uuid::Uuid;
+
+use crate::services::{rating::seeding_ratings, tournament::{error::Error, models::{game_builder::{self, GameResult}, match_structure, participant, tournament}}};
+
+use super::{engine, models::{bracket::{self, BracketFormat, BracketModel}, bracket_match::{self, BracketMatchModel, BracketSection}}};
+
+#[derive(Clone)]
+pub struct BracketService;
+
+impl BracketService {
+    /// Builds bracket of tournament participants and creates matches that can be played right away.
+    /// Explicitly seeded players go first in given order, the rest are seeded by their rating in tournament's mod.
+    pub async fn generate_bracket(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        format: BracketFormat,
+        best_of: i32,
+        group_number: Option<i32>,
+        seeds: Vec<Uuid>
+    ) -> Result<BracketModel, Error> {
+        if best_of < 1 || best_of % 2 == 0 {
+            return Err(Error::Validation(format!("Matches must be played to an odd number of games, got {}", best_of)));
+        }
+
+        let transaction = db.begin().await?;
+        let tournament = tournament::Entity::find_by_id(tournament_id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let existing = bracket::Entity::find()
+            .filter(bracket::Column::TournamentId.eq(tournament_id))
+            .all(&transaction)
+            .await?;
+        if existing.iter().any(|bracket| bracket.group_number == group_number) {
+            return Err(Error::Conflict("Bracket for these participants already exists".to_string()));
+        }
+
+        let participants = participant::Entity::find()
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .filter(|participant| group_number.is_none_or(|group| participant.group_number == group))
+            .map(|participant| participant.user_id)
+            .collect::<HashSet<Uuid>>();
+
+        let mut players = vec![];
+        for user_id in seeds {
+            if !participants.contains(&user_id) {
+                return Err(Error::Validation(format!("Seeded user {} doesn't participate in bracket", user_id)));
+            }
+            if players.contains(&user_id) {
+                return Err(Error::Validation(format!("User {} is seeded more than once", user_id)));
+            }
+            players.push(user_id);
+        }
+
+        // unrated players go last
+        let ratings = seeding_ratings(&transaction, participants.iter().copied(), tournament.mod_type).await?;
+        let mut unseeded = participants.into_iter().filter(|user_id| !players.contains(user_id)).collect::<Vec<Uuid>>();
+        unseeded.sort_by(|first, second| {
+            let first_rating = ratings.get(first).copied().unwrap_or(f64::MIN);
+            let second_rating = ratings.get(second).copied().unwrap_or(f64::MIN);
+            second_rating.total_cmp(&first_rating).then(first.cmp(second))
+        });
+        players.extend(unseeded);
+
+        if players.len() < engine::min_players(format) {
+            return Err(Error::Validation(format!("At least {} participants are required for this bracket, got {}", engine::min_players(format), players.len())));
+        }
+
+        let bracket = bracket::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tournament_id: Set(tournament_id),
+            format: Set(format),
+            group_number: Set(group_number),
+            best_of: Set(best_of),
+            created_at: NotSet
+        }.insert(&transaction).await?;
+
+        let planned = engine::plan(format, &players);
+        let ids = planned.iter().map(|_| Uuid::new_v4()).collect::<Vec<Uuid>>();
+        let matches = planned.into_iter().enumerate().map(|(index, planned)| bracket_match::ActiveModel {
+            id: Set(ids[index]),
+            bracket_id: Set(bracket.id),
+            section: Set(planned.section),
+            round: Set(planned.round),
+            slot: Set(planned.slot),
+            first_player: Set(planned.first_player),
+            second_player: Set(planned.second_player),
+            winner: Set(None),
+            match_id: Set(None),
+            winner_next: Set(planned.winner_next.map(|(next, _)| ids[next])),
+            winner_next_position: Set(planned.winner_next.map(|(_, position)| position)),
+            loser_next: Set(planned.loser_next.map(|(next, _)| ids[next])),
+            loser_next_position: Set(planned.loser_next.map(|(_, position)| position))
+        }).collect::<Vec<bracket_match::ActiveModel>>();
+        bracket_match::Entity::insert_many(matches).exec(&transaction).await?;
+
+        advance_bracket(&transaction, &bracket).await?;
+        transaction.commit().await?;
+        Ok(bracket)
+    }
+
+    pub async fn get_brackets(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid
+    ) -> Result<Vec<BracketModel>, Error> {
+        Ok(bracket::Entity::find()
+            .filter(bracket::Column::TournamentId.eq(tournament_id))
+            .order_by_asc(bracket::Column::CreatedAt)
+            .all(db)
+            .await?)
+    }
+
+    pub async fn get_bracket(
+        &self,
+        db: &DatabaseConnection,
+        id: Uuid
+    ) -> Result<Option<BracketModel>, Error> {
+        Ok(bracket::Entity::find_by_id(id).one(db).await?)
+    }
+
+    pub async fn get_bracket_matches(
+        &self,
+        db: &DatabaseConnection,
+        bracket_id: Uuid
+    ) -> Result<Vec<BracketMatchModel>, Error> {
+        load_bracket_matches(db, bracket_id).await
+    }
+
+    /// Removes bracket with its matches. Matches that already have games are kept as regular tournament matches.
+    pub async fn delete_bracket(
+        &self,
+        db: &DatabaseConnection,
+        id: Uuid
+    ) -> Result<(), Error> {
+        let transaction = db.begin().await?;
+        let bracket = bracket::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No bracket found with id {}", id)))?;
+
+        let match_ids = load_bracket_matches(&transaction, bracket.id)
+            .await?
+            .into_iter()
+            .filter_map(|bracket_match| bracket_match.match_id)
+            .collect::<Vec<Uuid>>();
+        let played = game_builder::Entity::find()
+            .select_only()
+            .column(game_builder::Column::MatchId)
+            .filter(game_builder::Column::MatchId.is_in(match_ids.clone()))
+            .into_tuple::<Uuid>()
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .collect::<HashSet<Uuid>>();
+
+        bracket::Entity::delete_by_id(bracket.id).exec(&transaction).await?;
+        match_structure::Entity::delete_many()
+            .filter(match_structure::Column::Id.is_in(match_ids.into_iter().filter(|id| !played.contains(id))))
+            .exec(&transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Decides bracket match of given game once one of players won enough games and advances bracket.
+    pub async fn record_game_result(
+        &self,
+        db: &DatabaseConnection,
+        game_id: Uuid
+    ) -> Result<(), Error> {
+        let game = game_builder::Entity::find_by_id(game_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No game found with id {}", game_id)))?;
+        self.record_match_result(db, game.match_id).await
+    }
+
+    /// Decides bracket match once one of players won enough games and advances bracket.
+    /// Does nothing for matches outside of brackets. Games changed after match was decided don't affect bracket.
+    pub async fn record_match_result(
+        &self,
+        db: &DatabaseConnection,
+        match_id: Uuid
+    ) -> Result<(), Error> {
+        let Some(bracket_id) = bracket_match::Entity::find()
+            .select_only()
+            .column(bracket_match::Column::BracketId)
+            .filter(bracket_match::Column::MatchId.eq(match_id))
+            .into_tuple::<Uuid>()
+            .one(db)
+            .await? else {
+            return Ok(());
+        };
+
+        // concurrent reports of the same bracket would advance players twice
+        let transaction = db.begin().await?;
+        let Some(bracket) = bracket::Entity::find_by_id(bracket_id).lock_exclusive().one(&transaction).await? else {
+            return Ok(());
+        };
+        let Some(bracket_match) = bracket_match::Entity::find()
+            .filter(bracket_match::Column::MatchId.eq(match_id))
+            .one(&transaction)
+            .await? else {
+            return Ok(());
+        };
+        if bracket_match.winner.is_some() {
+            return Ok(());
+        }
+
+        let (mut first_player_wins, mut second_player_wins) = (0, 0);
+        for game in game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.eq(match_id))
+            .all(&transaction)
+            .await? {
+            match game.result {
+                GameResult::FirstPlayerWon => first_player_wins += 1,
+                GameResult::SecondPlayerWon => second_player_wins += 1,
+                GameResult::NotSelected => {}
+            }
+        }
+
+        // players of reported match may be swapped relative to bracket, results are read in match order
+        let Some(match_model) = match_structure::Entity::find_by_id(match_id).one(&transaction).await? else {
+            return Ok(());
+        };
+        let required_wins = bracket.best_of / 2 + 1;
+        let winner = if first_player_wins >= required_wins {
+            match_model.first_player
+        } else if second_player_wins >= required_wins {
+            match_model.second_player
+        } else {
+            return Ok(());
+        };
+
+        let mut match_to_update = bracket_match.into_active_model();
+        match_to_update.winner = Set(Some(winner));
+        match_to_update.update(&transaction).await?;
+
+        advance_bracket(&transaction, &bracket).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+async fn load_bracket_matches<C: ConnectionTrait>(db: &C, bracket_id: Uuid) -> Result<Vec<BracketMatchModel>, Error> {
+    Ok(bracket_match::Entity::find()
+        .filter(bracket_match::Column::BracketId.eq(bracket_id))
+        .order_by_asc(bracket_match::Column::Section)
+        .order_by_asc(bracket_match::Column::Round)
+        .order_by_asc(bracket_match::Column::Slot)
+        .all(db)
+        .await?)
+}
+
+/// Fills players of matches whose previous matches are decided and creates tournament matches for ones ready to be played.
+async fn advance_bracket<C: ConnectionTrait>(db: &C, bracket: &BracketModel) -> Result<(), Error> {
+    let stage = tournament::Entity::find_by_id(bracket.tournament_id).one(db).await?.and_then(|tournament| tournament.stage);
+    let mut matches = load_bracket_matches(db, bracket.id).await?;
+    let changed = engine::resolve(&mut matches).into_iter().collect::<HashSet<usize>>();
+
+    for (index, bracket_match) in matches.into_iter().enumerate() {
+        let match_id = match (bracket_match.first_player, bracket_match.second_player, bracket_match.winner, bracket_match.match_id) {
+            (Some(first_player), Some(second_player), None, None) => {
+                let match_model = match_structure::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tournament_id: Set(bracket.tournament_id),
+                    // bracket matches aren't created by discord messages
+                    message_id: Set(None),
+                    first_player: Set(first_player),
+                    second_player: Set(second_player),
+                    challonge_id: Set(None),
+                    report_link: Set(None),
+                    // rounds of elimination sections overlap, so only round robin rounds are meaningful for the match itself
+                    round: Set((bracket_match.section == BracketSection::RoundRobin).then_some(bracket_match.round)),
+                    deadline: Set(None),
+                    stage: Set(stage),
+                    created_at: NotSet
+                }.insert(db).await?;
+                Some(match_model.id)
+            },
+            _ => None
+        };
+
+        if match_id.is_none() && !changed.contains(&index) {
+            continue;
+        }
+        let mut match_to_update = bracket_match.clone().into_active_model();
+        match_to_update.first_player = Set(bracket_match.first_player);
+        match_to_update.second_player = Set(bracket_match.second_player);
+        match_to_update.winner = Set(bracket_match.winner);
+        match_to_update.match_id = Set(match_id.or(bracket_match.match_id));
+        match_to_update.update(db).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub use super::service::CatalogueService;
+pub use super::types::{CatalogueModel, HeroUpdateInput, LocalizedNameInput, ModUpdateInput, RaceUpdateInput};
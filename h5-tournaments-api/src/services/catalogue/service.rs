@@ -0,0 +1,449 @@
+use std::collections::{BTreeMap, HashSet};
+
+use sea_orm::{prelude::Expr, sea_query::Query, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait};
+
+use crate::services::tournament::{error::Error, models::{game_builder, game_mod::{self, GameModModel}, heroes::{self, HeroNew, HeroesNew}, match_structure, race::{self, DisplayNames, NameVariantList, RaceModel}, tournament}, types::ModId};
+
+use super::types::{CatalogueModel, HeroUpdateInput, LocalizedNameInput, ModUpdateInput, RaceUpdateInput};
+
+#[derive(Clone)]
+pub struct CatalogueService;
+
+impl CatalogueService {
+    pub async fn get_mods(&self, db: &DatabaseConnection, active_only: bool) -> Result<Vec<GameModModel>, Error> {
+        let mut query = game_mod::Entity::find();
+        if active_only {
+            query = query.filter(game_mod::Column::Active.eq(true));
+        }
+        Ok(query.order_by_asc(game_mod::Column::Id).all(db).await?)
+    }
+
+    pub async fn create_mod(&self, db: &DatabaseConnection, name: String, version: Option<String>) -> Result<GameModModel, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let mods = game_mod::Entity::find().all(&transaction).await?;
+        let game_mod = GameModModel {
+            id: ModId(mods.iter().map(|game_mod| game_mod.id.0).max().unwrap_or(-1) + 1),
+            name: normalize_name(&name)?,
+            version: normalize_version(version),
+            active: true
+        };
+        check_mod_name(&mods, &game_mod)?;
+
+        game_mod::Entity::insert(game_mod::ActiveModel {
+            id: Set(game_mod.id),
+            name: Set(game_mod.name.clone()),
+            version: Set(game_mod.version.clone()),
+            active: Set(game_mod.active)
+        })
+        .exec(&transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(game_mod)
+    }
+
+    pub async fn update_mod(&self, db: &DatabaseConnection, id: ModId, changes: ModUpdateInput) -> Result<GameModModel, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let mods = game_mod::Entity::find().all(&transaction).await?;
+        let mut game_mod = mods.iter()
+            .find(|game_mod| game_mod.id == id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No mod found with id {}", id)))?;
+
+        if let Some(name) = changes.name {
+            game_mod.name = normalize_name(&name)?;
+        }
+        if let Some(version) = changes.version {
+            game_mod.version = normalize_version(Some(version));
+        }
+        if let Some(active) = changes.active {
+            game_mod.active = active;
+        }
+        check_mod_name(&mods, &game_mod)?;
+
+        game_mod::Entity::update(game_mod::ActiveModel {
+            id: Set(game_mod.id),
+            name: Set(game_mod.name.clone()),
+            version: Set(game_mod.version.clone()),
+            active: Set(game_mod.active)
+        })
+        .exec(&transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(game_mod)
+    }
+
+    pub async fn get_races(&self, db: &DatabaseConnection) -> Result<Vec<RaceModel>, Error> {
+        let races = race::Entity::find()
+            .order_by_asc(race::Column::Id)
+            .all(db)
+            .await?;
+        Ok(races)
+    }
+
+    /// Races and heroes of mod. Mods no heroes were added to yet have empty catalogue of version 0.
+    pub async fn get_catalogue(&self, db: &DatabaseConnection, mod_type: ModId) -> Result<CatalogueModel, Error> {
+        let game_mod = game_mod::Entity::find_by_id(mod_type)
+            .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No mod found with id {}", mod_type)))?;
+        let races = race::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(race::Column::ModId.is_null())
+                    .add(race::Column::ModId.eq(mod_type))
+            )
+            .order_by_asc(race::Column::Id)
+            .all(db)
+            .await?;
+        let catalogue = heroes::Entity::find()
+            .filter(heroes::Column::ModType.eq(mod_type))
+            .one(db)
+            .await?;
+        let (version, heroes) = match catalogue {
+            Some(catalogue) => (catalogue.version, catalogue.heroes.entities),
+            None => (0, vec![])
+        };
+        Ok(CatalogueModel { game_mod, version, races, heroes })
+    }
+
+    /// Race without mod is shared by all mods.
+    pub async fn create_race(
+        &self,
+        db: &DatabaseConnection,
+        name: String,
+        mod_type: Option<ModId>,
+        display_names: Vec<LocalizedNameInput>,
+        name_variants: Vec<String>
+    ) -> Result<RaceModel, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        if let Some(mod_type) = mod_type {
+            check_mod_exists(&transaction, mod_type).await?;
+        }
+        let races = race::Entity::find().all(&transaction).await?;
+        let race = RaceModel {
+            id: races.iter().map(|race| race.id).max().unwrap_or(0) + 1,
+            name: normalize_name(&name)?,
+            mod_id: mod_type,
+            display_names: normalize_display_names(display_names)?,
+            name_variants: NameVariantList(normalize_variants(name_variants))
+        };
+        check_names_clash("Race", &race_names(&races), race.id, &race.name, &race.name_variants.0)?;
+
+        race::Entity::insert(race::ActiveModel {
+            id: Set(race.id),
+            name: Set(race.name.clone()),
+            mod_id: Set(race.mod_id),
+            display_names: Set(race.display_names.clone()),
+            name_variants: Set(race.name_variants.clone())
+        })
+        .exec(&transaction)
+        .await?;
+        bump_versions(&transaction, race.mod_id).await?;
+        transaction.commit().await?;
+        Ok(race)
+    }
+
+    pub async fn update_race(&self, db: &DatabaseConnection, id: i32, changes: RaceUpdateInput) -> Result<RaceModel, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let races = race::Entity::find().all(&transaction).await?;
+        let mut race = races.iter()
+            .find(|race| race.id == id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No race found with id {}", id)))?;
+
+        if let Some(name) = changes.name {
+            race.name = normalize_name(&name)?;
+        }
+        if let Some(display_names) = changes.display_names {
+            race.display_names = normalize_display_names(display_names)?;
+        }
+        if let Some(name_variants) = changes.name_variants {
+            race.name_variants = NameVariantList(normalize_variants(name_variants));
+        }
+        check_names_clash("Race", &race_names(&races), race.id, &race.name, &race.name_variants.0)?;
+
+        race::Entity::update(race::ActiveModel {
+            id: Set(race.id),
+            name: Set(race.name.clone()),
+            mod_id: Set(race.mod_id),
+            display_names: Set(race.display_names.clone()),
+            name_variants: Set(race.name_variants.clone())
+        })
+        .exec(&transaction)
+        .await?;
+        bump_versions(&transaction, race.mod_id).await?;
+        transaction.commit().await?;
+        Ok(race)
+    }
+
+    /// Races can't be deleted while any hero or game refers to them.
+    pub async fn delete_race(&self, db: &DatabaseConnection, id: i32) -> Result<(), Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let race = race::Entity::find_by_id(id)
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No race found with id {}", id)))?;
+
+        let catalogues = heroes::Entity::find().all(&transaction).await?;
+        if let Some(catalogue) = catalogues.iter().find(|catalogue| catalogue.heroes.entities.iter().any(|hero| hero.race == id)) {
+            return Err(Error::Conflict(format!("Race {} has heroes in catalogue of mod {}", id, catalogue.mod_type)));
+        }
+        let games_count = game_builder::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(game_builder::Column::FirstPlayerRace.eq(id))
+                    .add(game_builder::Column::SecondPlayerRace.eq(id))
+            )
+            .count(&transaction)
+            .await?;
+        if games_count > 0 {
+            return Err(Error::Conflict(format!("Race {} is used in {} games", id, games_count)));
+        }
+
+        race::Entity::delete_by_id(id).exec(&transaction).await?;
+        bump_versions(&transaction, race.mod_id).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    pub async fn create_hero(
+        &self,
+        db: &DatabaseConnection,
+        mod_type: ModId,
+        race: i32,
+        name: String,
+        display_names: Vec<LocalizedNameInput>,
+        name_variants: Vec<String>
+    ) -> Result<HeroNew, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        check_mod_exists(&transaction, mod_type).await?;
+        check_race_available(&transaction, race, mod_type).await?;
+        let mut catalogue = get_or_create_catalogue(&transaction, mod_type).await?;
+        let hero = HeroNew {
+            id: catalogue.entities.iter().map(|hero| hero.id).max().unwrap_or(0) + 1,
+            race,
+            name: normalize_name(&name)?,
+            display_names: normalize_display_names(display_names)?,
+            name_variants: normalize_variants(name_variants)
+        };
+        check_names_clash("Hero", &hero_names(&catalogue.entities), hero.id, &hero.name, &hero.name_variants)?;
+
+        catalogue.entities.push(hero.clone());
+        save_catalogue(&transaction, mod_type, catalogue).await?;
+        transaction.commit().await?;
+        Ok(hero)
+    }
+
+    pub async fn update_hero(
+        &self,
+        db: &DatabaseConnection,
+        mod_type: ModId,
+        id: i32,
+        changes: HeroUpdateInput
+    ) -> Result<HeroNew, Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let mut catalogue = get_or_create_catalogue(&transaction, mod_type).await?;
+        let mut hero = catalogue.entities.iter()
+            .find(|hero| hero.id == id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No hero found with id {} in catalogue of mod {}", id, mod_type)))?;
+
+        if let Some(name) = changes.name {
+            hero.name = normalize_name(&name)?;
+        }
+        if let Some(race) = changes.race {
+            check_race_available(&transaction, race, mod_type).await?;
+            hero.race = race;
+        }
+        if let Some(display_names) = changes.display_names {
+            hero.display_names = normalize_display_names(display_names)?;
+        }
+        if let Some(name_variants) = changes.name_variants {
+            hero.name_variants = normalize_variants(name_variants);
+        }
+        check_names_clash("Hero", &hero_names(&catalogue.entities), hero.id, &hero.name, &hero.name_variants)?;
+
+        if let Some(existing) = catalogue.entities.iter_mut().find(|existing| existing.id == id) {
+            *existing = hero.clone();
+        }
+        save_catalogue(&transaction, mod_type, catalogue).await?;
+        transaction.commit().await?;
+        Ok(hero)
+    }
+
+    /// Heroes can't be deleted while any game of tournament with this mod refers to them.
+    pub async fn delete_hero(&self, db: &DatabaseConnection, mod_type: ModId, id: i32) -> Result<(), Error> {
+        let transaction = begin_catalogue_change(db).await?;
+        let mut catalogue = get_or_create_catalogue(&transaction, mod_type).await?;
+        if !catalogue.entities.iter().any(|hero| hero.id == id) {
+            return Err(Error::NotFound(format!("No hero found with id {} in catalogue of mod {}", id, mod_type)));
+        }
+
+        let games_count = game_builder::Entity::find()
+            .join(JoinType::InnerJoin, game_builder::Relation::Match.def())
+            .join(JoinType::InnerJoin, match_structure::Relation::Tournament.def())
+            .filter(tournament::Column::ModType.eq(mod_type))
+            .filter(
+                Condition::any()
+                    .add(game_builder::Column::FirstPlayerHero.eq(id))
+                    .add(game_builder::Column::SecondPlayerHero.eq(id))
+            )
+            .count(&transaction)
+            .await?;
+        if games_count > 0 {
+            return Err(Error::Conflict(format!("Hero {} is used in {} games", id, games_count)));
+        }
+
+        catalogue.entities.retain(|hero| hero.id != id);
+        save_catalogue(&transaction, mod_type, catalogue).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+/// Catalogue changes are rare, so they are simply serialized to keep ids and versions consistent.
+async fn begin_catalogue_change(db: &DatabaseConnection) -> Result<DatabaseTransaction, Error> {
+    let transaction = db.begin().await?;
+    transaction.execute_unprepared("LOCK TABLE mods, races, heroes_new IN EXCLUSIVE MODE").await?;
+    Ok(transaction)
+}
+
+async fn check_mod_exists(db: &DatabaseTransaction, id: ModId) -> Result<(), Error> {
+    match game_mod::Entity::find_by_id(id).one(db).await? {
+        Some(_) => Ok(()),
+        None => Err(Error::Validation(format!("No mod found with id {}", id)))
+    }
+}
+
+/// Heroes may belong only to shared races or to races of their own mod.
+async fn check_race_available(db: &DatabaseTransaction, id: i32, mod_type: ModId) -> Result<(), Error> {
+    match race::Entity::find_by_id(id).one(db).await? {
+        Some(race) if race.mod_id.is_none_or(|race_mod| race_mod == mod_type) => Ok(()),
+        Some(race) => Err(Error::Validation(format!("Race {} belongs to another mod {}", id, race.mod_id.unwrap_or_default()))),
+        None => Err(Error::Validation(format!("No race found with id {}", id)))
+    }
+}
+
+async fn get_or_create_catalogue(db: &DatabaseTransaction, mod_type: ModId) -> Result<HeroesNew, Error> {
+    let catalogue = heroes::Entity::find()
+        .filter(heroes::Column::ModType.eq(mod_type))
+        .one(db)
+        .await?;
+    Ok(catalogue.map(|catalogue| catalogue.heroes).unwrap_or(HeroesNew { entities: vec![] }))
+}
+
+/// Writes heroes of mod increasing its catalogue version, row of mod is created on first change.
+async fn save_catalogue(db: &DatabaseTransaction, mod_type: ModId, mut catalogue: HeroesNew) -> Result<(), Error> {
+    catalogue.entities.sort_by_key(|hero| hero.id);
+    let existing = heroes::Entity::find()
+        .filter(heroes::Column::ModType.eq(mod_type))
+        .one(db)
+        .await?;
+    match existing {
+        Some(existing) => {
+            heroes::Entity::update(heroes::ActiveModel {
+                id: Set(existing.id),
+                heroes: Set(catalogue),
+                version: Set(existing.version + 1),
+                ..Default::default()
+            })
+            .exec(db)
+            .await?;
+        },
+        None => {
+            heroes::Entity::insert(heroes::ActiveModel {
+                mod_type: Set(mod_type),
+                heroes: Set(catalogue),
+                version: Set(1),
+                ..Default::default()
+            })
+            .exec(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Shared races are part of every catalogue, so their changes make all of them stale.
+async fn bump_versions(db: &DatabaseTransaction, mod_type: Option<ModId>) -> Result<(), Error> {
+    let mut query = Query::update()
+        .table(heroes::Entity)
+        .value(heroes::Column::Version, Expr::col(heroes::Column::Version).add(1))
+        .to_owned();
+    if let Some(mod_type) = mod_type {
+        query.and_where(heroes::Column::ModType.eq(mod_type));
+    }
+    db.execute(db.get_database_backend().build(&query)).await?;
+    Ok(())
+}
+
+fn race_names(races: &[RaceModel]) -> Vec<(i32, Vec<String>)> {
+    races.iter()
+        .map(|race| (race.id, std::iter::once(race.name.clone()).chain(race.name_variants.0.iter().cloned()).collect()))
+        .collect()
+}
+
+fn hero_names(heroes: &[HeroNew]) -> Vec<(i32, Vec<String>)> {
+    heroes.iter()
+        .map(|hero| (hero.id, std::iter::once(hero.name.clone()).chain(hero.name_variants.iter().cloned()).collect()))
+        .collect()
+}
+
+/// Names and variants must identify a single entry, otherwise parser can't tell which one report mentions.
+fn check_names_clash(kind: &str, existing: &[(i32, Vec<String>)], id: i32, name: &str, variants: &[String]) -> Result<(), Error> {
+    for candidate in std::iter::once(name).chain(variants.iter().map(String::as_str)) {
+        let candidate = candidate.to_lowercase();
+        if let Some((other_id, _)) = existing.iter()
+            .filter(|(other_id, _)| *other_id != id)
+            .find(|(_, names)| names.iter().any(|other| other.to_lowercase() == candidate)) {
+            return Err(Error::Conflict(format!("{} {} already has name or variant {}", kind, other_id, candidate)));
+        }
+    }
+    Ok(())
+}
+
+fn check_mod_name(mods: &[GameModModel], game_mod: &GameModModel) -> Result<(), Error> {
+    match mods.iter().find(|other| other.id != game_mod.id && other.name.to_lowercase() == game_mod.name.to_lowercase()) {
+        Some(other) => Err(Error::Conflict(format!("Mod {} already has name {}", other.id, other.name))),
+        None => Ok(())
+    }
+}
+
+fn normalize_version(version: Option<String>) -> Option<String> {
+    version
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+fn normalize_name(name: &str) -> Result<String, Error> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err(Error::Validation("Name can't be empty".to_string()))
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+fn normalize_display_names(display_names: Vec<LocalizedNameInput>) -> Result<DisplayNames, Error> {
+    let mut names = BTreeMap::new();
+    for display_name in display_names {
+        let locale = display_name.locale.trim().to_lowercase();
+        if locale.is_empty() {
+            return Err(Error::Validation("Locale of display name can't be empty".to_string()));
+        }
+        let name = normalize_name(&display_name.name)?;
+        if names.insert(locale.clone(), name).is_some() {
+            return Err(Error::Validation(format!("Display name for locale {} is given more than once", locale)));
+        }
+    }
+    Ok(DisplayNames(names))
+}
+
+/// Trims variants dropping empty and repeated ones.
+fn normalize_variants(variants: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    variants.into_iter()
+        .map(|variant| variant.trim().to_string())
+        .filter(|variant| !variant.is_empty() && seen.insert(variant.to_lowercase()))
+        .collect()
+}
@@ -0,0 +1,48 @@
+use crate::services::tournament::models::{game_mod::GameModModel, heroes::HeroNew, race::RaceModel};
+
+#[derive(Debug, Clone, async_graphql::InputObject)]
+pub struct LocalizedNameInput {
+    /// Locale code like `ru` or `en`
+    pub locale: String,
+    pub name: String
+}
+
+/// Changes of race, fields left empty are kept as is.
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct RaceUpdateInput {
+    pub name: Option<String>,
+    /// Replaces all display names of race
+    pub display_names: Option<Vec<LocalizedNameInput>>,
+    /// Replaces all name variants of race
+    pub name_variants: Option<Vec<String>>
+}
+
+/// Changes of hero, fields left empty are kept as is.
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct HeroUpdateInput {
+    pub name: Option<String>,
+    pub race: Option<i32>,
+    /// Replaces all display names of hero
+    pub display_names: Option<Vec<LocalizedNameInput>>,
+    /// Replaces all name variants of hero
+    pub name_variants: Option<Vec<String>>
+}
+
+/// Changes of mod, fields left empty are kept as is.
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct ModUpdateInput {
+    pub name: Option<String>,
+    /// Empty string removes version
+    pub version: Option<String>,
+    pub active: Option<bool>
+}
+
+/// Races and heroes of a single mod, version allows clients to detect stale cached catalogue.
+#[derive(Debug, Clone, async_graphql::SimpleObject)]
+pub struct CatalogueModel {
+    #[graphql(name = "mod")]
+    pub game_mod: GameModModel,
+    pub version: i32,
+    pub races: Vec<RaceModel>,
+    pub heroes: Vec<HeroNew>
+}
@@ -0,0 +1,2 @@
+pub use super::service::{ExportService, EXPORT_FORMAT_VERSION};
+pub use super::types::TournamentExportModel;
@@ -0,0 +1,203 @@
+use std::{collections::HashMap, io::{Cursor, Write}, time::SystemTime};
+
+use sea_orm::{prelude::{DateTimeUtc, DateTimeWithTimeZone}, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::services::tournament::{error::Error, models::{game_builder, heroes, match_structure, participant, race, tournament, user}};
+
+use super::types::{ExportedGameModel, ExportedMatchModel, ExportedParticipantModel, ExportedTournamentModel, ExportedUserModel, TournamentExportModel};
+
+/// Current version of export documents, must be increased on breaking changes of their layout.
+/// Version 2 stores mod of tournament as id from mods registry instead of enum variant name.
+pub const EXPORT_FORMAT_VERSION: i32 = 2;
+
+#[derive(Clone)]
+pub struct ExportService;
+
+impl ExportService {
+    pub async fn export_tournament(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid
+    ) -> Result<TournamentExportModel, Error> {
+        let tournament = tournament::Entity::find_by_id(tournament_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let participants = participant::Entity::find()
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .order_by_asc(participant::Column::GroupNumber)
+            .order_by_asc(participant::Column::Id)
+            .all(db)
+            .await?;
+
+        let matches = match_structure::Entity::find()
+            .filter(match_structure::Column::TournamentId.eq(tournament_id))
+            .order_by_asc(match_structure::Column::CreatedAt)
+            .order_by_asc(match_structure::Column::Id)
+            .all(db)
+            .await?;
+
+        let games = game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.is_in(matches.iter().map(|match_model| match_model.id).collect::<Vec<Uuid>>()))
+            .order_by_asc(game_builder::Column::CreatedAt)
+            .order_by_asc(game_builder::Column::Id)
+            .all(db)
+            .await?;
+
+        let user_ids = participants.iter().map(|participant| participant.user_id)
+            .chain(matches.iter().flat_map(|match_model| [match_model.first_player, match_model.second_player]))
+            .collect::<Vec<Uuid>>();
+        let users = user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids))
+            .order_by_asc(user::Column::Nickname)
+            .all(db)
+            .await?;
+
+        let races = race::Entity::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|race| (race.id, race.name))
+            .collect::<HashMap<i32, String>>();
+
+        let heroes = heroes::Entity::find()
+            .filter(heroes::Column::ModType.eq(tournament.mod_type))
+            .one(db)
+            .await?
+            .map(|heroes| heroes.heroes.entities.into_iter().map(|hero| (hero.id, hero.name)).collect::<HashMap<i32, String>>())
+            .unwrap_or_default();
+
+        let exported_at = DateTimeWithTimeZone::from(DateTimeUtc::from(SystemTime::now()));
+        Ok(build_export(exported_at, tournament, users, participants, matches, games, &races, &heroes))
+    }
+
+    /// Packs export into zip archive with a csv file for tournament, users, participants, matches and games.
+    pub fn export_csv_bundle(
+        &self,
+        export: &TournamentExportModel
+    ) -> Result<Vec<u8>, Error> {
+        let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+        write_csv(&mut archive, "tournament.csv", std::slice::from_ref(&export.tournament))?;
+        write_csv(&mut archive, "users.csv", &export.users)?;
+        write_csv(&mut archive, "participants.csv", &export.participants)?;
+        write_csv(&mut archive, "matches.csv", &export.matches)?;
+        write_csv(&mut archive, "games.csv", &export.games)?;
+        let archive = archive.finish()
+            .map_err(|error| Error::Internal(format!("Failed to pack export archive: {}", error)))?;
+        Ok(archive.into_inner())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_export(
+    exported_at: DateTimeWithTimeZone,
+    tournament: tournament::Model,
+    users: Vec<user::Model>,
+    participants: Vec<participant::Model>,
+    matches: Vec<match_structure::Model>,
+    games: Vec<game_builder::Model>,
+    races: &HashMap<i32, String>,
+    heroes: &HashMap<i32, String>
+) -> TournamentExportModel {
+    let nicknames = users.iter().map(|user| (user.id, user.nickname.clone())).collect::<HashMap<Uuid, String>>();
+    let nickname = |user_id: Uuid| nicknames.get(&user_id).cloned().unwrap_or_default();
+    let race_name = |race: Option<i32>| race.and_then(|race| races.get(&race).cloned());
+    let hero_name = |hero: Option<i32>| hero.and_then(|hero| heroes.get(&hero).cloned());
+
+    let mut numbers = HashMap::new();
+    let games = games.into_iter()
+        .map(|game| {
+            let number = numbers.entry(game.match_id).or_insert(0);
+            *number += 1;
+            ExportedGameModel {
+                id: game.id,
+                match_id: game.match_id,
+                number: *number,
+                first_player_race: race_name(game.first_player_race),
+                first_player_hero: hero_name(game.first_player_hero),
+                second_player_race: race_name(game.second_player_race),
+                second_player_hero: hero_name(game.second_player_hero),
+                bargains_color: game.bargains_color,
+                bargains_amount: game.bargains_amount,
+                result: game.result,
+                outcome: game.outcome,
+                created_at: game.created_at
+            }
+        })
+        .collect();
+
+    TournamentExportModel {
+        version: EXPORT_FORMAT_VERSION,
+        exported_at,
+        participants: participants.into_iter()
+            .map(|participant| ExportedParticipantModel {
+                id: participant.id,
+                user_id: participant.user_id,
+                nickname: nickname(participant.user_id),
+                group_number: participant.group_number,
+                challonge_id: participant.challonge_id
+            })
+            .collect(),
+        matches: matches.into_iter()
+            .map(|match_model| ExportedMatchModel {
+                id: match_model.id,
+                message_id: match_model.message_id,
+                challonge_id: match_model.challonge_id,
+                first_player_id: match_model.first_player,
+                first_player_nickname: nickname(match_model.first_player),
+                second_player_id: match_model.second_player,
+                second_player_nickname: nickname(match_model.second_player),
+                report_link: match_model.report_link,
+                round: match_model.round,
+                deadline: match_model.deadline,
+                stage: match_model.stage,
+                created_at: Some(match_model.created_at)
+            })
+            .collect(),
+        games,
+        users: users.into_iter()
+            .map(|user| ExportedUserModel {
+                id: user.id,
+                discord_id: user.discord_id,
+                discord_nick: user.discord_nick,
+                nickname: user.nickname
+            })
+            .collect(),
+        tournament: ExportedTournamentModel {
+            id: tournament.id,
+            name: tournament.name,
+            stage: tournament.stage,
+            game_type: tournament.game_type,
+            mod_type: tournament.mod_type,
+            with_bargains: tournament.with_bargains,
+            with_bargains_color: tournament.with_bargains_color,
+            with_foreign_heroes: tournament.with_foreign_heroes,
+            challonge_id: tournament.challonge_id,
+            organizer_id: tournament.organizer,
+            operator_id: tournament.operator_id,
+            channel_id: tournament.channel_id,
+            register_channel: tournament.register_channel,
+            role_id: tournament.role_id
+        }
+    }
+}
+
+fn write_csv<T: Serialize>(
+    archive: &mut ZipWriter<Cursor<Vec<u8>>>,
+    name: &str,
+    rows: &[T]
+) -> Result<(), Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)
+            .map_err(|error| Error::Internal(format!("Failed to write {}: {}", name, error)))?;
+    }
+    let content = writer.into_inner()
+        .map_err(|error| Error::Internal(format!("Failed to write {}: {}", name, error)))?;
+
+    archive.start_file(name, SimpleFileOptions::default())
+        .and_then(|_| archive.write_all(&content).map_err(Into::into))
+        .map_err(|error| Error::Internal(format!("Failed to pack {}: {}", name, error)))
+}
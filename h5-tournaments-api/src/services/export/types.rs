@@ -0,0 +1,156 @@
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+use crate::{prelude::ModId, services::tournament::models::{game_builder::{BargainsColor, GameOutcome, GameResult}, tournament::{GameType, TournamentStage}}};
+
+/// Whole tournament in a single document, layout changes are tracked with `version`.
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct TournamentExportModel {
+    /// Version of export format, it's increased every time fields are renamed or removed
+    pub version: i32,
+    pub exported_at: DateTimeWithTimeZone,
+    pub tournament: ExportedTournamentModel,
+    /// Every user referenced by participants and matches
+    pub users: Vec<ExportedUserModel>,
+    pub participants: Vec<ExportedParticipantModel>,
+    pub matches: Vec<ExportedMatchModel>,
+    pub games: Vec<ExportedGameModel>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ExportedTournamentModel {
+    pub id: Uuid,
+    pub name: String,
+    pub stage: Option<TournamentStage>,
+    pub game_type: GameType,
+    #[serde(deserialize_with = "deserialize_mod")]
+    pub mod_type: ModId,
+    pub with_bargains: bool,
+    pub with_bargains_color: bool,
+    pub with_foreign_heroes: bool,
+    pub challonge_id: Option<String>,
+    pub organizer_id: Uuid,
+    pub operator_id: Uuid,
+    pub channel_id: Option<i64>,
+    pub register_channel: Option<i64>,
+    pub role_id: i64
+}
+
+/// Version 1 documents name mod by variant of former `ModType` enum, later ones keep its id from mods registry.
+fn deserialize_mod<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ModId, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ExportedMod {
+        Id(ModId),
+        Name(String)
+    }
+
+    match ExportedMod::deserialize(deserializer)? {
+        ExportedMod::Id(id) => Ok(id),
+        // registry ids were given the values of enum variants
+        ExportedMod::Name(name) => match name.as_str() {
+            "Universe" => Ok(ModId::UNIVERSE),
+            "Hrta" => Ok(ModId(1)),
+            _ => Err(D::Error::custom(format!("Unknown mod {}", name)))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ExportedUserModel {
+    pub id: Uuid,
+    pub discord_id: i64,
+    pub discord_nick: String,
+    pub nickname: String
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ExportedParticipantModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub nickname: String,
+    pub group_number: i32,
+    pub challonge_id: Option<String>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ExportedMatchModel {
+    pub id: Uuid,
+    /// Discord message match was reported with, null for matches created ahead of time that aren't reported yet
+    pub message_id: Option<i64>,
+    pub challonge_id: Option<String>,
+    pub first_player_id: Uuid,
+    pub first_player_nickname: String,
+    pub second_player_id: Uuid,
+    pub second_player_nickname: String,
+    pub report_link: Option<String>,
+    #[serde(default)]
+    pub round: Option<i32>,
+    #[serde(default)]
+    pub deadline: Option<DateTimeWithTimeZone>,
+    #[serde(default)]
+    pub stage: Option<TournamentStage>,
+    /// Missing in documents exported before matches kept it
+    #[serde(default)]
+    pub created_at: Option<DateTimeWithTimeZone>
+}
+
+/// Game with races and heroes resolved to their names. Ids that are missing in catalogue are exported as null names.
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct ExportedGameModel {
+    pub id: Uuid,
+    pub match_id: Uuid,
+    /// Position of game in its match, starting from 1
+    pub number: i32,
+    pub first_player_race: Option<String>,
+    pub first_player_hero: Option<String>,
+    pub second_player_race: Option<String>,
+    pub second_player_hero: Option<String>,
+    pub bargains_color: Option<BargainsColor>,
+    pub bargains_amount: Option<i32>,
+    pub result: GameResult,
+    pub outcome: GameOutcome,
+    pub created_at: Option<DateTimeWithTimeZone>
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::prelude::ModId;
+
+    use super::ExportedTournamentModel;
+
+    fn tournament(mod_type: serde_json::Value) -> serde_json::Result<ExportedTournamentModel> {
+        serde_json::from_value(json!({
+            "id": "00000000-0000-0000-0000-000000000001",
+            "name": "Cup",
+            "stage": null,
+            "game_type": "Rmg",
+            "mod_type": mod_type,
+            "with_bargains": false,
+            "with_bargains_color": false,
+            "with_foreign_heroes": false,
+            "challonge_id": null,
+            "organizer_id": "00000000-0000-0000-0000-000000000002",
+            "operator_id": "00000000-0000-0000-0000-000000000003",
+            "channel_id": 10,
+            "register_channel": 11,
+            "role_id": 12
+        }))
+    }
+
+    #[test]
+    fn mods_of_first_version_are_read_by_name() {
+        assert_eq!(tournament(json!("Universe")).unwrap().mod_type, ModId::UNIVERSE);
+        assert_eq!(tournament(json!("Hrta")).unwrap().mod_type, ModId(1));
+        assert!(tournament(json!("Unknown")).is_err());
+    }
+
+    #[test]
+    fn mods_of_later_versions_are_read_by_id() {
+        assert_eq!(tournament(json!(1)).unwrap().mod_type, ModId(1));
+        assert_eq!(tournament(json!(5)).unwrap().mod_type, ModId(5));
+    }
+}
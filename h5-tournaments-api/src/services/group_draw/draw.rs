@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+use uuid::Uuid;
+
+use super::types::GroupDrawMethod;
+
+/// Count of shuffles random draw tries before it settles for the one with the fewest violations.
+const RANDOM_DRAW_ATTEMPTS: usize = 32;
+
+/// Distributes players into `groups_count` groups, sizes of groups differ by one at most.
+/// Players must be ordered by seed for seeded methods. Players of the same `apart` set are placed
+/// into different groups whenever possible, returns groups and count of pairs that couldn't be separated.
+pub(crate) fn draw(
+    method: GroupDrawMethod,
+    mut players: Vec<Uuid>,
+    groups_count: usize,
+    apart: &[HashSet<Uuid>],
+    rng: &mut impl Rng
+) -> (Vec<Vec<Uuid>>, i32) {
+    if method != GroupDrawMethod::Random {
+        return deal(method, &players, groups_count, apart, rng);
+    }
+
+    // rows are dealt one by one, so an unlucky shuffle can leave no free group for a player of the last rows
+    let mut best: Option<(Vec<Vec<Uuid>>, i32)> = None;
+    for _ in 0..RANDOM_DRAW_ATTEMPTS {
+        players.shuffle(rng);
+        let (groups, violations) = deal(method, &players, groups_count, apart, rng);
+        if best.as_ref().is_none_or(|(_, best_violations)| violations < *best_violations) {
+            best = Some((groups, violations));
+        }
+        if violations == 0 {
+            break;
+        }
+    }
+    best.unwrap_or_default()
+}
+
+/// Deals players into groups row by row, every row takes a separate group for each of its players.
+fn deal(
+    method: GroupDrawMethod,
+    players: &[Uuid],
+    groups_count: usize,
+    apart: &[HashSet<Uuid>],
+    rng: &mut impl Rng
+) -> (Vec<Vec<Uuid>>, i32) {
+    let mut groups = vec![vec![]; groups_count];
+    for (row, players) in players.chunks(groups_count).enumerate() {
+        // order of groups players of the row are dealt into if nothing keeps them apart
+        let order = match method {
+            GroupDrawMethod::Random | GroupDrawMethod::Pots => {
+                let mut order = (0..groups_count).collect::<Vec<usize>>();
+                order.shuffle(rng);
+                order
+            },
+            GroupDrawMethod::Snake if row % 2 == 0 => (0..groups_count).collect(),
+            GroupDrawMethod::Snake => (0..groups_count).rev().collect()
+        };
+        // player prefers group it's dealt into, then the ones following it
+        let preferences = (0..players.len())
+            .map(|index| {
+                let mut preference = order.clone();
+                preference.rotate_left(index);
+                preference
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        let allowed = players.iter()
+            .map(|player| (0..groups_count).map(|group| conflicts(*player, &groups[group], apart) == 0).collect::<Vec<bool>>())
+            .collect::<Vec<Vec<bool>>>();
+
+        // every player of row takes a separate group, so it's a matching of players to groups they have no conflicts in
+        let mut owners: Vec<Option<usize>> = vec![None; groups_count];
+        let mut unplaced = vec![];
+        for player in 0..players.len() {
+            let mut visited = vec![false; groups_count];
+            if !place(player, &preferences, &allowed, &mut owners, &mut visited) {
+                unplaced.push(player);
+            }
+        }
+        for player in unplaced {
+            let group = preferences[player].iter().copied().find(|group| owners[*group].is_none()).unwrap();
+            owners[group] = Some(player);
+        }
+
+        for (group, owner) in owners.into_iter().enumerate() {
+            if let Some(player) = owner {
+                groups[group].push(players[player]);
+            }
+        }
+    }
+
+    let violations = groups.iter()
+        .map(|group| group.iter().enumerate().map(|(index, player)| conflicts(*player, &group[..index], apart)).sum::<i32>())
+        .sum();
+    (groups, violations)
+}
+
+/// Count of players of group given player should be kept apart from.
+fn conflicts(player: Uuid, group: &[Uuid], apart: &[HashSet<Uuid>]) -> i32 {
+    apart.iter()
+        .filter(|set| set.contains(&player))
+        .map(|set| group.iter().filter(|other| **other != player && set.contains(other)).count() as i32)
+        .sum()
+}
+
+/// Augmenting path search of Kuhn's matching, tries groups in order of player preference.
+fn place(
+    player: usize,
+    preferences: &[Vec<usize>],
+    allowed: &[Vec<bool>],
+    owners: &mut [Option<usize>],
+    visited: &mut [bool]
+) -> bool {
+    for group in preferences[player].iter().copied() {
+        if !allowed[player][group] || visited[group] {
+            continue;
+        }
+        visited[group] = true;
+        if owners[group].is_none_or(|owner| place(owner, preferences, allowed, owners, visited)) {
+            owners[group] = Some(player);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn players(count: usize) -> Vec<Uuid> {
+        (1..=count as u128).map(Uuid::from_u128).collect()
+    }
+
+    fn seeds(groups: &[Vec<Uuid>]) -> Vec<Vec<u128>> {
+        groups.iter().map(|group| group.iter().map(|player| player.as_u128()).collect()).collect()
+    }
+
+    #[test]
+    fn snake_changes_direction_every_row() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (groups, violations) = draw(GroupDrawMethod::Snake, players(8), 3, &[], &mut rng);
+        assert_eq!(seeds(&groups), vec![vec![1, 6, 7], vec![2, 5, 8], vec![3, 4]]);
+        assert_eq!(violations, 0);
+    }
+
+    #[test]
+    fn snake_moves_players_kept_apart_to_the_closest_group() {
+        let players = players(4);
+        let apart = [HashSet::from([players[0], players[3]])];
+        let mut rng = StdRng::seed_from_u64(0);
+        let (groups, violations) = draw(GroupDrawMethod::Snake, players, 2, &apart, &mut rng);
+        assert_eq!(seeds(&groups), vec![vec![1, 3], vec![2, 4]]);
+        assert_eq!(violations, 0);
+    }
+
+    #[test]
+    fn pairs_that_cant_be_separated_are_counted() {
+        let players = players(4);
+        let apart = [HashSet::from([players[0], players[1], players[2]])];
+        let mut rng = StdRng::seed_from_u64(0);
+        let (groups, violations) = draw(GroupDrawMethod::Snake, players, 2, &apart, &mut rng);
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<usize>>(), vec![2, 2]);
+        assert_eq!(violations, 1);
+    }
+
+    #[test]
+    fn random_draws_keep_sets_apart_and_repeat_with_the_same_seed() {
+        let players = players(12);
+        let apart = [
+            HashSet::from([players[0], players[1], players[2], players[3]]),
+            HashSet::from([players[4], players[5]]),
+            HashSet::from([players[0], players[6]])
+        ];
+        for seed in 0..50 {
+            for method in [GroupDrawMethod::Random, GroupDrawMethod::Pots] {
+                let (groups, violations) = draw(method, players.clone(), 4, &apart, &mut StdRng::seed_from_u64(seed));
+                assert_eq!(violations, 0);
+                assert!(groups.iter().all(|group| group.len() == 3));
+                for set in &apart {
+                    assert!(groups.iter().all(|group| group.iter().filter(|player| set.contains(player)).count() <= 1));
+                }
+                if method == GroupDrawMethod::Pots {
+                    // every group gets one player of each pot
+                    for pot in players.chunks(4) {
+                        assert!(groups.iter().all(|group| group.iter().filter(|player| pot.contains(player)).count() == 1));
+                    }
+                }
+                assert_eq!(draw(method, players.clone(), 4, &apart, &mut StdRng::seed_from_u64(seed)).0, groups);
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+mod draw;
+mod service;
+pub(crate) mod prelude;
+pub mod types;
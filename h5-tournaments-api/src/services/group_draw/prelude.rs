@@ -0,0 +1,2 @@
+pub use super::service::GroupDrawService;
+pub use super::types::{DrawnGroupModel, GroupDrawInput, GroupDrawMethod, GroupDrawModel};
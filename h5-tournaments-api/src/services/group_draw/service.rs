@@ -0,0 +1,102 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::{graphql::mutation::UpdateParticipant, prelude::TournamentService, services::{rating::seeding_ratings, tournament::{error::Error, models::{participant, tournament, user}}}};
+
+use super::{draw, types::{DrawnGroupModel, GroupDrawInput, GroupDrawMethod, GroupDrawModel}};
+
+#[derive(Clone)]
+pub struct GroupDrawService;
+
+impl GroupDrawService {
+    /// Splits all participants of tournament into groups and saves their group numbers.
+    /// Seeded methods order players by explicit seeds first and by rating in tournament's mod after them, unrated players go last in random order.
+    pub async fn draw_groups(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        input: GroupDrawInput
+    ) -> Result<GroupDrawModel, Error> {
+        let tournament = tournament::Entity::find_by_id(tournament_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let mut users = participant::Entity::find()
+            .find_also_related(user::Entity)
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(_, user)| user.map(|user| (user.id, user)))
+            .collect::<HashMap<Uuid, user::Model>>();
+
+        if input.groups < 1 || input.groups as usize > users.len() {
+            return Err(Error::Validation(format!("Can't draw {} participants into {} groups", users.len(), input.groups)));
+        }
+
+        let random_seed = input.random_seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(random_seed as u64);
+
+        let mut players = vec![];
+        if input.method != GroupDrawMethod::Random {
+            for user_id in input.seeds.unwrap_or_default() {
+                if !users.contains_key(&user_id) {
+                    return Err(Error::Validation(format!("Seeded user {} doesn't participate in tournament", user_id)));
+                }
+                if players.contains(&user_id) {
+                    return Err(Error::Validation(format!("User {} is seeded more than once", user_id)));
+                }
+                players.push(user_id);
+            }
+        }
+
+        // sorted before shuffling so the same random seed gives the same draw
+        let mut unseeded = users.keys().copied().filter(|user_id| !players.contains(user_id)).collect::<Vec<Uuid>>();
+        unseeded.sort();
+        unseeded.shuffle(&mut rng);
+        if input.method != GroupDrawMethod::Random {
+            let ratings = seeding_ratings(db, unseeded.iter().copied(), tournament.mod_type).await?;
+            unseeded.sort_by(|first, second| {
+                let first_rating = ratings.get(first).copied().unwrap_or(f64::MIN);
+                let second_rating = ratings.get(second).copied().unwrap_or(f64::MIN);
+                second_rating.total_cmp(&first_rating)
+            });
+        }
+        players.extend(unseeded);
+
+        let mut apart = vec![];
+        for set in input.keep_apart.unwrap_or_default() {
+            if let Some(user_id) = set.iter().find(|user_id| !users.contains_key(user_id)) {
+                return Err(Error::Validation(format!("User {} kept apart doesn't participate in tournament", user_id)));
+            }
+            apart.push(set.into_iter().collect::<HashSet<Uuid>>());
+        }
+
+        let (groups, violations) = draw::draw(input.method, players, input.groups as usize, &apart, &mut rng);
+
+        let updates = groups.iter()
+            .enumerate()
+            .flat_map(|(index, group)| group.iter().map(move |user_id| UpdateParticipant {
+                user_id: *user_id,
+                tournament_id,
+                challonge_id: None,
+                group_number: Some(index as i32 + 1)
+            }))
+            .collect::<Vec<UpdateParticipant>>();
+        TournamentService.participants_bulk_update(db, updates).await?;
+
+        Ok(GroupDrawModel {
+            groups: groups.into_iter()
+                .enumerate()
+                .map(|(index, group)| DrawnGroupModel {
+                    group: index as i32 + 1,
+                    users: group.into_iter().filter_map(|user_id| users.remove(&user_id)).collect()
+                })
+                .collect(),
+            violations,
+            random_seed
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use crate::services::tournament::models::user::UserModel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+pub enum GroupDrawMethod {
+    /// Players are shuffled and dealt into groups
+    Random,
+    /// Players ordered by seed fill groups row by row, direction changes every row
+    Snake,
+    /// Players ordered by seed are split into pots of group count size, every group gets a random player of each pot
+    Pots
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct DrawnGroupModel {
+    pub group: i32,
+    /// Players in order they were drawn
+    pub users: Vec<UserModel>
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct GroupDrawModel {
+    pub groups: Vec<DrawnGroupModel>,
+    /// Pairs of players that were asked to be kept apart but had to share a group
+    pub violations: i32,
+    /// Repeating the draw with this seed and the same participants gives the same groups
+    pub random_seed: i64
+}
+
+#[derive(Debug, Clone, async_graphql::InputObject)]
+pub struct GroupDrawInput {
+    /// Count of groups participants are split into, numbered from 1
+    pub groups: i32,
+    pub method: GroupDrawMethod,
+    /// Users ordered from the best seed for seeded methods, participants that aren't listed are seeded by rating
+    pub seeds: Option<Vec<Uuid>>,
+    /// Sets of users that should end up in different groups, e.g. members of the same clan
+    pub keep_apart: Option<Vec<Vec<Uuid>>>,
+    /// Seed of random generator to repeat previous draw, a new one is picked if not set
+    pub random_seed: Option<i64>
+}
@@ -0,0 +1,3 @@
+mod service;
+pub(crate) mod prelude;
+pub mod types;
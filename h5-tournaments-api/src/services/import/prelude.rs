@@ -0,0 +1,2 @@
+pub use super::service::ImportService;
+pub use super::types::{ImportConflictModel, ImportReportModel};
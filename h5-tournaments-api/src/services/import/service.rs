@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::services::{export::{prelude::EXPORT_FORMAT_VERSION, types::TournamentExportModel}, tournament::{error::Error, models::{game_builder, game_mod, heroes, match_structure, operator, organizer, participant, race, tournament::{self, TournamentStage}, tournament_stage_change, user}}};
+
+use super::types::{ImportConflictModel, ImportReportModel};
+
+#[derive(Clone)]
+pub struct ImportService;
+
+/// Rows import document is turned into, ids of document users are already replaced with local ones.
+struct ImportPlan {
+    tournament: tournament::ActiveModel,
+    stage_change: tournament_stage_change::ActiveModel,
+    users: Vec<user::ActiveModel>,
+    participants: Vec<participant::ActiveModel>,
+    matches: Vec<match_structure::ActiveModel>,
+    games: Vec<game_builder::ActiveModel>,
+    report: ImportReportModel
+}
+
+impl ImportService {
+    /// Recreates tournament from export document in a single transaction.
+    /// Nothing is written if document has conflicts with existing data or it's a dry run.
+    pub async fn import_tournament(
+        &self,
+        db: &DatabaseConnection,
+        document: TournamentExportModel,
+        dry_run: bool
+    ) -> Result<ImportReportModel, Error> {
+        // version 1 differs only by the way mod is stored, it's converted while document is read
+        if !(1..=EXPORT_FORMAT_VERSION).contains(&document.version) {
+            return Err(Error::Validation(format!("Unsupported import document version {}, expected 1 to {}", document.version, EXPORT_FORMAT_VERSION)));
+        }
+
+        let transaction = db.begin().await?;
+        let mut plan = plan_import(&transaction, document, dry_run).await?;
+        if dry_run || !plan.report.conflicts.is_empty() {
+            transaction.rollback().await?;
+            return Ok(plan.report);
+        }
+
+        tournament::Entity::insert(plan.tournament).exec(&transaction).await?;
+        tournament_stage_change::Entity::insert(plan.stage_change).exec(&transaction).await?;
+        for chunk in plan.users.chunks(1000) {
+            user::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+        for chunk in plan.participants.chunks(1000) {
+            participant::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+        for chunk in plan.matches.chunks(1000) {
+            match_structure::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+        for chunk in plan.games.chunks(1000) {
+            game_builder::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+        transaction.commit().await?;
+
+        plan.report.applied = true;
+        Ok(plan.report)
+    }
+}
+
+fn conflict(entity: &str, id: impl ToString, message: impl Into<String>) -> ImportConflictModel {
+    ImportConflictModel {
+        entity: entity.to_string(),
+        id: id.to_string(),
+        message: message.into()
+    }
+}
+
+async fn plan_import<C: ConnectionTrait>(
+    db: &C,
+    document: TournamentExportModel,
+    dry_run: bool
+) -> Result<ImportPlan, Error> {
+    let mut conflicts = vec![];
+    let source = &document.tournament;
+
+    if tournament::Entity::find_by_id(source.id).one(db).await?.is_some() {
+        conflicts.push(conflict("tournament", source.id, "Tournament with this id already exists"));
+    }
+    if organizer::Entity::find_by_id(source.organizer_id).one(db).await?.is_none() {
+        conflicts.push(conflict("organizer", source.organizer_id, "Organizer doesn't exist"));
+    }
+    if operator::Entity::find_by_id(source.operator_id).one(db).await?.is_none() {
+        conflicts.push(conflict("operator", source.operator_id, "Operator doesn't exist"));
+    }
+    if game_mod::Entity::find_by_id(source.mod_type).one(db).await?.is_none() {
+        conflicts.push(conflict("mod", source.mod_type, "Mod doesn't exist"));
+    }
+    // channels are unique among tournaments, so tournament can't be recreated while another one uses them
+    if let Some(channel_id) = source.channel_id
+        && let Some(existing) = tournament::Entity::find().filter(tournament::Column::ChannelId.eq(channel_id)).one(db).await? {
+        conflicts.push(conflict("tournament", source.id, format!("Reports channel {} is already used by tournament {}", channel_id, existing.id)));
+    }
+    if let Some(register_channel) = source.register_channel
+        && let Some(existing) = tournament::Entity::find().filter(tournament::Column::RegisterChannel.eq(register_channel)).one(db).await? {
+        conflicts.push(conflict("tournament", source.id, format!("Register channel {} is already used by tournament {}", register_channel, existing.id)));
+    }
+
+    // users of document are matched with existing ones by discord id, the rest are created keeping their ids when possible
+    let existing_users = user::Entity::find()
+        .filter(user::Column::DiscordId.is_in(document.users.iter().map(|user| user.discord_id).collect::<Vec<i64>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.discord_id, user.id))
+        .collect::<HashMap<i64, Uuid>>();
+    let taken_user_ids = user::Entity::find()
+        .filter(user::Column::Id.is_in(document.users.iter().map(|user| user.id).collect::<Vec<Uuid>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| user.id)
+        .collect::<HashSet<Uuid>>();
+
+    let mut local_users = HashMap::new();
+    let mut discord_ids = HashSet::new();
+    let mut users = vec![];
+    for source_user in &document.users {
+        if !discord_ids.insert(source_user.discord_id) || local_users.contains_key(&source_user.id) {
+            conflicts.push(conflict("user", source_user.id, "User is listed in document more than once"));
+            continue;
+        }
+        let local_id = match existing_users.get(&source_user.discord_id) {
+            Some(id) => *id,
+            None => {
+                let id = if taken_user_ids.contains(&source_user.id) { Uuid::new_v4() } else { source_user.id };
+                users.push(user::ActiveModel {
+                    id: Set(id),
+                    discord_id: Set(source_user.discord_id),
+                    discord_nick: Set(source_user.discord_nick.clone()),
+                    nickname: Set(source_user.nickname.clone()),
+                    registered_manually: Set(false)
+                });
+                id
+            }
+        };
+        local_users.insert(source_user.id, local_id);
+    }
+
+    let taken_participant_ids = participant::Entity::find()
+        .filter(participant::Column::Id.is_in(document.participants.iter().map(|participant| participant.id).collect::<Vec<Uuid>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|participant| participant.id)
+        .collect::<HashSet<Uuid>>();
+
+    let mut participant_users = HashSet::new();
+    let mut participants = vec![];
+    for source_participant in &document.participants {
+        if taken_participant_ids.contains(&source_participant.id) {
+            conflicts.push(conflict("participant", source_participant.id, "Participant with this id already exists"));
+        }
+        let Some(user_id) = local_users.get(&source_participant.user_id) else {
+            conflicts.push(conflict("participant", source_participant.id, format!("User {} isn't listed in document", source_participant.user_id)));
+            continue;
+        };
+        if !participant_users.insert(*user_id) {
+            conflicts.push(conflict("participant", source_participant.id, "User participates in tournament more than once"));
+        }
+        participants.push(participant::ActiveModel {
+            id: Set(source_participant.id),
+            tournament_id: Set(source.id),
+            user_id: Set(*user_id),
+            group_number: Set(source_participant.group_number),
+            challonge_id: Set(source_participant.challonge_id.clone())
+        });
+    }
+
+    let taken_match_ids = match_structure::Entity::find()
+        .filter(match_structure::Column::Id.is_in(document.matches.iter().map(|match_model| match_model.id).collect::<Vec<Uuid>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|match_model| match_model.id)
+        .collect::<HashSet<Uuid>>();
+
+    let mut matches = vec![];
+    for source_match in &document.matches {
+        if taken_match_ids.contains(&source_match.id) {
+            conflicts.push(conflict("match", source_match.id, "Match with this id already exists"));
+        }
+        let (Some(first_player), Some(second_player)) = (local_users.get(&source_match.first_player_id), local_users.get(&source_match.second_player_id)) else {
+            conflicts.push(conflict("match", source_match.id, "Some of match players aren't listed in document"));
+            continue;
+        };
+        matches.push(match_structure::ActiveModel {
+            id: Set(source_match.id),
+            tournament_id: Set(source.id),
+            // earlier exports wrote zero for matches that had no message
+            message_id: Set(source_match.message_id.filter(|message_id| *message_id != 0)),
+            first_player: Set(*first_player),
+            second_player: Set(*second_player),
+            challonge_id: Set(source_match.challonge_id.clone()),
+            report_link: Set(source_match.report_link.clone()),
+            round: Set(source_match.round),
+            deadline: Set(source_match.deadline),
+            stage: Set(source_match.stage),
+            created_at: source_match.created_at.map_or(NotSet, Set)
+        });
+    }
+
+    let races = race::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|race| (race.name, race.id))
+        .collect::<HashMap<String, i32>>();
+    let heroes = heroes::Entity::find()
+        .filter(heroes::Column::ModType.eq(source.mod_type))
+        .one(db)
+        .await?
+        .map(|heroes| heroes.heroes.entities.into_iter().map(|hero| (hero.name, hero.id)).collect::<HashMap<String, i32>>())
+        .unwrap_or_default();
+    let taken_game_ids = game_builder::Entity::find()
+        .filter(game_builder::Column::Id.is_in(document.games.iter().map(|game| game.id).collect::<Vec<Uuid>>()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|game| game.id)
+        .collect::<HashSet<Uuid>>();
+    let match_ids = document.matches.iter().map(|match_model| match_model.id).collect::<HashSet<Uuid>>();
+
+    let mut games = vec![];
+    for source_game in &document.games {
+        if taken_game_ids.contains(&source_game.id) {
+            conflicts.push(conflict("game", source_game.id, "Game with this id already exists"));
+        }
+        if !match_ids.contains(&source_game.match_id) {
+            conflicts.push(conflict("game", source_game.id, format!("Match {} isn't listed in document", source_game.match_id)));
+        }
+        let mut resolve = |names: &HashMap<String, i32>, name: &Option<String>, kind: &str| match name {
+            Some(name) => match names.get(name) {
+                Some(id) => Some(*id),
+                None => {
+                    conflicts.push(conflict("game", source_game.id, format!("Unknown {} {}", kind, name)));
+                    None
+                }
+            },
+            None => None
+        };
+        let first_player_race = resolve(&races, &source_game.first_player_race, "race");
+        let first_player_hero = resolve(&heroes, &source_game.first_player_hero, "hero");
+        let second_player_race = resolve(&races, &source_game.second_player_race, "race");
+        let second_player_hero = resolve(&heroes, &source_game.second_player_hero, "hero");
+        games.push(game_builder::ActiveModel {
+            id: Set(source_game.id),
+            match_id: Set(source_game.match_id),
+            first_player_race: Set(first_player_race),
+            first_player_hero: Set(first_player_hero),
+            second_player_race: Set(second_player_race),
+            second_player_hero: Set(second_player_hero),
+            bargains_color: Set(source_game.bargains_color),
+            bargains_amount: Set(source_game.bargains_amount),
+            result: Set(source_game.result),
+            outcome: Set(source_game.outcome),
+            created_at: Set(source_game.created_at)
+        });
+    }
+
+    let report = ImportReportModel {
+        dry_run,
+        applied: false,
+        tournament_id: source.id,
+        matched_users: (local_users.len() - users.len()) as i32,
+        created_users: users.len() as i32,
+        participants: participants.len() as i32,
+        matches: matches.len() as i32,
+        games: games.len() as i32,
+        conflicts
+    };
+
+    let tournament = tournament::ActiveModel {
+        id: Set(source.id),
+        operator_id: Set(source.operator_id),
+        channel_id: Set(source.channel_id),
+        name: Set(source.name.clone()),
+        stage: Set(source.stage),
+        register_channel: Set(source.register_channel),
+        with_bargains: Set(source.with_bargains),
+        with_bargains_color: Set(source.with_bargains_color),
+        with_foreign_heroes: Set(source.with_foreign_heroes),
+        role_id: Set(source.role_id),
+        challonge_id: Set(source.challonge_id.clone()),
+        organizer: Set(source.organizer_id),
+        game_type: Set(source.game_type),
+        mod_type: Set(source.mod_type)
+    };
+    // history of imported tournament starts at the stage it was exported at
+    let stage_change = tournament_stage_change::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        tournament_id: Set(source.id),
+        previous_stage: Set(None),
+        stage: Set(source.stage.unwrap_or(TournamentStage::Unknown)),
+        changed_at: NotSet
+    };
+
+    Ok(ImportPlan { tournament, stage_change, users, participants, matches, games, report })
+}
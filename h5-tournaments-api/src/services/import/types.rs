@@ -0,0 +1,28 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Problem that prevents import document from being applied.
+#[derive(Debug, Clone, Serialize, async_graphql::SimpleObject)]
+pub struct ImportConflictModel {
+    /// Kind of conflicting entity: tournament, organizer, operator, user, participant, match or game
+    pub entity: String,
+    /// Id of conflicting entity in import document
+    pub id: String,
+    pub message: String
+}
+
+#[derive(Debug, Clone, Serialize, async_graphql::SimpleObject)]
+pub struct ImportReportModel {
+    pub dry_run: bool,
+    /// Document is written only when it's not a dry run and no conflicts were found
+    pub applied: bool,
+    pub tournament_id: Uuid,
+    /// Users of document that already exist and were matched by discord id
+    pub matched_users: i32,
+    /// Users of document that don't exist yet and are created by import
+    pub created_users: i32,
+    pub participants: i32,
+    pub matches: i32,
+    pub games: i32,
+    pub conflicts: Vec<ImportConflictModel>
+}
@@ -0,0 +1,4 @@
+mod service;
+pub(crate) mod prelude;
+pub mod models;
+pub mod types;
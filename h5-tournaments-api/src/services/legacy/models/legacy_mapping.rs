@@ -0,0 +1,49 @@
+use sea_orm::prelude::*;
+
+pub type LegacyMappingModel = Model;
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, Hash, async_graphql::Enum)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum LegacyMappingKind {
+    /// Legacy tournament id mapped to tournament
+    Tournament = 0,
+    /// Legacy match id mapped to match
+    Match = 1,
+    /// Normalized player name mapped to user
+    Player = 2
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "legacy_mappings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub kind: LegacyMappingKind,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub legacy_key: String,
+    pub new_id: Uuid,
+    pub created_at: DateTimeWithTimeZone
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl LegacyMappingModel {
+    async fn kind(&self) -> LegacyMappingKind {
+        self.kind
+    }
+
+    async fn legacy_key(&self) -> String {
+        self.legacy_key.clone()
+    }
+
+    async fn new_id(&self) -> Uuid {
+        self.new_id
+    }
+
+    async fn created_at(&self) -> DateTimeWithTimeZone {
+        self.created_at
+    }
+}
@@ -0,0 +1,3 @@
+pub use super::service::LegacyMigrationService;
+pub use super::models::legacy_mapping::{LegacyMappingKind, LegacyMappingModel};
+pub use super::types::LegacyMigrationReportModel;
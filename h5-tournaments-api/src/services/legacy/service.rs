@@ -0,0 +1,405 @@
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{prelude::{DateTimeUtc, DateTimeWithTimeZone, Expr}, sea_query::extension::postgres::PgExpr, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, NotSet, QueryFilter, QuerySelect, Set, TransactionTrait};
+use uuid::Uuid;
+
+use crate::{prelude::ModId, services::tournament::{error::Error, filters::escape_like, models::{game_builder::{self, GameOutcome, GameResult}, game_mod, heroes, match_structure, operator, participant, race, tournament::{self, GameType}, user}, types::{self, Game, Hero, Match, Tournament}}};
+
+use super::{models::legacy_mapping::{self, LegacyMappingKind}, types::LegacyMigrationReportModel};
+
+/// First millisecond of 2015 discord snowflakes are counted from
+const DISCORD_EPOCH: i64 = 1_420_070_400_000;
+
+#[derive(Clone)]
+pub struct LegacyMigrationService;
+
+impl LegacyMigrationService {
+    /// Converts legacy tournament with its matches and games into current tables.
+    /// Operator is found by discord server of legacy tournament, so it must be created beforehand.
+    pub async fn migrate_tournament(
+        &self,
+        db: &DatabaseConnection,
+        legacy_tournament_id: Uuid,
+        organizer_id: Uuid
+    ) -> Result<LegacyMigrationReportModel, Error> {
+        let pool = db.get_postgres_connection_pool();
+        let legacy_tournament: Tournament = sqlx::query_as("SELECT * FROM tournaments WHERE id=$1;")
+            .bind(legacy_tournament_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No legacy tournament found with id {}", legacy_tournament_id)))?;
+        let legacy_matches: Vec<Match> = sqlx::query_as("SELECT * FROM matches_old WHERE tournament_id=$1 ORDER BY message_id, id;")
+            .bind(legacy_tournament_id)
+            .fetch_all(pool)
+            .await?;
+        let legacy_games: Vec<Game> = sqlx::query_as("SELECT * FROM games_old WHERE match_id = ANY($1) ORDER BY id;")
+            .bind(legacy_matches.iter().map(|legacy_match| legacy_match.id).collect::<Vec<i32>>())
+            .fetch_all(pool)
+            .await?;
+        let legacy_heroes: Vec<Hero> = sqlx::query_as("SELECT * FROM heroes;")
+            .fetch_all(pool)
+            .await?;
+
+        let transaction = db.begin().await?;
+        // concurrent migrations would create the same users and matches twice
+        transaction.execute_unprepared("LOCK TABLE legacy_mappings IN EXCLUSIVE MODE").await?;
+
+        let report = migrate(&transaction, legacy_tournament, legacy_matches, legacy_games, legacy_heroes, organizer_id).await?;
+        transaction.commit().await?;
+        Ok(report)
+    }
+
+    /// Migrates every legacy tournament, stops at the first one that fails.
+    /// Tournaments migrated before the failure stay migrated, so it's safe to run it again after fixing the cause.
+    pub async fn migrate_all(
+        &self,
+        db: &DatabaseConnection,
+        organizer_id: Uuid
+    ) -> Result<Vec<LegacyMigrationReportModel>, Error> {
+        let legacy_tournaments: Vec<Tournament> = sqlx::query_as("SELECT * FROM tournaments ORDER BY first_message_id;")
+            .fetch_all(db.get_postgres_connection_pool())
+            .await?;
+
+        let mut reports = vec![];
+        for legacy_tournament in legacy_tournaments {
+            reports.push(self.migrate_tournament(db, legacy_tournament.id, organizer_id).await?);
+        }
+        Ok(reports)
+    }
+
+    pub async fn get_mappings(
+        &self,
+        db: &DatabaseConnection,
+        kind: Option<LegacyMappingKind>
+    ) -> Result<Vec<legacy_mapping::Model>, Error> {
+        let mut query = legacy_mapping::Entity::find();
+        if let Some(kind) = kind {
+            query = query.filter(legacy_mapping::Column::Kind.eq(kind));
+        }
+        Ok(query.all(db).await?)
+    }
+}
+
+/// Legacy names are typed by hand, so they are compared ignoring case and surrounding spaces.
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Legacy matches were created by discord messages, their ids keep milliseconds since discord epoch in the upper bits.
+fn message_time(message_id: i64) -> Option<DateTimeWithTimeZone> {
+    DateTimeUtc::from_timestamp_millis((message_id >> 22) + DISCORD_EPOCH).map(DateTimeWithTimeZone::from)
+}
+
+async fn find_mapping(
+    transaction: &DatabaseTransaction,
+    kind: LegacyMappingKind,
+    legacy_key: &str
+) -> Result<Option<Uuid>, Error> {
+    Ok(legacy_mapping::Entity::find_by_id((kind, legacy_key.to_string()))
+        .one(transaction)
+        .await?
+        .map(|mapping| mapping.new_id))
+}
+
+async fn insert_mapping(
+    transaction: &DatabaseTransaction,
+    kind: LegacyMappingKind,
+    legacy_key: String,
+    new_id: Uuid
+) -> Result<(), Error> {
+    let mapping = legacy_mapping::ActiveModel {
+        kind: Set(kind),
+        legacy_key: Set(legacy_key),
+        new_id: Set(new_id),
+        created_at: NotSet
+    };
+    legacy_mapping::Entity::insert(mapping).exec(transaction).await?;
+    Ok(())
+}
+
+async fn migrate(
+    transaction: &DatabaseTransaction,
+    legacy_tournament: Tournament,
+    legacy_matches: Vec<Match>,
+    legacy_games: Vec<Game>,
+    legacy_heroes: Vec<Hero>,
+    organizer_id: Uuid
+) -> Result<LegacyMigrationReportModel, Error> {
+    let mod_type = ModId(legacy_tournament.mod_type as i32);
+    if game_mod::Entity::find_by_id(mod_type).one(transaction).await?.is_none() {
+        return Err(Error::Validation(format!("Legacy tournament {} has unknown mod type {}", legacy_tournament.id, legacy_tournament.mod_type)));
+    }
+
+    let existing_tournament = find_mapping(transaction, LegacyMappingKind::Tournament, &legacy_tournament.id.to_string()).await?;
+    let tournament_id = match existing_tournament {
+        Some(tournament_id) => tournament_id,
+        None => {
+            let operator = operator::Entity::find()
+                .filter(operator::Column::ServerId.eq(legacy_tournament.server_id))
+                .one(transaction)
+                .await?
+                .ok_or_else(|| Error::Validation(format!("No operator found for discord server {} of legacy tournament", legacy_tournament.server_id)))?;
+
+            let with_bargains = legacy_games.iter().any(|game| game.bargains_amount != 0);
+            let tournament_id = Uuid::new_v4();
+            let tournament_to_insert = tournament_model(&legacy_tournament, tournament_id, operator.id, organizer_id, with_bargains);
+            tournament::Entity::insert(tournament_to_insert).exec(transaction).await?;
+            insert_mapping(transaction, LegacyMappingKind::Tournament, legacy_tournament.id.to_string(), tournament_id).await?;
+            tournament_id
+        }
+    };
+
+    let legacy_matches_count = legacy_matches.len();
+    let mut pending_matches = vec![];
+    for legacy_match in legacy_matches {
+        if find_mapping(transaction, LegacyMappingKind::Match, &legacy_match.id.to_string()).await?.is_none() {
+            pending_matches.push(legacy_match);
+        }
+    }
+
+    let mut report = LegacyMigrationReportModel {
+        legacy_tournament_id: legacy_tournament.id,
+        tournament_id,
+        tournament_created: existing_tournament.is_none(),
+        matches_migrated: pending_matches.len() as i32,
+        matches_skipped: (legacy_matches_count - pending_matches.len()) as i32,
+        games_migrated: 0,
+        users_matched: 0,
+        users_created: 0
+    };
+
+    let players = resolve_players(transaction, &pending_matches, &mut report).await?;
+    ensure_participants(transaction, tournament_id, players.values().copied().collect()).await?;
+
+    let races = race::Entity::find()
+        .all(transaction)
+        .await?
+        .into_iter()
+        .map(|race| race.id)
+        .collect::<HashSet<i32>>();
+    let legacy_hero_names = legacy_heroes.into_iter()
+        .map(|hero| (hero.id, normalize_name(&hero.actual_name)))
+        .collect::<HashMap<i32, String>>();
+    let heroes = heroes::Entity::find()
+        .filter(heroes::Column::ModType.eq(mod_type))
+        .one(transaction)
+        .await?
+        .map(|heroes| heroes.heroes.entities.into_iter().map(|hero| (normalize_name(&hero.name), hero.id)).collect::<HashMap<String, i32>>())
+        .unwrap_or_default();
+    // legacy ids are 0 for races and heroes that weren't detected
+    let race = |id: i64| i32::try_from(id).ok().filter(|id| races.contains(id));
+    let hero = |id: i64| i32::try_from(id).ok()
+        .and_then(|id| legacy_hero_names.get(&id))
+        .and_then(|name| heroes.get(name).copied());
+
+    let mut games_by_match = HashMap::<i32, Vec<&Game>>::new();
+    for game in &legacy_games {
+        games_by_match.entry(game.match_id).or_default().push(game);
+    }
+
+    for legacy_match in pending_matches {
+        let match_id = Uuid::new_v4();
+        let match_to_insert = match_structure::ActiveModel {
+            id: Set(match_id),
+            tournament_id: Set(tournament_id),
+            message_id: Set(Some(legacy_match.message_id)),
+            first_player: Set(players[&normalize_name(&legacy_match.first_player)]),
+            second_player: Set(players[&normalize_name(&legacy_match.second_player)]),
+            challonge_id: Set(None),
+            report_link: Set(None),
+            round: Set(None),
+            deadline: Set(None),
+            stage: Set(None),
+            created_at: message_time(legacy_match.message_id).map_or(NotSet, Set)
+        };
+        match_structure::Entity::insert(match_to_insert).exec(transaction).await?;
+        insert_mapping(transaction, LegacyMappingKind::Match, legacy_match.id.to_string(), match_id).await?;
+
+        let games = games_by_match.remove(&legacy_match.id).unwrap_or_default();
+        report.games_migrated += games.len() as i32;
+        let games_to_insert = games.into_iter()
+            .map(|game| game_builder::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                match_id: Set(match_id),
+                first_player_race: Set(race(game.first_player_race)),
+                first_player_hero: Set(hero(game.first_player_hero)),
+                second_player_race: Set(race(game.second_player_race)),
+                second_player_hero: Set(hero(game.second_player_hero)),
+                bargains_color: Set(None),
+                bargains_amount: Set(Some(game.bargains_amount)),
+                result: Set(match game.result {
+                    types::GameResult::NotDetected => GameResult::NotSelected,
+                    types::GameResult::FirstPlayerWon => GameResult::FirstPlayerWon,
+                    types::GameResult::SecondPlayerWon => GameResult::SecondPlayerWon
+                }),
+                // legacy reports didn't track how games ended
+                outcome: Set(GameOutcome::FinalBattleVictory),
+                created_at: Set(None)
+            })
+            .collect::<Vec<game_builder::ActiveModel>>();
+        if !games_to_insert.is_empty() {
+            game_builder::Entity::insert_many(games_to_insert).exec(transaction).await?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Builds tournament for legacy one. Legacy tournaments knew nothing about registration and roles, so these are left empty.
+/// Several legacy tournaments could be held in the same channel while current ones keep channels unique,
+/// so channels are left empty too, legacy channel stays known through the mapping.
+fn tournament_model(
+    legacy_tournament: &Tournament,
+    tournament_id: Uuid,
+    operator_id: Uuid,
+    organizer_id: Uuid,
+    with_bargains: bool
+) -> tournament::ActiveModel {
+    tournament::ActiveModel {
+        id: Set(tournament_id),
+        operator_id: Set(operator_id),
+        channel_id: Set(None),
+        name: Set(legacy_tournament.name.clone()),
+        stage: Set(None),
+        register_channel: Set(None),
+        with_bargains: Set(with_bargains),
+        with_bargains_color: Set(false),
+        with_foreign_heroes: Set(false),
+        role_id: Set(0),
+        challonge_id: Set(None),
+        organizer: Set(organizer_id),
+        game_type: Set(GameType::Rmg),
+        mod_type: Set(ModId(legacy_tournament.mod_type as i32))
+    }
+}
+
+/// Finds users for player names of matches, creating unregistered users for unknown players.
+/// Returns users by normalized player names.
+async fn resolve_players(
+    transaction: &DatabaseTransaction,
+    matches: &[Match],
+    report: &mut LegacyMigrationReportModel
+) -> Result<HashMap<String, Uuid>, Error> {
+    let mut players = HashMap::new();
+    // player is named as in the first match they appear in
+    let mut names = vec![];
+    let mut seen_names = HashSet::new();
+    for name in matches.iter().flat_map(|legacy_match| [&legacy_match.first_player, &legacy_match.second_player]) {
+        if seen_names.insert(normalize_name(name)) {
+            names.push((normalize_name(name), name.trim().to_string()));
+        }
+    }
+
+    // discord ids are positive, so unregistered users get negative ones that can't collide with real accounts
+    let mut placeholder_discord_id = user::Entity::find()
+        .select_only()
+        .column_as(Expr::col(user::Column::DiscordId).min(), "min")
+        .into_tuple::<Option<i64>>()
+        .one(transaction)
+        .await?
+        .flatten()
+        .unwrap_or(0)
+        .min(0);
+
+    for (normalized_name, name) in names {
+        if let Some(user_id) = find_mapping(transaction, LegacyMappingKind::Player, &normalized_name).await? {
+            report.users_matched += 1;
+            players.insert(normalized_name, user_id);
+            continue;
+        }
+
+        let matching_users = user::Entity::find()
+            .filter(Expr::col(user::Column::Nickname).ilike(escape_like(&name)))
+            .all(transaction)
+            .await?;
+        let user_id = match matching_users.as_slice() {
+            [user] => {
+                report.users_matched += 1;
+                user.id
+            },
+            // nothing or several users with this nickname, a new user is safer than a wrong one
+            _ => {
+                placeholder_discord_id -= 1;
+                let user_id = Uuid::new_v4();
+                let user_to_insert = user::ActiveModel {
+                    id: Set(user_id),
+                    discord_id: Set(placeholder_discord_id),
+                    discord_nick: Set(String::new()),
+                    nickname: Set(name),
+                    registered_manually: Set(false)
+                };
+                user::Entity::insert(user_to_insert).exec(transaction).await?;
+                report.users_created += 1;
+                user_id
+            }
+        };
+        insert_mapping(transaction, LegacyMappingKind::Player, normalized_name.clone(), user_id).await?;
+        players.insert(normalized_name, user_id);
+    }
+
+    Ok(players)
+}
+
+async fn ensure_participants(
+    transaction: &DatabaseTransaction,
+    tournament_id: Uuid,
+    users: HashSet<Uuid>
+) -> Result<(), Error> {
+    let existing_participants = participant::Entity::find()
+        .filter(participant::Column::TournamentId.eq(tournament_id))
+        .all(transaction)
+        .await?
+        .into_iter()
+        .map(|participant| participant.user_id)
+        .collect::<HashSet<Uuid>>();
+
+    let participants_to_insert = users.difference(&existing_participants)
+        .map(|user_id| participant::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tournament_id: Set(tournament_id),
+            user_id: Set(*user_id),
+            group_number: Set(0),
+            challonge_id: Set(None)
+        })
+        .collect::<Vec<participant::ActiveModel>>();
+    if !participants_to_insert.is_empty() {
+        participant::Entity::insert_many(participants_to_insert).exec(transaction).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::Set;
+    use uuid::Uuid;
+
+    use crate::services::tournament::types::Tournament;
+
+    use super::tournament_model;
+
+    fn legacy_tournament(name: &str, channel_id: i64) -> Tournament {
+        Tournament {
+            id: Uuid::new_v4(),
+            mod_type: 0,
+            server_id: 1,
+            channel_id,
+            first_message_id: 0,
+            last_message_id: 0,
+            name: name.to_string()
+        }
+    }
+
+    #[test]
+    fn tournaments_of_the_same_channel_leave_channels_empty() {
+        let operator_id = Uuid::new_v4();
+        let organizer_id = Uuid::new_v4();
+        let first = tournament_model(&legacy_tournament("First", 42), Uuid::new_v4(), operator_id, organizer_id, false);
+        let second = tournament_model(&legacy_tournament("Second", 42), Uuid::new_v4(), operator_id, organizer_id, true);
+
+        for model in [&first, &second] {
+            assert_eq!(model.channel_id, Set(None));
+            assert_eq!(model.register_channel, Set(None));
+        }
+        assert_eq!(second.with_bargains, Set(true));
+    }
+}
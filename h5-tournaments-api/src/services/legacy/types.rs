@@ -0,0 +1,18 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Result of migrating a single legacy tournament. Migration can be repeated, already migrated matches are skipped.
+#[derive(Debug, Clone, Serialize, async_graphql::SimpleObject)]
+pub struct LegacyMigrationReportModel {
+    pub legacy_tournament_id: Uuid,
+    pub tournament_id: Uuid,
+    /// False if tournament was migrated before and only new matches were added to it
+    pub tournament_created: bool,
+    pub matches_migrated: i32,
+    pub matches_skipped: i32,
+    pub games_migrated: i32,
+    /// Players resolved to existing users, by earlier mapping or by nickname
+    pub users_matched: i32,
+    /// Players no user was found for, they are created unregistered with placeholder discord id
+    pub users_created: i32
+}
@@ -1 +1,13 @@
-pub(crate) mod tournament;
\ No newline at end of file
+pub(crate) mod tournament;
+pub(crate) mod statistics;
+pub(crate) mod rating;
+pub(crate) mod pagination;
+pub(crate) mod auth;
+pub(crate) mod export;
+pub(crate) mod import;
+pub(crate) mod legacy;
+pub(crate) mod catalogue;
+pub(crate) mod bracket;
+pub(crate) mod standings;
+pub(crate) mod group_draw;
+pub(crate) mod swiss;
\ No newline at end of file
@@ -0,0 +1,224 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sea_orm::{prelude::{DateTimeWithTimeZone, Uuid}, sea_query::{IntoCondition, NullOrdering}, ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::services::tournament::{error::Error, filters::SortDirection};
+
+/// Size of pages requested without `first` or `last`, also the largest allowed page.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Value of order column kept in cursor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KeyValue {
+    Null,
+    Int(i32),
+    BigInt(i64),
+    Text(String),
+    Uuid(Uuid),
+    Time(DateTimeWithTimeZone)
+}
+
+impl From<i32> for KeyValue {
+    fn from(value: i32) -> Self {
+        KeyValue::Int(value)
+    }
+}
+
+impl From<i64> for KeyValue {
+    fn from(value: i64) -> Self {
+        KeyValue::BigInt(value)
+    }
+}
+
+impl From<String> for KeyValue {
+    fn from(value: String) -> Self {
+        KeyValue::Text(value)
+    }
+}
+
+impl From<Uuid> for KeyValue {
+    fn from(value: Uuid) -> Self {
+        KeyValue::Uuid(value)
+    }
+}
+
+impl From<DateTimeWithTimeZone> for KeyValue {
+    fn from(value: DateTimeWithTimeZone) -> Self {
+        KeyValue::Time(value)
+    }
+}
+
+impl<T: Into<KeyValue>> From<Option<T>> for KeyValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(KeyValue::Null, Into::into)
+    }
+}
+
+impl From<KeyValue> for Value {
+    fn from(value: KeyValue) -> Self {
+        match value {
+            KeyValue::Null => Value::Int(None),
+            KeyValue::Int(value) => value.into(),
+            KeyValue::BigInt(value) => value.into(),
+            KeyValue::Text(value) => value.into(),
+            KeyValue::Uuid(value) => value.into(),
+            KeyValue::Time(value) => value.into()
+        }
+    }
+}
+
+/// Position of item in ordered list, values of its order columns with id of item as the last one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor(pub Vec<KeyValue>);
+
+impl Cursor {
+    /// Opaque representation given to clients.
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(&self.0).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, Error> {
+        URL_SAFE_NO_PAD.decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .map(Cursor)
+            .ok_or_else(|| Error::Validation(format!("Invalid cursor {}", cursor)))
+    }
+}
+
+/// Relay connection arguments with decoded cursors.
+#[derive(Debug, Default, Clone)]
+pub struct PageArguments {
+    pub after: Option<Cursor>,
+    pub before: Option<Cursor>,
+    pub first: Option<usize>,
+    pub last: Option<usize>,
+    /// Whether count of all matching items is needed
+    pub with_total: bool
+}
+
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<(Cursor, T)>,
+    pub has_previous: bool,
+    pub has_next: bool,
+    /// Zero unless requested
+    pub total: usize
+}
+
+/// Ordered columns of paged list, the last one must be unique. Nulls go last in both directions.
+pub(crate) struct Keyset<C, K> {
+    pub columns: Vec<C>,
+    pub direction: SortDirection,
+    /// Values of columns of item
+    pub key: K
+}
+
+/// Loads requested slice of query results, slices start right after or before cursors of items they are requested relative to,
+/// so changes of the list between requests don't shift pages.
+pub(crate) async fn fetch_page<E, K>(
+    db: &DatabaseConnection,
+    select: Select<E>,
+    keyset: Keyset<E::Column, K>,
+    arguments: PageArguments
+) -> Result<Page<E::Model>, Error>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+    K: Fn(&E::Model) -> Cursor
+{
+    for cursor in arguments.after.iter().chain(arguments.before.iter()) {
+        if cursor.0.len() != keyset.columns.len() {
+            return Err(Error::Validation("Cursor doesn't belong to this list".to_string()));
+        }
+    }
+
+    let total = if arguments.with_total {
+        select.clone().count(db).await? as usize
+    } else {
+        0
+    };
+
+    // only `last` pages from the end of the slice, otherwise it's paged from the start
+    let forward = arguments.first.is_some() || arguments.last.is_none();
+    let limit = if forward {
+        arguments.first.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+    } else {
+        arguments.last.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE)
+    };
+
+    let mut query = select;
+    if let Some(after) = &arguments.after {
+        query = query.filter(beyond(&keyset.columns, after, keyset.direction, true));
+    }
+    if let Some(before) = &arguments.before {
+        query = query.filter(beyond(&keyset.columns, before, keyset.direction, false));
+    }
+    let (order, nulls) = match (keyset.direction, forward) {
+        (SortDirection::Asc, true) => (Order::Asc, NullOrdering::Last),
+        (SortDirection::Desc, true) => (Order::Desc, NullOrdering::Last),
+        (SortDirection::Asc, false) => (Order::Desc, NullOrdering::First),
+        (SortDirection::Desc, false) => (Order::Asc, NullOrdering::First)
+    };
+    for column in &keyset.columns {
+        query = query.order_by_with_nulls(*column, order.clone(), nulls);
+    }
+
+    let mut items = query.limit(limit as u64 + 1).all(db).await?;
+    let more = items.len() > limit;
+    items.truncate(limit);
+    if !forward {
+        items.reverse();
+    }
+    let (mut has_previous, has_next) = if forward {
+        (arguments.after.is_some(), more)
+    } else {
+        (more, arguments.before.is_some())
+    };
+    // `first` and `last` together take the end of the first page
+    if forward && let Some(last) = arguments.last && items.len() > last {
+        items.drain(..items.len() - last);
+        has_previous = true;
+    }
+
+    Ok(Page {
+        items: items.into_iter().map(|item| ((keyset.key)(&item), item)).collect(),
+        has_previous,
+        has_next,
+        total
+    })
+}
+
+/// Items placed after cursor in given order, or before it if `forward` isn't set.
+fn beyond<C: ColumnTrait>(columns: &[C], cursor: &Cursor, direction: SortDirection, forward: bool) -> Condition {
+    let greater = (direction == SortDirection::Asc) == forward;
+    let mut condition = Condition::any();
+    for (index, (column, value)) in columns.iter().zip(&cursor.0).enumerate() {
+        let mut step = Condition::all();
+        for (column, value) in columns[..index].iter().zip(&cursor.0) {
+            step = step.add(match value {
+                KeyValue::Null => column.is_null(),
+                value => column.eq(Value::from(value.clone()))
+            });
+        }
+        let next = match value {
+            // nothing goes after null
+            KeyValue::Null if forward => continue,
+            KeyValue::Null => column.is_not_null().into_condition(),
+            value => {
+                let compared = if greater {
+                    column.gt(Value::from(value.clone()))
+                } else {
+                    column.lt(Value::from(value.clone()))
+                };
+                if forward {
+                    Condition::any().add(compared).add(column.is_null())
+                } else {
+                    compared.into_condition()
+                }
+            }
+        };
+        condition = condition.add(step.add(next));
+    }
+    condition
+}
@@ -0,0 +1,104 @@
+use std::f64::consts::{LN_10, PI};
+
+pub(crate) const INITIAL_RATING: f64 = 1500.0;
+pub(crate) const INITIAL_DEVIATION: f64 = 350.0;
+// Keeps ratings of players with many games from freezing
+const MIN_DEVIATION: f64 = 50.0;
+const Q: f64 = LN_10 / 400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Glicko {
+    pub rating: f64,
+    pub deviation: f64
+}
+
+impl Default for Glicko {
+    fn default() -> Self {
+        Glicko {
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION
+        }
+    }
+}
+
+fn g(deviation: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * Q * Q * deviation * deviation / (PI * PI)).sqrt()
+}
+
+impl Glicko {
+    /// Glicko-1 update of a single match treated as a rating period, every game of match is a separate result against the same opponent.
+    pub fn updated(&self, opponent: &Glicko, wins: i32, losses: i32) -> Glicko {
+        let results = std::iter::repeat_n(1.0, wins as usize)
+            .chain(std::iter::repeat_n(0.0, losses as usize))
+            .map(|score| (*opponent, score));
+        self.updated_with(results)
+    }
+
+    /// Glicko-1 update of a rating period with scores against opponents, 1 for a win and 0 for a loss.
+    fn updated_with(&self, results: impl IntoIterator<Item = (Glicko, f64)>) -> Glicko {
+        let mut inverse_d_squared = 0.0;
+        let mut score_difference = 0.0;
+        let mut games = 0;
+        for (opponent, score) in results {
+            let g = g(opponent.deviation);
+            let expected = 1.0 / (1.0 + 10f64.powf(-g * (self.rating - opponent.rating) / 400.0));
+            inverse_d_squared += Q * Q * g * g * expected * (1.0 - expected);
+            score_difference += g * (score - expected);
+            games += 1;
+        }
+        if games == 0 {
+            return *self;
+        }
+        let denominator = 1.0 / (self.deviation * self.deviation) + inverse_d_squared;
+        Glicko {
+            rating: self.rating + Q / denominator * score_difference,
+            deviation: (1.0 / denominator).sqrt().max(MIN_DEVIATION)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glicko;
+
+    fn glicko(rating: f64, deviation: f64) -> Glicko {
+        Glicko { rating, deviation }
+    }
+
+    #[test]
+    fn rating_period_matches_glickman_example() {
+        let player = glicko(1500.0, 200.0);
+        let updated = player.updated_with([
+            (glicko(1400.0, 30.0), 1.0),
+            (glicko(1550.0, 100.0), 0.0),
+            (glicko(1700.0, 300.0), 0.0)
+        ]);
+        assert!((updated.rating - 1464.1).abs() < 0.1);
+        assert!((updated.deviation - 151.4).abs() < 0.1);
+    }
+
+    #[test]
+    fn games_of_match_are_results_against_the_same_opponent() {
+        let player = glicko(1500.0, 200.0);
+        let opponent = glicko(1400.0, 30.0);
+        let expected = player.updated_with([(opponent, 1.0), (opponent, 1.0), (opponent, 0.0)]);
+        assert_eq!(player.updated(&opponent, 2, 1), expected);
+        assert!(expected.rating > player.rating);
+        assert!(expected.deviation < player.deviation);
+    }
+
+    #[test]
+    fn match_without_games_changes_nothing() {
+        let player = glicko(1500.0, 200.0);
+        assert_eq!(player.updated(&glicko(1700.0, 50.0), 0, 0), player);
+    }
+
+    #[test]
+    fn deviation_doesnt_fall_below_minimum() {
+        let mut player = Glicko::default();
+        for _ in 0..100 {
+            player = player.updated(&Glicko::default(), 3, 2);
+        }
+        assert_eq!(player.deviation, super::MIN_DEVIATION);
+    }
+}
@@ -0,0 +1,6 @@
+mod glicko;
+mod service;
+pub(crate) mod prelude;
+pub mod models;
+
+pub(crate) use service::seeding_ratings;
@@ -0,0 +1,2 @@
+pub(crate) mod rating;
+pub(crate) mod rating_history;
@@ -0,0 +1,76 @@
+use sea_orm::prelude::*;
+
+use crate::prelude::ModId;
+
+pub type RatingModel = Model;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "ratings")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    // None for overall rating across all mods
+    pub mod_type: Option<ModId>,
+    pub rating: f64,
+    pub deviation: f64,
+    pub matches: i32,
+    pub games: i32,
+    pub wins: i32
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    User
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::User => Entity::belongs_to(crate::services::tournament::models::user::Entity)
+                .from(Column::UserId)
+                .to(crate::services::tournament::models::user::Column::Id)
+                .into()
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl RatingModel {
+    async fn user(&self) -> Uuid {
+        self.user_id
+    }
+
+    /// Null for overall rating across all mods
+    async fn mod_type(&self) -> Option<ModId> {
+        self.mod_type
+    }
+
+    async fn rating(&self) -> f64 {
+        self.rating
+    }
+
+    /// Glicko rating deviation, lower values mean more reliable rating
+    async fn deviation(&self) -> f64 {
+        self.deviation
+    }
+
+    /// Rating lowered by two deviations, suitable for seeding since it doesn't favor players with few games
+    async fn conservative_rating(&self) -> f64 {
+        self.rating - 2.0 * self.deviation
+    }
+
+    async fn matches(&self) -> i32 {
+        self.matches
+    }
+
+    async fn games(&self) -> i32 {
+        self.games
+    }
+
+    async fn wins(&self) -> i32 {
+        self.wins
+    }
+}
@@ -0,0 +1,81 @@
+use sea_orm::prelude::*;
+
+use crate::prelude::ModId;
+
+pub type RatingHistoryModel = Model;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "rating_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    // Order of processing, entries of the same match share it
+    pub sequence: i64,
+    pub user_id: Uuid,
+    pub mod_type: Option<ModId>,
+    pub match_id: Uuid,
+    pub opponent_id: Uuid,
+    pub games_won: i32,
+    pub games_lost: i32,
+    pub rating_before: f64,
+    pub rating_after: f64,
+    pub deviation_before: f64,
+    pub deviation_after: f64,
+    pub created_at: DateTimeWithTimeZone
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl RatingHistoryModel {
+    async fn sequence(&self) -> i64 {
+        self.sequence
+    }
+
+    async fn user(&self) -> Uuid {
+        self.user_id
+    }
+
+    async fn mod_type(&self) -> Option<ModId> {
+        self.mod_type
+    }
+
+    async fn match_id(&self) -> Uuid {
+        self.match_id
+    }
+
+    async fn opponent(&self) -> Uuid {
+        self.opponent_id
+    }
+
+    async fn games_won(&self) -> i32 {
+        self.games_won
+    }
+
+    async fn games_lost(&self) -> i32 {
+        self.games_lost
+    }
+
+    async fn rating_before(&self) -> f64 {
+        self.rating_before
+    }
+
+    async fn rating_after(&self) -> f64 {
+        self.rating_after
+    }
+
+    async fn deviation_before(&self) -> f64 {
+        self.deviation_before
+    }
+
+    async fn deviation_after(&self) -> f64 {
+        self.deviation_after
+    }
+
+    async fn created_at(&self) -> DateTimeWithTimeZone {
+        self.created_at
+    }
+}
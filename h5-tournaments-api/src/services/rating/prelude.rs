@@ -0,0 +1,2 @@
+pub use super::service::RatingService;
+pub use super::models::{rating::RatingModel, rating_history::RatingHistoryModel};
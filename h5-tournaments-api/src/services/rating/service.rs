@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{prelude::Expr, sea_query::Query, ActiveModelTrait, ActiveValue::{NotSet, Set}, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use uuid::Uuid;
+
+use crate::{prelude::ModId, services::tournament::{error::Error, models::{game_builder::{self, GameResult}, match_structure, tournament}}};
+
+use super::{glicko::Glicko, models::{rating::{self, RatingModel}, rating_history::{self, RatingHistoryModel}}};
+
+#[derive(Clone)]
+pub struct RatingService;
+
+/// Rating loaded or created during processing of matches.
+struct RatingState {
+    model: rating::Model,
+    is_new: bool,
+    is_changed: bool
+}
+
+fn mod_type_condition<C: ColumnTrait>(column: C, mod_type: Option<ModId>) -> Condition {
+    match mod_type {
+        Some(mod_type) => Condition::all().add(column.eq(mod_type)),
+        None => Condition::all().add(column.is_null())
+    }
+}
+
+/// Conservative ratings of given users in a mod used to seed them, unrated users are missing.
+/// Conservative rating doesn't favor players with few games.
+pub(crate) async fn seeding_ratings<C: ConnectionTrait>(
+    db: &C,
+    user_ids: impl IntoIterator<Item = Uuid>,
+    mod_type: ModId
+) -> Result<HashMap<Uuid, f64>, Error> {
+    Ok(rating::Entity::find()
+        .filter(rating::Column::UserId.is_in(user_ids))
+        .filter(rating::Column::ModType.eq(mod_type))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|rating| (rating.user_id, rating.rating - 2.0 * rating.deviation))
+        .collect())
+}
+
+impl RatingService {
+    /// Rates reported matches that weren't rated yet in order of their creation. Rated matches created after
+    /// the earliest of not rated ones are taken back first, so late reports are rated in the order matches were played.
+    /// Games changed after their match was rated are only taken into account by full recompute.
+    pub async fn update_ratings(&self, db: &DatabaseConnection) -> Result<u64, Error> {
+        self.process_matches(db, false).await
+    }
+
+    /// Drops all ratings and their history and rates every reported match again.
+    pub async fn recompute_ratings(&self, db: &DatabaseConnection) -> Result<u64, Error> {
+        self.process_matches(db, true).await
+    }
+
+    async fn process_matches(&self, db: &DatabaseConnection, recompute: bool) -> Result<u64, Error> {
+        let transaction = db.begin().await?;
+        // concurrent updates would rate the same matches twice
+        transaction.execute_unprepared("LOCK TABLE rating_history IN EXCLUSIVE MODE").await?;
+
+        if recompute {
+            rating_history::Entity::delete_many().exec(&transaction).await?;
+            rating::Entity::delete_many().exec(&transaction).await?;
+        } else {
+            rollback_later_matches(&transaction).await?;
+        }
+
+        let rated_matches = Query::select()
+            .column(rating_history::Column::MatchId)
+            .from(rating_history::Entity)
+            .to_owned();
+
+        let matches = match_structure::Entity::find()
+            .find_also_related(tournament::Entity)
+            .filter(match_structure::Column::Id.not_in_subquery(rated_matches.clone()))
+            .order_by_asc(match_structure::Column::CreatedAt)
+            .order_by_asc(match_structure::Column::Id)
+            .all(&transaction)
+            .await?;
+
+        let mut games = HashMap::<Uuid, (i32, i32)>::new();
+        for game in game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.not_in_subquery(rated_matches))
+            .filter(game_builder::Column::Result.ne(GameResult::NotSelected))
+            .all(&transaction)
+            .await? {
+            let result = games.entry(game.match_id).or_default();
+            if game.result == GameResult::FirstPlayerWon {
+                result.0 += 1;
+            } else {
+                result.1 += 1;
+            }
+        }
+
+        let mut ratings = rating::Entity::find()
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .map(|model| ((model.user_id, model.mod_type), RatingState { model, is_new: false, is_changed: false }))
+            .collect::<HashMap<(Uuid, Option<ModId>), RatingState>>();
+
+        let mut sequence = rating_history::Entity::find()
+            .select_only()
+            .column_as(Expr::col(rating_history::Column::Sequence).max(), "sequence")
+            .into_tuple::<Option<i64>>()
+            .one(&transaction)
+            .await?
+            .flatten()
+            .unwrap_or(0);
+
+        let mut history = vec![];
+        let mut processed = 0;
+        for (match_model, tournament) in matches {
+            let (Some(&result), Some(tournament)) = (games.get(&match_model.id), tournament) else {
+                continue;
+            };
+            sequence += 1;
+            processed += 1;
+            history.extend(rate_match(&mut ratings, &match_model, tournament.mod_type, result, sequence));
+        }
+
+        let mut new_ratings = vec![];
+        for state in ratings.into_values().filter(|state| state.is_changed) {
+            if state.is_new {
+                new_ratings.push(state.model.into_active_model());
+            } else {
+                rating::Entity::update(state.model.into_active_model().reset_all()).exec(&transaction).await?;
+            }
+        }
+
+        for chunk in new_ratings.chunks(1000) {
+            rating::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+        for chunk in history.chunks(1000) {
+            rating_history::Entity::insert_many(chunk.to_vec()).exec(&transaction).await?;
+        }
+
+        transaction.commit().await?;
+        Ok(processed)
+    }
+
+    pub async fn get_ratings(
+        &self,
+        db: &DatabaseConnection,
+        mod_type: Option<ModId>,
+        limit: Option<u64>
+    ) -> Result<Vec<RatingModel>, Error> {
+        Ok(rating::Entity::find()
+            .filter(mod_type_condition(rating::Column::ModType, mod_type))
+            .order_by_desc(rating::Column::Rating)
+            .limit(limit)
+            .all(db)
+            .await?)
+    }
+
+    pub async fn get_rating(
+        &self,
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        mod_type: Option<ModId>
+    ) -> Result<Option<RatingModel>, Error> {
+        Ok(rating::Entity::find()
+            .filter(rating::Column::UserId.eq(user_id))
+            .filter(mod_type_condition(rating::Column::ModType, mod_type))
+            .one(db)
+            .await?)
+    }
+
+    pub async fn get_rating_history(
+        &self,
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        mod_type: Option<ModId>
+    ) -> Result<Vec<RatingHistoryModel>, Error> {
+        Ok(rating_history::Entity::find()
+            .filter(rating_history::Column::UserId.eq(user_id))
+            .filter(mod_type_condition(rating_history::Column::ModType, mod_type))
+            .order_by_asc(rating_history::Column::Sequence)
+            .all(db)
+            .await?)
+    }
+}
+
+/// Takes back ratings of matches created after the earliest reported match that wasn't rated yet.
+/// Ratings return to the state before the first of taken back matches, ratings left without matches are removed.
+async fn rollback_later_matches<C: ConnectionTrait>(db: &C) -> Result<(), Error> {
+    let rated_matches = Query::select()
+        .column(rating_history::Column::MatchId)
+        .from(rating_history::Entity)
+        .to_owned();
+    let reported_matches = Query::select()
+        .column(game_builder::Column::MatchId)
+        .from(game_builder::Entity)
+        .and_where(game_builder::Column::Result.ne(GameResult::NotSelected))
+        .to_owned();
+    let Some(earliest) = match_structure::Entity::find()
+        .filter(match_structure::Column::Id.not_in_subquery(rated_matches))
+        .filter(match_structure::Column::Id.in_subquery(reported_matches))
+        .order_by_asc(match_structure::Column::CreatedAt)
+        .order_by_asc(match_structure::Column::Id)
+        .one(db)
+        .await? else {
+        return Ok(());
+    };
+
+    let later_matches = Query::select()
+        .column(match_structure::Column::Id)
+        .from(match_structure::Entity)
+        .cond_where(
+            Condition::any()
+                .add(match_structure::Column::CreatedAt.gt(earliest.created_at))
+                .add(Condition::all().add(match_structure::Column::CreatedAt.eq(earliest.created_at)).add(match_structure::Column::Id.gt(earliest.id)))
+        )
+        .to_owned();
+    let taken_back = rating_history::Entity::find()
+        .filter(rating_history::Column::MatchId.in_subquery(later_matches))
+        .order_by_asc(rating_history::Column::Sequence)
+        .all(db)
+        .await?;
+    if taken_back.is_empty() {
+        return Ok(());
+    }
+
+    let mut ratings = HashMap::new();
+    for (user_id, mod_type) in taken_back.iter().map(|entry| (entry.user_id, entry.mod_type)).collect::<HashSet<(Uuid, Option<ModId>)>>() {
+        if let Some(model) = rating::Entity::find()
+            .filter(rating::Column::UserId.eq(user_id))
+            .filter(mod_type_condition(rating::Column::ModType, mod_type))
+            .one(db)
+            .await? {
+            ratings.insert((user_id, mod_type), model);
+        }
+    }
+    take_back(&mut ratings, &taken_back);
+
+    rating_history::Entity::delete_many()
+        .filter(rating_history::Column::Id.is_in(taken_back.iter().map(|entry| entry.id).collect::<Vec<Uuid>>()))
+        .exec(db)
+        .await?;
+    for model in ratings.into_values() {
+        if model.matches > 0 {
+            rating::Entity::update(model.into_active_model().reset_all()).exec(db).await?;
+        } else {
+            rating::Entity::delete_by_id(model.id).exec(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns ratings to the state before the first of taken back history entries, which are ordered by sequence.
+fn take_back(ratings: &mut HashMap<(Uuid, Option<ModId>), rating::Model>, taken_back: &[rating_history::Model]) {
+    let mut restored = HashSet::new();
+    for entry in taken_back {
+        let key = (entry.user_id, entry.mod_type);
+        let Some(model) = ratings.get_mut(&key) else {
+            continue;
+        };
+        if restored.insert(key) {
+            model.rating = entry.rating_before;
+            model.deviation = entry.deviation_before;
+        }
+        model.matches -= 1;
+        model.games -= entry.games_won + entry.games_lost;
+        model.wins -= entry.games_won;
+    }
+}
+
+/// Rates match in overall ratings of its players and in ones of its mod, returns history entries of both.
+fn rate_match(
+    ratings: &mut HashMap<(Uuid, Option<ModId>), RatingState>,
+    match_model: &match_structure::Model,
+    mod_type: ModId,
+    (first_player_wins, second_player_wins): (i32, i32),
+    sequence: i64
+) -> Vec<rating_history::ActiveModel> {
+    let mut history = vec![];
+    for mod_type in [None, Some(mod_type)] {
+        let first_player = rating_entry(ratings, match_model.first_player, mod_type);
+        let second_player = rating_entry(ratings, match_model.second_player, mod_type);
+        let first_player_updated = first_player.updated(&second_player, first_player_wins, second_player_wins);
+        let second_player_updated = second_player.updated(&first_player, second_player_wins, first_player_wins);
+
+        for (user_id, opponent_id, before, after, won, lost) in [
+            (match_model.first_player, match_model.second_player, first_player, first_player_updated, first_player_wins, second_player_wins),
+            (match_model.second_player, match_model.first_player, second_player, second_player_updated, second_player_wins, first_player_wins)
+        ] {
+            let state = ratings.get_mut(&(user_id, mod_type)).unwrap();
+            state.model.rating = after.rating;
+            state.model.deviation = after.deviation;
+            state.model.matches += 1;
+            state.model.games += won + lost;
+            state.model.wins += won;
+            state.is_changed = true;
+
+            history.push(rating_history::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                sequence: Set(sequence),
+                user_id: Set(user_id),
+                mod_type: Set(mod_type),
+                match_id: Set(match_model.id),
+                opponent_id: Set(opponent_id),
+                games_won: Set(won),
+                games_lost: Set(lost),
+                rating_before: Set(before.rating),
+                rating_after: Set(after.rating),
+                deviation_before: Set(before.deviation),
+                deviation_after: Set(after.deviation),
+                created_at: NotSet
+            });
+        }
+    }
+    history
+}
+
+/// Current rating of user, players without one start with initial rating.
+fn rating_entry(ratings: &mut HashMap<(Uuid, Option<ModId>), RatingState>, user_id: Uuid, mod_type: Option<ModId>) -> Glicko {
+    let state = ratings.entry((user_id, mod_type)).or_insert_with(|| {
+        let initial = Glicko::default();
+        RatingState {
+            model: rating::Model {
+                id: Uuid::new_v4(),
+                user_id,
+                mod_type,
+                rating: initial.rating,
+                deviation: initial.deviation,
+                matches: 0,
+                games: 0,
+                wins: 0
+            },
+            is_new: true,
+            is_changed: false
+        }
+    });
+    Glicko {
+        rating: state.model.rating,
+        deviation: state.model.deviation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sea_orm::{prelude::{DateTimeUtc, DateTimeWithTimeZone}, ActiveValue::Set, TryIntoModel};
+    use uuid::Uuid;
+
+    use crate::{prelude::ModId, services::{rating::models::{rating, rating_history}, tournament::models::match_structure}};
+
+    use super::{rate_match, take_back, RatingState};
+
+    type Ratings = HashMap<(Uuid, Option<ModId>), RatingState>;
+
+    fn match_between(first_player: Uuid, second_player: Uuid) -> match_structure::Model {
+        match_structure::Model {
+            id: Uuid::new_v4(),
+            tournament_id: Uuid::new_v4(),
+            message_id: None,
+            first_player,
+            second_player,
+            challonge_id: None,
+            report_link: None,
+            round: None,
+            deadline: None,
+            stage: None,
+            created_at: DateTimeWithTimeZone::from(DateTimeUtc::from_timestamp(0, 0).unwrap())
+        }
+    }
+
+    /// Rates matches one after another, as update of ratings does, returning stored history.
+    fn rate(ratings: &mut Ratings, matches: &[(&match_structure::Model, (i32, i32))], sequence: i64) -> Vec<rating_history::Model> {
+        matches.iter()
+            .enumerate()
+            .flat_map(|(index, (match_model, result))| rate_match(ratings, match_model, ModId::UNIVERSE, *result, sequence + index as i64))
+            .map(|mut entry| {
+                entry.created_at = Set(DateTimeWithTimeZone::from(DateTimeUtc::from_timestamp(0, 0).unwrap()));
+                entry.try_into_model().unwrap()
+            })
+            .collect()
+    }
+
+    fn stored(ratings: &Ratings) -> HashMap<(Uuid, Option<ModId>), rating::Model> {
+        ratings.iter().map(|(key, state)| (*key, state.model.clone())).collect()
+    }
+
+    #[test]
+    fn match_is_rated_overall_and_in_its_mod() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut ratings = Ratings::new();
+        let history = rate(&mut ratings, &[(&match_between(a, b), (2, 1))], 1);
+
+        assert_eq!(history.len(), 4);
+        for mod_type in [None, Some(ModId::UNIVERSE)] {
+            let winner = &ratings[&(a, mod_type)].model;
+            let loser = &ratings[&(b, mod_type)].model;
+            assert!(winner.rating > loser.rating);
+            assert_eq!((winner.matches, winner.games, winner.wins), (1, 3, 2));
+            assert_eq!((loser.matches, loser.games, loser.wins), (1, 3, 1));
+        }
+    }
+
+    #[test]
+    fn late_report_is_rated_before_taken_back_matches() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let first = match_between(a, b);
+        let late = match_between(b, c);
+        let last = match_between(a, c);
+
+        // first and last matches were rated before the one played between them was reported
+        let mut ratings = Ratings::new();
+        rate(&mut ratings, &[(&first, (2, 0))], 1);
+        let after_first = stored(&ratings);
+        let taken_back = rate(&mut ratings, &[(&last, (1, 2))], 2);
+
+        let mut restored = stored(&ratings);
+        take_back(&mut restored, &taken_back);
+        for (key, model) in &after_first {
+            assert_eq!(&restored[key], model);
+        }
+        // ratings created by taken back matches are left without matches and removed
+        for key in [(c, None), (c, Some(ModId::UNIVERSE))] {
+            assert_eq!(restored[&key].matches, 0);
+        }
+
+        let mut ratings = restored.into_iter()
+            .filter(|(_, model)| model.matches > 0)
+            .map(|(key, model)| (key, RatingState { model, is_new: false, is_changed: false }))
+            .collect::<Ratings>();
+        rate(&mut ratings, &[(&late, (1, 2)), (&last, (1, 2))], 2);
+
+        let mut expected = Ratings::new();
+        rate(&mut expected, &[(&first, (2, 0)), (&late, (1, 2)), (&last, (1, 2))], 1);
+        for (key, state) in &expected {
+            let model = &ratings[key].model;
+            assert!((model.rating - state.model.rating).abs() < 1e-9);
+            assert!((model.deviation - state.model.deviation).abs() < 1e-9);
+            assert_eq!((model.matches, model.games, model.wins), (state.model.matches, state.model.games, state.model.wins));
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub(crate) mod ranking;
+mod service;
+pub(crate) mod prelude;
+pub mod models;
+pub mod types;
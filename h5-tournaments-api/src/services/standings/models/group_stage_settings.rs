@@ -0,0 +1,66 @@
+use sea_orm::{prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+pub type GroupStageSettingsModel = Model;
+
+/// Criteria players of group are ordered by, every next one only applies to players still tied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, async_graphql::Enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StandingsTiebreaker {
+    MatchWins,
+    /// Games won minus games lost
+    GameDifference,
+    GameWins,
+    /// Match wins counted only in matches between tied players
+    HeadToHead
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Tiebreakers(pub Vec<StandingsTiebreaker>);
+
+impl Default for Tiebreakers {
+    fn default() -> Self {
+        Tiebreakers(vec![StandingsTiebreaker::MatchWins, StandingsTiebreaker::GameDifference, StandingsTiebreaker::HeadToHead])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "group_stage_settings")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tournament_id: Uuid,
+    pub qualifiers_per_group: i32,
+    pub tiebreakers: Tiebreakers
+}
+
+impl Model {
+    /// Settings of tournament organizer didn't change yet.
+    pub fn default_for(tournament_id: Uuid) -> Self {
+        Model {
+            tournament_id,
+            qualifiers_per_group: 2,
+            tiebreakers: Tiebreakers::default()
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl GroupStageSettingsModel {
+    async fn tournament(&self) -> Uuid {
+        self.tournament_id
+    }
+
+    /// Count of best players of every group who advance to play-off
+    async fn qualifiers_per_group(&self) -> i32 {
+        self.qualifiers_per_group
+    }
+
+    async fn tiebreakers(&self) -> Vec<StandingsTiebreaker> {
+        self.tiebreakers.0.clone()
+    }
+}
@@ -0,0 +1 @@
+pub(crate) mod group_stage_settings;
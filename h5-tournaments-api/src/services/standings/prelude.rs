@@ -0,0 +1,3 @@
+pub use super::service::StandingsService;
+pub use super::models::group_stage_settings::{GroupStageSettingsModel, StandingsTiebreaker};
+pub use super::types::{GroupStageSettingsInput, GroupStandingsModel, StandingsEntryModel};
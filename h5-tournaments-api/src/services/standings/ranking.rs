@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::models::group_stage_settings::StandingsTiebreaker;
+
+/// Played match of group with count of games each player won.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MatchResult {
+    pub first_player: Uuid,
+    pub second_player: Uuid,
+    pub first_player_wins: i32,
+    pub second_player_wins: i32
+}
+
+impl MatchResult {
+    /// Wins of given player and of the opponent, None if player didn't play this match.
+    pub(crate) fn score_of(&self, player: Uuid) -> Option<(i32, i32)> {
+        if self.first_player == player {
+            Some((self.first_player_wins, self.second_player_wins))
+        } else if self.second_player == player {
+            Some((self.second_player_wins, self.first_player_wins))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct PlayerRecord {
+    pub matches: i32,
+    pub match_wins: i32,
+    pub match_draws: i32,
+    pub match_losses: i32,
+    pub game_wins: i32,
+    pub game_losses: i32
+}
+
+/// Totals of player over given matches, only matches where opponent is in `opponents` are counted.
+pub(crate) fn record(player: Uuid, matches: &[MatchResult], opponents: Option<&HashSet<Uuid>>) -> PlayerRecord {
+    let mut record = PlayerRecord::default();
+    for match_result in matches {
+        let Some((wins, losses)) = match_result.score_of(player) else {
+            continue;
+        };
+        let opponent = if match_result.first_player == player { match_result.second_player } else { match_result.first_player };
+        if opponents.is_some_and(|opponents| !opponents.contains(&opponent)) {
+            continue;
+        }
+        record.matches += 1;
+        record.game_wins += wins;
+        record.game_losses += losses;
+        match wins.cmp(&losses) {
+            std::cmp::Ordering::Greater => record.match_wins += 1,
+            std::cmp::Ordering::Equal => record.match_draws += 1,
+            std::cmp::Ordering::Less => record.match_losses += 1
+        }
+    }
+    record
+}
+
+/// Orders players by tiebreakers, players none of tiebreakers could separate end up in the same chunk.
+/// Players must come in the order ties should be listed in. Head to head that split tied players
+/// is applied again to every smaller tie before moving to the next tiebreaker.
+pub(crate) fn rank(players: Vec<Uuid>, matches: &[MatchResult], tiebreakers: &[StandingsTiebreaker]) -> Vec<Vec<Uuid>> {
+    let Some((tiebreaker, rest)) = tiebreakers.split_first() else {
+        return vec![players];
+    };
+    if players.len() < 2 {
+        return vec![players];
+    }
+
+    // head to head is recalculated for every set of tied players
+    let tied = players.iter().copied().collect::<HashSet<Uuid>>();
+    let keys = players.iter()
+        .map(|player| {
+            let key = match tiebreaker {
+                StandingsTiebreaker::MatchWins => record(*player, matches, None).match_wins,
+                StandingsTiebreaker::GameDifference => {
+                    let record = record(*player, matches, None);
+                    record.game_wins - record.game_losses
+                },
+                StandingsTiebreaker::GameWins => record(*player, matches, None).game_wins,
+                StandingsTiebreaker::HeadToHead => record(*player, matches, Some(&tied)).match_wins
+            };
+            (*player, key)
+        })
+        .collect::<HashMap<Uuid, i32>>();
+
+    let mut sorted = players;
+    sorted.sort_by_key(|player| std::cmp::Reverse(keys[player]));
+
+    let mut chunks: Vec<Vec<Uuid>> = vec![];
+    for player in sorted {
+        match chunks.last_mut() {
+            Some(chunk) if keys[&chunk[0]] == keys[&player] => chunk.push(player),
+            _ => chunks.push(vec![player])
+        }
+    }
+    let next = if *tiebreaker == StandingsTiebreaker::HeadToHead && chunks.len() > 1 { tiebreakers } else { rest };
+    chunks.into_iter().flat_map(|chunk| rank(chunk, matches, next)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(index: u128) -> Uuid {
+        Uuid::from_u128(index)
+    }
+
+    fn result(first: u128, second: u128, first_wins: i32, second_wins: i32) -> MatchResult {
+        MatchResult {
+            first_player: player(first),
+            second_player: player(second),
+            first_player_wins: first_wins,
+            second_player_wins: second_wins
+        }
+    }
+
+    #[test]
+    fn record_counts_only_given_opponents() {
+        let matches = [result(1, 2, 2, 0), result(1, 3, 1, 1), result(3, 1, 2, 1)];
+        assert_eq!(record(player(1), &matches, None), PlayerRecord {
+            matches: 3,
+            match_wins: 1,
+            match_draws: 1,
+            match_losses: 1,
+            game_wins: 4,
+            game_losses: 3
+        });
+        let opponents = HashSet::from([player(2)]);
+        assert_eq!(record(player(1), &matches, Some(&opponents)).match_wins, 1);
+        assert_eq!(record(player(1), &matches, Some(&opponents)).matches, 1);
+    }
+
+    #[test]
+    fn head_to_head_only_counts_matches_between_tied_players() {
+        // 1, 2 and 3 beat each other in a cycle and all beat 4
+        let matches = [
+            result(1, 2, 2, 0),
+            result(2, 3, 2, 0),
+            result(3, 1, 2, 0),
+            result(1, 4, 2, 1),
+            result(2, 4, 2, 0),
+            result(3, 4, 2, 0)
+        ];
+        let players = (1..=4).map(player).collect::<Vec<Uuid>>();
+        // cycle between tied players can't be broken, so they keep given order
+        assert_eq!(
+            rank(players, &matches, &[StandingsTiebreaker::MatchWins, StandingsTiebreaker::HeadToHead]),
+            vec![vec![player(1), player(2), player(3)], vec![player(4)]]
+        );
+    }
+
+    #[test]
+    fn head_to_head_is_applied_again_within_smaller_ties() {
+        // head to head among all four gives 1 and 4 two wins, 2 and 3 one win,
+        // then 4 beat 1 and 2 beat 3 though game wins say otherwise
+        let matches = [
+            result(1, 2, 2, 0),
+            result(1, 3, 2, 0),
+            result(2, 3, 2, 1),
+            result(3, 4, 2, 0),
+            result(4, 1, 2, 1),
+            result(4, 2, 2, 1)
+        ];
+        let players = (1..=4).map(player).collect::<Vec<Uuid>>();
+        assert_eq!(
+            rank(players, &matches, &[StandingsTiebreaker::HeadToHead, StandingsTiebreaker::GameWins]),
+            vec![vec![player(4)], vec![player(1)], vec![player(2)], vec![player(3)]]
+        );
+    }
+
+    #[test]
+    fn later_tiebreakers_separate_what_head_to_head_could_not() {
+        // 1 and 2 drew, 1 won more games against 3
+        let matches = [result(1, 2, 1, 1), result(1, 3, 2, 0), result(2, 3, 2, 1)];
+        let players = vec![player(2), player(1)];
+        assert_eq!(
+            rank(players.clone(), &matches, &[StandingsTiebreaker::HeadToHead, StandingsTiebreaker::GameDifference]),
+            vec![vec![player(1)], vec![player(2)]]
+        );
+        assert_eq!(rank(players, &matches, &[StandingsTiebreaker::HeadToHead]), vec![vec![player(2), player(1)]]);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use sea_orm::{sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set};
+use uuid::Uuid;
+
+use crate::services::{bracket::models::bracket_match::{self, BracketSection}, tournament::{error::Error, models::{game_builder::{self, GameResult}, match_structure, participant, tournament, user}}};
+
+use super::{models::group_stage_settings::{self, GroupStageSettingsModel, Tiebreakers}, ranking::{self, MatchResult}, types::{GroupStageSettingsInput, GroupStandingsModel, StandingsEntryModel}};
+
+#[derive(Clone)]
+pub struct StandingsService;
+
+impl StandingsService {
+    pub async fn get_settings(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid
+    ) -> Result<GroupStageSettingsModel, Error> {
+        Ok(group_stage_settings::Entity::find_by_id(tournament_id)
+            .one(db)
+            .await?
+            .unwrap_or_else(|| GroupStageSettingsModel::default_for(tournament_id)))
+    }
+
+    pub async fn update_settings(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        changes: GroupStageSettingsInput
+    ) -> Result<GroupStageSettingsModel, Error> {
+        let mut settings = self.get_settings(db, tournament_id).await?;
+        if let Some(qualifiers_per_group) = changes.qualifiers_per_group {
+            if qualifiers_per_group < 0 {
+                return Err(Error::Validation("Count of qualifiers can't be negative".to_string()));
+            }
+            settings.qualifiers_per_group = qualifiers_per_group;
+        }
+        if let Some(tiebreakers) = changes.tiebreakers {
+            if tiebreakers.is_empty() {
+                return Err(Error::Validation("At least one tiebreaker is required".to_string()));
+            }
+            if tiebreakers.iter().collect::<HashSet<_>>().len() != tiebreakers.len() {
+                return Err(Error::Validation("Tiebreakers can't be repeated".to_string()));
+            }
+            settings.tiebreakers = Tiebreakers(tiebreakers);
+        }
+
+        let settings_to_save = group_stage_settings::ActiveModel {
+            tournament_id: Set(tournament_id),
+            qualifiers_per_group: Set(settings.qualifiers_per_group),
+            tiebreakers: Set(settings.tiebreakers.clone())
+        };
+        group_stage_settings::Entity::insert(settings_to_save)
+            .on_conflict(
+                OnConflict::column(group_stage_settings::Column::TournamentId)
+                    .update_columns([group_stage_settings::Column::QualifiersPerGroup, group_stage_settings::Column::Tiebreakers])
+                    .to_owned()
+            )
+            .exec(db)
+            .await?;
+        Ok(settings)
+    }
+
+    /// Tables of tournament groups built from reported games, only matches between players of the same group are counted.
+    /// Matches of elimination brackets are skipped, so play-off doesn't change group tables.
+    pub async fn get_standings(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        group: Option<i32>
+    ) -> Result<Vec<GroupStandingsModel>, Error> {
+        tournament::Entity::find_by_id(tournament_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+        let settings = self.get_settings(db, tournament_id).await?;
+
+        let participants = participant::Entity::find()
+            .find_also_related(user::Entity)
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|(participant, _)| group.is_none_or(|group| participant.group_number == group))
+            .filter_map(|(participant, user)| user.map(|user| (participant.group_number, user)))
+            .collect::<Vec<_>>();
+        let groups_of_players = participants.iter()
+            .map(|(group_number, user)| (user.id, *group_number))
+            .collect::<HashMap<Uuid, i32>>();
+
+        let matches = match_structure::Entity::find()
+            .filter(match_structure::Column::TournamentId.eq(tournament_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|match_model| {
+                let first_group = groups_of_players.get(&match_model.first_player);
+                first_group.is_some() && first_group == groups_of_players.get(&match_model.second_player)
+            })
+            .collect::<Vec<_>>();
+        let match_ids = matches.iter().map(|match_model| match_model.id).collect::<Vec<Uuid>>();
+
+        let play_off_matches = bracket_match::Entity::find()
+            .select_only()
+            .column(bracket_match::Column::MatchId)
+            .filter(bracket_match::Column::MatchId.is_in(match_ids.clone()))
+            .filter(bracket_match::Column::Section.ne(BracketSection::RoundRobin))
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect::<HashSet<Uuid>>();
+
+        let mut scores = HashMap::<Uuid, (i32, i32)>::new();
+        for game in game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.is_in(match_ids))
+            .filter(game_builder::Column::Result.ne(GameResult::NotSelected))
+            .all(db)
+            .await? {
+            let score = scores.entry(game.match_id).or_default();
+            if game.result == GameResult::FirstPlayerWon {
+                score.0 += 1;
+            } else {
+                score.1 += 1;
+            }
+        }
+
+        // matches without reported games aren't played yet
+        let mut results = BTreeMap::<i32, Vec<MatchResult>>::new();
+        for match_model in matches.into_iter().filter(|match_model| !play_off_matches.contains(&match_model.id)) {
+            let Some(&(first_player_wins, second_player_wins)) = scores.get(&match_model.id) else {
+                continue;
+            };
+            results.entry(groups_of_players[&match_model.first_player]).or_default().push(MatchResult {
+                first_player: match_model.first_player,
+                second_player: match_model.second_player,
+                first_player_wins,
+                second_player_wins
+            });
+        }
+
+        let mut groups = BTreeMap::<i32, Vec<user::Model>>::new();
+        for (group_number, user) in participants {
+            groups.entry(group_number).or_default().push(user);
+        }
+
+        let mut standings = vec![];
+        for (group_number, mut users) in groups {
+            // players nothing could separate are listed by nickname
+            users.sort_by(|first, second| first.nickname.to_lowercase().cmp(&second.nickname.to_lowercase()).then(first.id.cmp(&second.id)));
+            let group_results = results.remove(&group_number).unwrap_or_default();
+            let ranked = ranking::rank(users.iter().map(|user| user.id).collect(), &group_results, &settings.tiebreakers.0);
+            let mut users = users.into_iter().map(|user| (user.id, user)).collect::<HashMap<Uuid, user::Model>>();
+
+            let mut entries = vec![];
+            for chunk in ranked {
+                let tied = chunk.len() > 1;
+                for user_id in chunk {
+                    let record = ranking::record(user_id, &group_results, None);
+                    let position = entries.len() as i32 + 1;
+                    entries.push(StandingsEntryModel {
+                        position,
+                        user: users.remove(&user_id).unwrap(),
+                        matches: record.matches,
+                        match_wins: record.match_wins,
+                        match_draws: record.match_draws,
+                        match_losses: record.match_losses,
+                        game_wins: record.game_wins,
+                        game_losses: record.game_losses,
+                        game_difference: record.game_wins - record.game_losses,
+                        tied,
+                        qualified: position <= settings.qualifiers_per_group
+                    });
+                }
+            }
+            standings.push(GroupStandingsModel { group: group_number, entries });
+        }
+        Ok(standings)
+    }
+}
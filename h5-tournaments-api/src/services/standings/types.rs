@@ -0,0 +1,35 @@
+use crate::services::tournament::models::user::UserModel;
+
+use super::models::group_stage_settings::StandingsTiebreaker;
+
+/// Changes of group stage settings, fields left empty are kept as is.
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct GroupStageSettingsInput {
+    pub qualifiers_per_group: Option<i32>,
+    /// Replaces the whole order of tiebreakers
+    pub tiebreakers: Option<Vec<StandingsTiebreaker>>
+}
+
+/// Row of group table. Match is won by player who won more of its games, matches with equal count of wins are draws.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct StandingsEntryModel {
+    pub position: i32,
+    pub user: UserModel,
+    pub matches: i32,
+    pub match_wins: i32,
+    pub match_draws: i32,
+    pub match_losses: i32,
+    pub game_wins: i32,
+    pub game_losses: i32,
+    pub game_difference: i32,
+    /// Player shares position with neighbours, none of tiebreakers could separate them
+    pub tied: bool,
+    /// Player advances to play-off
+    pub qualified: bool
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct GroupStandingsModel {
+    pub group: i32,
+    pub entries: Vec<StandingsEntryModel>
+}
@@ -0,0 +1,2 @@
+pub use super::service::StatisticsService;
+pub use super::types::{StatisticsFilter, RaceMatchupModel, MatchupsStatisticsModel, HeroStatisticsModel, BargainsStatisticsModel, PlayerProfileModel, HeadToHeadModel};
@@ -0,0 +1,676 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait};
+use uuid::Uuid;
+
+use crate::{prelude::ModId, services::tournament::{error::Error, models::{game_builder::{self, BargainsColor, GameResult}, heroes::{self, HeroNew}, match_structure, participant, tournament, user}}};
+
+use super::types::{BargainsAmountCountModel, BargainsAmountResultModel, BargainsColorResultModel, BargainsMatchupModel, BargainsResultRow, BargainsStatisticsModel, HeadToHeadGameModel, HeadToHeadMatchModel, HeadToHeadModel, HeroResultRow, HeroStatisticsModel, MatchupsStatisticsModel, PlayerBargainsModel, PlayerHeroModel, PlayerProfileModel, PlayerRaceModel, PlayerReportModel, PlayerTournamentModel, RaceMatchupModel, RaceResultRow, StatisticsFilter};
+
+#[derive(Clone)]
+pub struct StatisticsService;
+
+impl StatisticsService {
+    pub async fn get_race_matchups(
+        &self,
+        db: &DatabaseConnection,
+        filter: StatisticsFilter
+    ) -> Result<MatchupsStatisticsModel, Error> {
+        let rows = game_builder::Entity::find()
+            .select_only()
+            .column(game_builder::Column::FirstPlayerRace)
+            .column(game_builder::Column::SecondPlayerRace)
+            .column(game_builder::Column::Result)
+            .column_as(game_builder::Column::Id.count(), "games")
+            .join(JoinType::InnerJoin, game_builder::Relation::Match.def())
+            .join(JoinType::InnerJoin, match_structure::Relation::Tournament.def())
+            .filter(filter_condition(&filter))
+            .filter(game_builder::Column::FirstPlayerRace.is_not_null())
+            .filter(game_builder::Column::SecondPlayerRace.is_not_null())
+            .group_by(game_builder::Column::FirstPlayerRace)
+            .group_by(game_builder::Column::SecondPlayerRace)
+            .group_by(game_builder::Column::Result)
+            .into_model::<RaceResultRow>()
+            .all(db)
+            .await?;
+
+        Ok(aggregate_matchups(&rows))
+    }
+
+    pub async fn get_hero_statistics(
+        &self,
+        db: &DatabaseConnection,
+        filter: StatisticsFilter,
+        foreign_heroes: Option<bool>
+    ) -> Result<Vec<HeroStatisticsModel>, Error> {
+        let rows = game_builder::Entity::find()
+            .select_only()
+            .column(tournament::Column::ModType)
+            .column(game_builder::Column::FirstPlayerRace)
+            .column(game_builder::Column::FirstPlayerHero)
+            .column(game_builder::Column::SecondPlayerRace)
+            .column(game_builder::Column::SecondPlayerHero)
+            .column(game_builder::Column::Result)
+            .column_as(game_builder::Column::Id.count(), "games")
+            .join(JoinType::InnerJoin, game_builder::Relation::Match.def())
+            .join(JoinType::InnerJoin, match_structure::Relation::Tournament.def())
+            .filter(filter_condition(&filter))
+            .filter(game_builder::Column::FirstPlayerRace.is_not_null())
+            .filter(game_builder::Column::FirstPlayerHero.is_not_null())
+            .filter(game_builder::Column::SecondPlayerRace.is_not_null())
+            .filter(game_builder::Column::SecondPlayerHero.is_not_null())
+            .group_by(tournament::Column::ModType)
+            .group_by(game_builder::Column::FirstPlayerRace)
+            .group_by(game_builder::Column::FirstPlayerHero)
+            .group_by(game_builder::Column::SecondPlayerRace)
+            .group_by(game_builder::Column::SecondPlayerHero)
+            .group_by(game_builder::Column::Result)
+            .into_model::<HeroResultRow>()
+            .all(db)
+            .await?;
+
+        let catalogues = heroes::Entity::find()
+            .filter(heroes::Column::ModType.is_in(rows.iter().map(|row| row.mod_type).collect::<Vec<ModId>>()))
+            .all(db)
+            .await?;
+        let heroes = catalogues.iter()
+            .flat_map(|catalogue| catalogue.heroes.entities.iter().map(|hero| ((catalogue.mod_type, hero.id), hero)))
+            .collect::<HashMap<(ModId, i32), &HeroNew>>();
+
+        Ok(aggregate_heroes(&rows, &heroes, foreign_heroes))
+    }
+
+    pub async fn get_bargains_statistics(
+        &self,
+        db: &DatabaseConnection,
+        filter: StatisticsFilter
+    ) -> Result<BargainsStatisticsModel, Error> {
+        let rows = game_builder::Entity::find()
+            .select_only()
+            .column(game_builder::Column::FirstPlayerRace)
+            .column(game_builder::Column::SecondPlayerRace)
+            .column(game_builder::Column::BargainsAmount)
+            .column(game_builder::Column::BargainsColor)
+            .column(tournament::Column::WithBargainsColor)
+            .column(game_builder::Column::Result)
+            .column_as(game_builder::Column::Id.count(), "games")
+            .join(JoinType::InnerJoin, game_builder::Relation::Match.def())
+            .join(JoinType::InnerJoin, match_structure::Relation::Tournament.def())
+            .filter(filter_condition(&filter))
+            .filter(tournament::Column::WithBargains.eq(true))
+            .filter(game_builder::Column::FirstPlayerRace.is_not_null())
+            .filter(game_builder::Column::SecondPlayerRace.is_not_null())
+            .filter(game_builder::Column::BargainsAmount.is_not_null())
+            .group_by(game_builder::Column::FirstPlayerRace)
+            .group_by(game_builder::Column::SecondPlayerRace)
+            .group_by(game_builder::Column::BargainsAmount)
+            .group_by(game_builder::Column::BargainsColor)
+            .group_by(tournament::Column::WithBargainsColor)
+            .group_by(game_builder::Column::Result)
+            .into_model::<BargainsResultRow>()
+            .all(db)
+            .await?;
+
+        Ok(aggregate_bargains(&rows))
+    }
+
+    pub async fn get_player_profile(
+        &self,
+        db: &DatabaseConnection,
+        user_id: Uuid
+    ) -> Result<PlayerProfileModel, Error> {
+        let user = user::Entity::find_by_id(user_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No user found with id {}", user_id)))?;
+
+        let participants = participant::Entity::find()
+            .filter(participant::Column::UserId.eq(user_id))
+            .all(db)
+            .await?;
+
+        let matches = match_structure::Entity::find()
+            .filter(Condition::any()
+                .add(match_structure::Column::FirstPlayer.eq(user_id))
+                .add(match_structure::Column::SecondPlayer.eq(user_id)))
+            .all(db)
+            .await?;
+
+        let games = game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.is_in(matches.iter().map(|match_model| match_model.id).collect::<Vec<Uuid>>()))
+            .filter(game_builder::Column::Result.ne(GameResult::NotSelected))
+            .all(db)
+            .await?;
+
+        let tournament_ids = participants.iter().map(|participant| participant.tournament_id)
+            .chain(matches.iter().map(|match_model| match_model.tournament_id))
+            .collect::<HashSet<Uuid>>();
+        let tournaments = tournament::Entity::find()
+            .filter(tournament::Column::Id.is_in(tournament_ids))
+            .order_by_asc(tournament::Column::Name)
+            .all(db)
+            .await?;
+
+        Ok(aggregate_profile(user, &participants, &tournaments, &matches, &games))
+    }
+
+    pub async fn get_head_to_head(
+        &self,
+        db: &DatabaseConnection,
+        user_a: Uuid,
+        user_b: Uuid
+    ) -> Result<HeadToHeadModel, Error> {
+        if user_a == user_b {
+            return Err(Error::Validation("Head to head requires two different users".to_string()));
+        }
+
+        let users = user::Entity::find()
+            .filter(user::Column::Id.is_in([user_a, user_b]))
+            .all(db)
+            .await?;
+        let find_user = |id: Uuid| users.iter()
+            .find(|user| user.id == id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No user found with id {}", id)));
+        let (user_a_model, user_b_model) = (find_user(user_a)?, find_user(user_b)?);
+
+        let matches = match_structure::Entity::find()
+            .find_also_related(tournament::Entity)
+            .filter(Condition::any()
+                .add(match_structure::Column::FirstPlayer.eq(user_a).and(match_structure::Column::SecondPlayer.eq(user_b)))
+                .add(match_structure::Column::FirstPlayer.eq(user_b).and(match_structure::Column::SecondPlayer.eq(user_a))))
+            .order_by_asc(match_structure::Column::CreatedAt)
+            .order_by_asc(match_structure::Column::Id)
+            .all(db)
+            .await?;
+
+        let mut games = HashMap::<Uuid, Vec<game_builder::Model>>::new();
+        for game in game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.is_in(matches.iter().map(|(match_model, _)| match_model.id).collect::<Vec<Uuid>>()))
+            .order_by_asc(game_builder::Column::CreatedAt)
+            .all(db)
+            .await? {
+            games.entry(game.match_id).or_default().push(game);
+        }
+
+        let mut head_to_head = HeadToHeadModel {
+            user_a: user_a_model,
+            user_b: user_b_model,
+            user_a_match_wins: 0,
+            user_b_match_wins: 0,
+            user_a_game_wins: 0,
+            user_b_game_wins: 0,
+            matches: vec![]
+        };
+
+        for (match_model, tournament) in matches {
+            let Some(tournament) = tournament else {
+                continue;
+            };
+            // matches without reported games aren't played yet
+            let Some(match_games) = games.remove(&match_model.id) else {
+                continue;
+            };
+            let user_a_is_first = match_model.first_player == user_a;
+            let match_games = match_games.into_iter()
+                .map(|game| {
+                    let winner = match game.result {
+                        GameResult::FirstPlayerWon => Some(match_model.first_player),
+                        GameResult::SecondPlayerWon => Some(match_model.second_player),
+                        GameResult::NotSelected => None
+                    };
+                    let (user_a_race, user_a_hero, user_b_race, user_b_hero) = if user_a_is_first {
+                        (game.first_player_race, game.first_player_hero, game.second_player_race, game.second_player_hero)
+                    } else {
+                        (game.second_player_race, game.second_player_hero, game.first_player_race, game.first_player_hero)
+                    };
+                    HeadToHeadGameModel {
+                        id: game.id,
+                        user_a_race,
+                        user_a_hero,
+                        user_b_race,
+                        user_b_hero,
+                        winner,
+                        outcome: game.outcome,
+                        created_at: game.created_at
+                    }
+                })
+                .collect::<Vec<HeadToHeadGameModel>>();
+
+            let user_a_wins = match_games.iter().filter(|game| game.winner == Some(user_a)).count() as i64;
+            let user_b_wins = match_games.iter().filter(|game| game.winner == Some(user_b)).count() as i64;
+            let winner = if user_a_wins > user_b_wins {
+                head_to_head.user_a_match_wins += 1;
+                Some(user_a)
+            } else if user_b_wins > user_a_wins {
+                head_to_head.user_b_match_wins += 1;
+                Some(user_b)
+            } else {
+                None
+            };
+            head_to_head.user_a_game_wins += user_a_wins;
+            head_to_head.user_b_game_wins += user_b_wins;
+
+            head_to_head.matches.push(HeadToHeadMatchModel {
+                match_model,
+                tournament,
+                user_a_wins,
+                user_b_wins,
+                winner,
+                games: match_games
+            });
+        }
+
+        Ok(head_to_head)
+    }
+}
+
+/// Condition on joined games, matches and tournaments built from filter.
+pub(crate) fn filter_condition(filter: &StatisticsFilter) -> Condition {
+    Condition::all()
+        .add(game_builder::Column::Result.ne(GameResult::NotSelected))
+        .add_option(filter.tournament_id.map(|id| match_structure::Column::TournamentId.eq(id)))
+        .add_option(filter.mod_type.map(|mod_type| tournament::Column::ModType.eq(mod_type)))
+        .add_option(filter.game_type.map(|game_type| tournament::Column::GameType.eq(game_type)))
+        .add_option(filter.stage.map(|stage| match_structure::Column::Stage.eq(stage)))
+        .add_option(filter.from.map(|from| game_builder::Column::CreatedAt.gte(from)))
+        .add_option(filter.to.map(|to| game_builder::Column::CreatedAt.lte(to)))
+}
+
+pub(crate) fn win_rate(wins: i64, games: i64) -> Option<f64> {
+    if games == 0 {
+        None
+    } else {
+        Some(wins as f64 / games as f64)
+    }
+}
+
+fn aggregate_matchups(rows: &[RaceResultRow]) -> MatchupsStatisticsModel {
+    let mut statistics = MatchupsStatisticsModel::default();
+    let mut matchups = BTreeMap::<(i32, i32), RaceMatchupModel>::new();
+
+    for row in rows {
+        let first_race = row.first_player_race.min(row.second_player_race);
+        let second_race = row.first_player_race.max(row.second_player_race);
+        let matchup = matchups.entry((first_race, second_race)).or_insert_with(|| RaceMatchupModel {
+            first_race,
+            second_race,
+            ..Default::default()
+        });
+
+        let winner = match row.result {
+            GameResult::FirstPlayerWon => {
+                matchup.first_player_wins += row.games;
+                statistics.first_player_wins += row.games;
+                row.first_player_race
+            },
+            _ => {
+                matchup.second_player_wins += row.games;
+                statistics.second_player_wins += row.games;
+                row.second_player_race
+            }
+        };
+
+        if row.first_player_race == first_race {
+            matchup.first_race_as_first_player += row.games;
+        }
+
+        if first_race != second_race {
+            if winner == first_race {
+                matchup.first_race_wins += row.games;
+            } else {
+                matchup.second_race_wins += row.games;
+            }
+        }
+
+        matchup.games += row.games;
+        statistics.total_games += row.games;
+    }
+
+    statistics.matchups = matchups.into_values()
+        .map(|mut matchup| {
+            if matchup.first_race != matchup.second_race {
+                matchup.first_race_win_rate = win_rate(matchup.first_race_wins, matchup.games);
+                matchup.second_race_win_rate = win_rate(matchup.second_race_wins, matchup.games);
+            }
+            matchup
+        })
+        .collect();
+
+    statistics
+}
+
+#[derive(Default)]
+struct HeroUsage {
+    picks: i64,
+    wins: i64,
+    foreign_picks: i64,
+    opponent_races: BTreeMap<i32, i64>
+}
+
+fn aggregate_heroes(
+    rows: &[HeroResultRow],
+    heroes: &HashMap<(ModId, i32), &HeroNew>,
+    foreign_heroes: Option<bool>
+) -> Vec<HeroStatisticsModel> {
+    let mut usages = HashMap::<(ModId, i32), HeroUsage>::new();
+
+    for row in rows {
+        let picks = [
+            (row.first_player_hero, row.first_player_race, row.second_player_race, row.result == GameResult::FirstPlayerWon),
+            (row.second_player_hero, row.second_player_race, row.first_player_race, row.result == GameResult::SecondPlayerWon)
+        ];
+        for (hero, race, opponent_race, won) in picks {
+            let foreign = heroes.get(&(row.mod_type, hero)).map(|hero| hero.race != race);
+            if foreign_heroes.is_some() && foreign != foreign_heroes {
+                continue;
+            }
+            let usage = usages.entry((row.mod_type, hero)).or_default();
+            usage.picks += row.games;
+            if won {
+                usage.wins += row.games;
+            }
+            if foreign == Some(true) {
+                usage.foreign_picks += row.games;
+            }
+            *usage.opponent_races.entry(opponent_race).or_default() += row.games;
+        }
+    }
+
+    let mut statistics = usages.into_iter()
+        .map(|((mod_type, hero), usage)| {
+            let catalogue_hero = heroes.get(&(mod_type, hero));
+            HeroStatisticsModel {
+                mod_type,
+                hero,
+                name: catalogue_hero.map(|hero| hero.name.clone()),
+                race: catalogue_hero.map(|hero| hero.race),
+                picks: usage.picks,
+                wins: usage.wins,
+                win_rate: win_rate(usage.wins, usage.picks),
+                foreign_picks: usage.foreign_picks,
+                // on equal counts race with lower id wins since max_by_key returns the last maximum
+                most_common_opponent_race: usage.opponent_races.iter()
+                    .rev()
+                    .max_by_key(|(_, games)| **games)
+                    .map(|(race, _)| *race)
+            }
+        })
+        .collect::<Vec<HeroStatisticsModel>>();
+
+    statistics.sort_by(|first, second| second.picks.cmp(&first.picks)
+        .then(first.mod_type.cmp(&second.mod_type))
+        .then(first.hero.cmp(&second.hero)));
+    statistics
+}
+
+/// Bargains amount and color are reported from the side of the first player.
+fn aggregate_bargains(rows: &[BargainsResultRow]) -> BargainsStatisticsModel {
+    let mut statistics = BargainsStatisticsModel::default();
+    let mut amounts = BTreeMap::<i32, BargainsAmountResultModel>::new();
+    let mut colors = BTreeMap::<i32, BargainsColorResultModel>::new();
+    let mut matchups = BTreeMap::<(i32, i32), (BargainsMatchupModel, BTreeMap<i32, i64>)>::new();
+    // weighted sums over first players: n, x, y, x^2, y^2, xy, where x is bargains amount and y is 1 for victory
+    let mut sums = [0f64; 6];
+
+    for row in rows {
+        let first_player_won = row.result == GameResult::FirstPlayerWon;
+        statistics.total_games += row.games;
+
+        for (amount, won) in [(row.bargains_amount, first_player_won), (-row.bargains_amount, !first_player_won)] {
+            let amount_result = amounts.entry(amount).or_insert_with(|| BargainsAmountResultModel { amount, ..Default::default() });
+            amount_result.sides += row.games;
+            if won {
+                amount_result.wins += row.games;
+            }
+        }
+
+        let (x, y, n) = (row.bargains_amount as f64, if first_player_won { 1.0 } else { 0.0 }, row.games as f64);
+        for (sum, value) in sums.iter_mut().zip([n, n * x, n * y, n * x * x, n * y * y, n * x * y]) {
+            *sum += value;
+        }
+
+        let first_player_color = match row.bargains_color {
+            Some(color) if row.with_bargains_color && color != BargainsColor::NotSelected => Some(color),
+            _ => None
+        };
+        if let Some(first_player_color) = first_player_color {
+            let second_player_color = if first_player_color == BargainsColor::BargainsColorRed {
+                BargainsColor::BargainsColorBlue
+            } else {
+                BargainsColor::BargainsColorRed
+            };
+            for (color, won) in [(first_player_color, first_player_won), (second_player_color, !first_player_won)] {
+                let color_result = colors.entry(color as i32).or_insert_with(|| BargainsColorResultModel { color, games: 0, wins: 0, win_rate: None });
+                color_result.games += row.games;
+                if won {
+                    color_result.wins += row.games;
+                }
+            }
+        }
+
+        let first_race = row.first_player_race.min(row.second_player_race);
+        let second_race = row.first_player_race.max(row.second_player_race);
+        let amount = if row.first_player_race == first_race { row.bargains_amount } else { -row.bargains_amount };
+        let (matchup, matchup_amounts) = matchups.entry((first_race, second_race)).or_insert_with(|| (
+            BargainsMatchupModel { first_race, second_race, min_amount: amount, max_amount: amount, ..Default::default() },
+            BTreeMap::new()
+        ));
+        matchup.games += row.games;
+        matchup.min_amount = matchup.min_amount.min(amount);
+        matchup.max_amount = matchup.max_amount.max(amount);
+        *matchup_amounts.entry(amount).or_default() += row.games;
+    }
+
+    let [n, sum_x, sum_y, sum_xx, sum_yy, sum_xy] = sums;
+    let denominator = ((n * sum_xx - sum_x * sum_x) * (n * sum_yy - sum_y * sum_y)).sqrt();
+    statistics.amount_result_correlation = if denominator > 0.0 {
+        Some((n * sum_xy - sum_x * sum_y) / denominator)
+    } else {
+        None
+    };
+
+    statistics.amounts = amounts.into_values()
+        .map(|mut amount| {
+            amount.win_rate = win_rate(amount.wins, amount.sides);
+            amount
+        })
+        .collect();
+
+    statistics.colors = colors.into_values()
+        .map(|mut color| {
+            color.win_rate = win_rate(color.wins, color.games);
+            color
+        })
+        .collect();
+
+    statistics.matchups = matchups.into_values()
+        .map(|(mut matchup, matchup_amounts)| {
+            let total = matchup_amounts.iter().map(|(amount, games)| *amount as i64 * games).sum::<i64>();
+            matchup.average_amount = Some(total as f64 / matchup.games as f64);
+            matchup.amounts = matchup_amounts.into_iter()
+                .map(|(amount, games)| BargainsAmountCountModel { amount, games })
+                .collect();
+            matchup
+        })
+        .collect();
+
+    statistics
+}
+
+/// Single game seen from the side of a player whose profile is built.
+struct PlayerGame {
+    won: bool,
+    race: Option<i32>,
+    hero: Option<i32>,
+    bargains_amount: Option<i32>,
+    bargains_color: Option<BargainsColor>
+}
+
+fn player_game(game: &game_builder::Model, is_first_player: bool) -> PlayerGame {
+    if is_first_player {
+        PlayerGame {
+            won: game.result == GameResult::FirstPlayerWon,
+            race: game.first_player_race,
+            hero: game.first_player_hero,
+            bargains_amount: game.bargains_amount,
+            bargains_color: game.bargains_color
+        }
+    } else {
+        PlayerGame {
+            won: game.result == GameResult::SecondPlayerWon,
+            race: game.second_player_race,
+            hero: game.second_player_hero,
+            bargains_amount: game.bargains_amount.map(|amount| -amount),
+            bargains_color: match game.bargains_color {
+                Some(BargainsColor::BargainsColorRed) => Some(BargainsColor::BargainsColorBlue),
+                Some(BargainsColor::BargainsColorBlue) => Some(BargainsColor::BargainsColorRed),
+                color => color
+            }
+        }
+    }
+}
+
+fn aggregate_profile(
+    user: user::Model,
+    participants: &[participant::Model],
+    tournaments: &[tournament::Model],
+    matches: &[match_structure::Model],
+    games: &[game_builder::Model]
+) -> PlayerProfileModel {
+    let tournaments_by_id = tournaments.iter()
+        .map(|tournament| (tournament.id, tournament))
+        .collect::<HashMap<Uuid, &tournament::Model>>();
+    let mut games_by_match = HashMap::<Uuid, Vec<&game_builder::Model>>::new();
+    for game in games {
+        games_by_match.entry(game.match_id).or_default().push(game);
+    }
+
+    let mut tournament_results = tournaments.iter()
+        .map(|tournament| (tournament.id, PlayerTournamentModel {
+            tournament: tournament.clone(),
+            group: participants.iter()
+                .find(|participant| participant.tournament_id == tournament.id)
+                .map(|participant| participant.group_number),
+            matches: 0,
+            match_wins: 0,
+            match_losses: 0,
+            games: 0,
+            game_wins: 0
+        }))
+        .collect::<HashMap<Uuid, PlayerTournamentModel>>();
+    let mut races = BTreeMap::<i32, (i64, i64)>::new();
+    let mut heroes = BTreeMap::<(ModId, i32), (ModId, i64, i64)>::new();
+    let mut colors = BTreeMap::<i32, BargainsColorResultModel>::new();
+    let mut bargains = PlayerBargainsModel::default();
+    let mut bargains_total = 0i64;
+    let mut reports = vec![];
+
+    for match_model in matches {
+        let tournament = tournaments_by_id.get(&match_model.tournament_id);
+        // matches without reported games aren't played yet
+        let Some(match_games) = games_by_match.get(&match_model.id) else {
+            continue;
+        };
+        let is_first_player = match_model.first_player == user.id;
+        let mut wins = 0;
+        let mut losses = 0;
+
+        for game in match_games {
+            let player_game = player_game(game, is_first_player);
+            if player_game.won {
+                wins += 1;
+            } else {
+                losses += 1;
+            }
+
+            if let Some(race) = player_game.race {
+                let race_result = races.entry(race).or_default();
+                race_result.0 += 1;
+                race_result.1 += player_game.won as i64;
+            }
+
+            if let (Some(hero), Some(tournament)) = (player_game.hero, tournament) {
+                let hero_result = heroes.entry((tournament.mod_type, hero)).or_insert((tournament.mod_type, 0, 0));
+                hero_result.1 += 1;
+                hero_result.2 += player_game.won as i64;
+            }
+
+            if let (Some(amount), Some(tournament)) = (player_game.bargains_amount, tournament.filter(|tournament| tournament.with_bargains)) {
+                bargains.games += 1;
+                bargains_total += amount as i64;
+                bargains.min_amount = Some(bargains.min_amount.map_or(amount, |min_amount| min_amount.min(amount)));
+                bargains.max_amount = Some(bargains.max_amount.map_or(amount, |max_amount| max_amount.max(amount)));
+
+                match player_game.bargains_color {
+                    Some(color) if tournament.with_bargains_color && color != BargainsColor::NotSelected => {
+                        let color_result = colors.entry(color as i32).or_insert_with(|| BargainsColorResultModel { color, games: 0, wins: 0, win_rate: None });
+                        color_result.games += 1;
+                        color_result.wins += player_game.won as i64;
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(tournament_result) = tournament_results.get_mut(&match_model.tournament_id) {
+            tournament_result.matches += 1;
+            tournament_result.games += wins + losses;
+            tournament_result.game_wins += wins;
+            if wins > losses {
+                tournament_result.match_wins += 1;
+            } else if losses > wins {
+                tournament_result.match_losses += 1;
+            }
+        }
+
+        if let Some(report_link) = &match_model.report_link {
+            reports.push(PlayerReportModel {
+                match_id: match_model.id,
+                tournament_id: match_model.tournament_id,
+                report_link: report_link.clone()
+            });
+        }
+    }
+
+    if bargains.games > 0 {
+        bargains.average_amount = Some(bargains_total as f64 / bargains.games as f64);
+    }
+    bargains.colors = colors.into_values()
+        .map(|mut color| {
+            color.win_rate = win_rate(color.wins, color.games);
+            color
+        })
+        .collect();
+
+    let mut races = races.into_iter()
+        .map(|(race, (games, wins))| PlayerRaceModel { race, games, wins, win_rate: win_rate(wins, games) })
+        .collect::<Vec<PlayerRaceModel>>();
+    races.sort_by_key(|race| std::cmp::Reverse(race.games));
+
+    let mut heroes = heroes.into_iter()
+        .map(|((_, hero), (mod_type, games, wins))| PlayerHeroModel { mod_type, hero, games, wins, win_rate: win_rate(wins, games) })
+        .collect::<Vec<PlayerHeroModel>>();
+    heroes.sort_by_key(|hero| std::cmp::Reverse(hero.games));
+
+    // tournaments are already ordered by name
+    let tournaments = tournaments.iter()
+        .filter_map(|tournament| tournament_results.remove(&tournament.id))
+        .collect::<Vec<PlayerTournamentModel>>();
+
+    let games = tournaments.iter().map(|tournament| tournament.games).sum::<i64>();
+    let game_wins = tournaments.iter().map(|tournament| tournament.game_wins).sum::<i64>();
+
+    PlayerProfileModel {
+        user,
+        matches: tournaments.iter().map(|tournament| tournament.matches).sum(),
+        match_wins: tournaments.iter().map(|tournament| tournament.match_wins).sum(),
+        match_losses: tournaments.iter().map(|tournament| tournament.match_losses).sum(),
+        games,
+        game_wins,
+        game_win_rate: win_rate(game_wins, games),
+        tournaments,
+        races,
+        heroes,
+        bargains,
+        reports
+    }
+}
@@ -0,0 +1,251 @@
+use sea_orm::{prelude::DateTimeWithTimeZone, FromQueryResult};
+use uuid::Uuid;
+
+use crate::{prelude::ModId, services::tournament::models::{game_builder::{BargainsColor, GameOutcome, GameResult}, match_structure::MatchModel, tournament::{GameType, TournamentModel, TournamentStage}, user::UserModel}};
+
+/// Narrows set of games statistics is collected from. Empty filter means all reported games.
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct StatisticsFilter {
+    pub tournament_id: Option<Uuid>,
+    pub mod_type: Option<ModId>,
+    pub game_type: Option<GameType>,
+    /// Stage of tournament matches of games were played at
+    pub stage: Option<TournamentStage>,
+    /// Only games reported at this moment or later. Games without known date are skipped
+    pub from: Option<DateTimeWithTimeZone>,
+    /// Only games reported at this moment or earlier. Games without known date are skipped
+    pub to: Option<DateTimeWithTimeZone>
+}
+
+/// Count of games with the same races and result.
+#[derive(Debug, FromQueryResult)]
+pub(crate) struct RaceResultRow {
+    pub first_player_race: i32,
+    pub second_player_race: i32,
+    pub result: GameResult,
+    pub games: i64
+}
+
+/// Results of games between two races. Races are ordered so `first_race <= second_race`.
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct RaceMatchupModel {
+    pub first_race: i32,
+    pub second_race: i32,
+    pub games: i64,
+    /// Wins of first race, always 0 for mirror matchups
+    pub first_race_wins: i64,
+    /// Wins of second race, always 0 for mirror matchups
+    pub second_race_wins: i64,
+    /// Null for mirror matchups
+    pub first_race_win_rate: Option<f64>,
+    /// Null for mirror matchups
+    pub second_race_win_rate: Option<f64>,
+    /// Games where first race was picked by first player
+    pub first_race_as_first_player: i64,
+    pub first_player_wins: i64,
+    pub second_player_wins: i64
+}
+
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct MatchupsStatisticsModel {
+    pub total_games: i64,
+    pub first_player_wins: i64,
+    pub second_player_wins: i64,
+    pub matchups: Vec<RaceMatchupModel>
+}
+
+/// Count of games with the same heroes, races and result in a single mod.
+#[derive(Debug, FromQueryResult)]
+pub(crate) struct HeroResultRow {
+    pub mod_type: ModId,
+    pub first_player_race: i32,
+    pub first_player_hero: i32,
+    pub second_player_race: i32,
+    pub second_player_hero: i32,
+    pub result: GameResult,
+    pub games: i64
+}
+
+/// Usage of a single hero of a mod's catalogue.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct HeroStatisticsModel {
+    pub mod_type: ModId,
+    pub hero: i32,
+    /// Null if hero is missing in the mod's catalogue
+    pub name: Option<String>,
+    /// Null if hero is missing in the mod's catalogue
+    pub race: Option<i32>,
+    pub picks: i64,
+    pub wins: i64,
+    pub win_rate: Option<f64>,
+    /// Picks by players whose race differs from the hero's one
+    pub foreign_picks: i64,
+    pub most_common_opponent_race: Option<i32>
+}
+
+/// Count of games with the same races, bargains and result.
+#[derive(Debug, FromQueryResult)]
+pub(crate) struct BargainsResultRow {
+    pub first_player_race: i32,
+    pub second_player_race: i32,
+    pub bargains_amount: i32,
+    pub bargains_color: Option<BargainsColor>,
+    pub with_bargains_color: bool,
+    pub result: GameResult,
+    pub games: i64
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct BargainsAmountCountModel {
+    pub amount: i32,
+    pub games: i64
+}
+
+/// Bargains made in games between two races. Amounts are given from the side of first race
+/// (from the side of first player in mirror matchups), races are ordered so `first_race <= second_race`.
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct BargainsMatchupModel {
+    pub first_race: i32,
+    pub second_race: i32,
+    pub games: i64,
+    pub average_amount: Option<f64>,
+    pub min_amount: i32,
+    pub max_amount: i32,
+    pub amounts: Vec<BargainsAmountCountModel>
+}
+
+/// Results of players who played with the same bargains amount.
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct BargainsAmountResultModel {
+    pub amount: i32,
+    /// Every game is counted for both players, so a game with zero bargains adds 2 sides to the 0 amount
+    pub sides: i64,
+    pub wins: i64,
+    pub win_rate: Option<f64>
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct BargainsColorResultModel {
+    pub color: BargainsColor,
+    pub games: i64,
+    pub wins: i64,
+    pub win_rate: Option<f64>
+}
+
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct BargainsStatisticsModel {
+    /// Games of tournaments with bargains that have bargains amount reported
+    pub total_games: i64,
+    /// Pearson correlation between bargains amount of a player and their victory, null if it can't be computed
+    pub amount_result_correlation: Option<f64>,
+    pub amounts: Vec<BargainsAmountResultModel>,
+    /// Only tournaments with bargains colors are counted here
+    pub colors: Vec<BargainsColorResultModel>,
+    pub matchups: Vec<BargainsMatchupModel>
+}
+
+/// Results of a player in a single tournament.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerTournamentModel {
+    pub tournament: TournamentModel,
+    /// Null if player has matches in tournament but isn't its participant anymore
+    pub group: Option<i32>,
+    pub matches: i64,
+    pub match_wins: i64,
+    pub match_losses: i64,
+    pub games: i64,
+    pub game_wins: i64
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerRaceModel {
+    pub race: i32,
+    pub games: i64,
+    pub wins: i64,
+    pub win_rate: Option<f64>
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerHeroModel {
+    pub mod_type: ModId,
+    pub hero: i32,
+    pub games: i64,
+    pub wins: i64,
+    pub win_rate: Option<f64>
+}
+
+/// Bargains of a player, amounts and colors are given from the player's side.
+#[derive(Debug, Default, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerBargainsModel {
+    pub games: i64,
+    pub average_amount: Option<f64>,
+    pub min_amount: Option<i32>,
+    pub max_amount: Option<i32>,
+    /// Only tournaments with bargains colors are counted here
+    pub colors: Vec<BargainsColorResultModel>
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerReportModel {
+    pub match_id: Uuid,
+    pub tournament_id: Uuid,
+    pub report_link: String
+}
+
+/// Historical results of a player across all tournaments.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct PlayerProfileModel {
+    pub user: UserModel,
+    pub matches: i64,
+    /// Match is won by player who won more of its games. Matches with equal count of wins are counted as neither won nor lost
+    pub match_wins: i64,
+    pub match_losses: i64,
+    pub games: i64,
+    pub game_wins: i64,
+    pub game_win_rate: Option<f64>,
+    pub tournaments: Vec<PlayerTournamentModel>,
+    /// Sorted by games played
+    pub races: Vec<PlayerRaceModel>,
+    /// Sorted by games played
+    pub heroes: Vec<PlayerHeroModel>,
+    pub bargains: PlayerBargainsModel,
+    pub reports: Vec<PlayerReportModel>
+}
+
+/// Game of head to head match seen from the side of the first requested user.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct HeadToHeadGameModel {
+    pub id: Uuid,
+    pub user_a_race: Option<i32>,
+    pub user_a_hero: Option<i32>,
+    pub user_b_race: Option<i32>,
+    pub user_b_hero: Option<i32>,
+    /// Null if result wasn't reported yet
+    pub winner: Option<Uuid>,
+    pub outcome: GameOutcome,
+    pub created_at: Option<DateTimeWithTimeZone>
+}
+
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct HeadToHeadMatchModel {
+    #[graphql(name = "match")]
+    pub match_model: MatchModel,
+    pub tournament: TournamentModel,
+    pub user_a_wins: i64,
+    pub user_b_wins: i64,
+    /// Null if both users won the same count of games
+    pub winner: Option<Uuid>,
+    pub games: Vec<HeadToHeadGameModel>
+}
+
+/// All matches played between two users, ordered from oldest to newest.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct HeadToHeadModel {
+    pub user_a: UserModel,
+    pub user_b: UserModel,
+    pub user_a_match_wins: i64,
+    pub user_b_match_wins: i64,
+    pub user_a_game_wins: i64,
+    pub user_b_game_wins: i64,
+    pub matches: Vec<HeadToHeadMatchModel>
+}
@@ -0,0 +1,5 @@
+mod pairing;
+mod service;
+pub(crate) mod prelude;
+pub mod models;
+pub mod types;
\ No newline at end of file
@@ -0,0 +1 @@
+pub(crate) mod swiss_pairing;
\ No newline at end of file
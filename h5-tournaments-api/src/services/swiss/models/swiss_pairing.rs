@@ -0,0 +1,80 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
+use sea_orm::prelude::*;
+
+use crate::services::tournament::{loaders::MatchLoader, models::match_structure::MatchModel};
+
+pub type SwissPairingModel = Model;
+
+/// Pair of players of swiss round. Pairing without second player is a bye, it counts as a won match.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "swiss_pairings")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub round: i32,
+    // Boards are numbered from 1 in order of players' standings, bye always takes the last one
+    pub board: i32,
+    pub first_player: Uuid,
+    pub second_player: Option<Uuid>,
+    pub match_id: Option<Uuid>
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Tournament
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Tournament => Entity::belongs_to(crate::services::tournament::models::tournament::Entity)
+                .from(Column::TournamentId)
+                .to(crate::services::tournament::models::tournament::Column::Id)
+                .into()
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl SwissPairingModel {
+    async fn id(&self) -> Uuid {
+        self.id
+    }
+
+    async fn tournament(&self) -> Uuid {
+        self.tournament_id
+    }
+
+    async fn round(&self) -> i32 {
+        self.round
+    }
+
+    async fn board(&self) -> i32 {
+        self.board
+    }
+
+    async fn first_player(&self) -> Uuid {
+        self.first_player
+    }
+
+    /// Null if first player got a bye
+    async fn second_player(&self) -> Option<Uuid> {
+        self.second_player
+    }
+
+    /// Tournament match, null for byes
+    async fn match_id(&self) -> Option<Uuid> {
+        self.match_id
+    }
+
+    async fn match_data<'a>(&self, context: &Context<'a>) -> Result<Option<MatchModel>, async_graphql::Error> {
+        let Some(match_id) = self.match_id else {
+            return Ok(None);
+        };
+        let loader = context.data::<DataLoader<MatchLoader>>()?;
+        loader.load_one(match_id).await.map_err(|error| error.extend())
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::services::standings::ranking::MatchResult;
+
+/// Totals of player over played swiss rounds. Points are doubled so draws stay integral,
+/// won match and bye give 2 points and draw gives 1.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct SwissRecord {
+    pub points: i32,
+    pub matches: i32,
+    pub match_wins: i32,
+    pub match_draws: i32,
+    pub match_losses: i32,
+    pub byes: i32,
+    // Opponents with doubled points player got against them
+    pub opponents: Vec<(Uuid, i32)>
+}
+
+impl SwissRecord {
+    fn played(&self, opponent: Uuid) -> bool {
+        self.opponents.iter().any(|(played, _)| *played == opponent)
+    }
+}
+
+/// Records of given players, results and byes of anyone else are skipped.
+pub(crate) fn records(players: &[Uuid], results: &[MatchResult], byes: &[Uuid]) -> HashMap<Uuid, SwissRecord> {
+    let mut records = players.iter()
+        .map(|player| (*player, SwissRecord::default()))
+        .collect::<HashMap<Uuid, SwissRecord>>();
+    for result in results {
+        for (player, opponent) in [(result.first_player, result.second_player), (result.second_player, result.first_player)] {
+            let (Some(record), Some((wins, losses))) = (records.get_mut(&player), result.score_of(player)) else {
+                continue;
+            };
+            let points = match wins.cmp(&losses) {
+                std::cmp::Ordering::Greater => {
+                    record.match_wins += 1;
+                    2
+                },
+                std::cmp::Ordering::Equal => {
+                    record.match_draws += 1;
+                    1
+                },
+                std::cmp::Ordering::Less => {
+                    record.match_losses += 1;
+                    0
+                }
+            };
+            record.matches += 1;
+            record.points += points;
+            record.opponents.push((opponent, points));
+        }
+    }
+    for player in byes {
+        if let Some(record) = records.get_mut(player) {
+            record.byes += 1;
+            record.points += 2;
+        }
+    }
+    records
+}
+
+/// Buchholz and Sonneborn-Berger of player in real points, opponents without record count with no points.
+pub(crate) fn tiebreaks(player: Uuid, records: &HashMap<Uuid, SwissRecord>) -> (f64, f64) {
+    let (mut buchholz, mut sonneborn_berger) = (0.0, 0.0);
+    for (opponent, points) in &records[&player].opponents {
+        let opponent_points = records.get(opponent).map_or(0, |record| record.points) as f64 / 2.0;
+        buchholz += opponent_points;
+        sonneborn_berger += opponent_points * *points as f64 / 2.0;
+    }
+    (buchholz, sonneborn_berger)
+}
+
+/// Orders players by points, Buchholz and Sonneborn-Berger, players equal in all of them keep the order they came in.
+pub(crate) fn order(players: &[Uuid], records: &HashMap<Uuid, SwissRecord>) -> Vec<Uuid> {
+    let tiebreaks = players.iter()
+        .map(|player| (*player, tiebreaks(*player, records)))
+        .collect::<HashMap<Uuid, (f64, f64)>>();
+    let mut ordered = players.to_vec();
+    ordered.sort_by(|first, second| {
+        let (first_buchholz, first_sonneborn_berger) = tiebreaks[first];
+        let (second_buchholz, second_sonneborn_berger) = tiebreaks[second];
+        records[second].points.cmp(&records[first].points)
+            .then(second_buchholz.total_cmp(&first_buchholz))
+            .then(second_sonneborn_berger.total_cmp(&first_sonneborn_berger))
+    });
+    ordered
+}
+
+/// Pairs of the next round in order of boards.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RoundPairing {
+    pub pairs: Vec<(Uuid, Uuid)>,
+    pub bye: Option<Uuid>
+}
+
+/// Pairs players of the next round, players must be ordered by standings and nobody is paired against the same opponent twice.
+/// If count of players is odd, the lowest placed player with the fewest byes gets one. None if there is no pairing without rematches.
+pub(crate) fn pair(players: &[Uuid], records: &HashMap<Uuid, SwissRecord>) -> Option<RoundPairing> {
+    if players.len().is_multiple_of(2) {
+        return pair_players(players, records).map(|pairs| RoundPairing { pairs, bye: None });
+    }
+
+    let mut candidates = players.iter().rev().copied().collect::<Vec<Uuid>>();
+    candidates.sort_by_key(|player| records[player].byes);
+    for bye in candidates {
+        let rest = players.iter().copied().filter(|player| *player != bye).collect::<Vec<Uuid>>();
+        if let Some(pairs) = pair_players(&rest, records) {
+            return Some(RoundPairing { pairs, bye: Some(bye) });
+        }
+    }
+    None
+}
+
+/// The highest placed player prefers the top of bottom half of its score group, then the rest of the group
+/// from the bottom of top half and lower score groups after them. Opponent is only taken if the rest of players
+/// can still be paired without rematches, so this gives the same pairs as trying every pairing in order of preference.
+fn pair_players(players: &[Uuid], records: &HashMap<Uuid, SwissRecord>) -> Option<Vec<(Uuid, Uuid)>> {
+    let mut matching = Matching::new(players.len(), |first, second| !records[&players[first]].played(players[second]));
+    if !matching.complete() {
+        return None;
+    }
+
+    let mut remaining = (0..players.len()).collect::<Vec<usize>>();
+    let mut pairs = vec![];
+    while let Some((&player, rest)) = remaining.split_first() {
+        let points = records[&players[player]].points;
+        let group_size = rest.iter().take_while(|other| records[&players[**other]].points == points).count();
+        let ideal = group_size.div_ceil(2).saturating_sub(1);
+
+        let opponent = rest[ideal..group_size].iter()
+            .chain(rest[..ideal].iter().rev())
+            .chain(rest[group_size..].iter())
+            .copied()
+            .find(|opponent| matching.try_pair(player, *opponent))?;
+        pairs.push((players[player], players[opponent]));
+        remaining.retain(|other| *other != player && *other != opponent);
+    }
+    Some(pairs)
+}
+
+/// Perfect matching of players that haven't played each other, kept up to date while pairs are taken out of it.
+/// Edmonds' blossom algorithm, every augmentation takes O(n^2).
+struct Matching {
+    allowed: Vec<Vec<bool>>,
+    active: Vec<bool>,
+    mate: Vec<Option<usize>>
+}
+
+impl Matching {
+    fn new(size: usize, allowed: impl Fn(usize, usize) -> bool) -> Self {
+        Matching {
+            allowed: (0..size).map(|first| (0..size).map(|second| first != second && allowed(first, second)).collect()).collect(),
+            active: vec![true; size],
+            mate: vec![None; size]
+        }
+    }
+
+    /// Matches every active vertex if it's possible.
+    fn complete(&mut self) -> bool {
+        for root in 0..self.mate.len() {
+            if self.active[root] && self.mate[root].is_none() && !self.augment(root) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Takes pair out of matching if the rest of vertices can still be matched, keeps matching unchanged otherwise.
+    fn try_pair(&mut self, first: usize, second: usize) -> bool {
+        if !self.allowed[first][second] {
+            return false;
+        }
+        let (active, mate) = (self.active.clone(), self.mate.clone());
+        for vertex in [first, second] {
+            self.active[vertex] = false;
+            if let Some(other) = self.mate[vertex].take() {
+                self.mate[other] = None;
+            }
+        }
+        if self.complete() {
+            return true;
+        }
+        self.active = active;
+        self.mate = mate;
+        false
+    }
+
+    /// Searches for augmenting path from unmatched root and flips it, odd cycles are contracted into their bases.
+    fn augment(&mut self, root: usize) -> bool {
+        let size = self.mate.len();
+        let mut used = vec![false; size];
+        let mut parent: Vec<Option<usize>> = vec![None; size];
+        let mut base = (0..size).collect::<Vec<usize>>();
+        used[root] = true;
+        let mut queue = std::collections::VecDeque::from([root]);
+
+        while let Some(vertex) = queue.pop_front() {
+            for next in 0..size {
+                if !self.active[next] || !self.allowed[vertex][next] || base[vertex] == base[next] || self.mate[vertex] == Some(next) {
+                    continue;
+                }
+                if next == root || self.mate[next].is_some_and(|mate| parent[mate].is_some()) {
+                    let current_base = self.common_base(&base, &parent, vertex, next);
+                    let mut blossom = vec![false; size];
+                    self.mark_path(&base, &mut parent, &mut blossom, vertex, current_base, next);
+                    self.mark_path(&base, &mut parent, &mut blossom, next, current_base, vertex);
+                    for other in 0..size {
+                        if blossom[base[other]] {
+                            base[other] = current_base;
+                            if !used[other] {
+                                used[other] = true;
+                                queue.push_back(other);
+                            }
+                        }
+                    }
+                } else if parent[next].is_none() {
+                    parent[next] = Some(vertex);
+                    let Some(mate) = self.mate[next] else {
+                        self.flip(&parent, next);
+                        return true;
+                    };
+                    used[mate] = true;
+                    queue.push_back(mate);
+                }
+            }
+        }
+        false
+    }
+
+    fn common_base(&self, base: &[usize], parent: &[Option<usize>], mut first: usize, mut second: usize) -> usize {
+        let mut visited = vec![false; base.len()];
+        loop {
+            first = base[first];
+            visited[first] = true;
+            match self.mate[first] {
+                Some(mate) => first = parent[mate].unwrap(),
+                None => break
+            }
+        }
+        loop {
+            second = base[second];
+            if visited[second] {
+                return second;
+            }
+            second = parent[self.mate[second].unwrap()].unwrap();
+        }
+    }
+
+    fn mark_path(&self, base: &[usize], parent: &mut [Option<usize>], blossom: &mut [bool], mut vertex: usize, current_base: usize, mut child: usize) {
+        while base[vertex] != current_base {
+            let mate = self.mate[vertex].unwrap();
+            blossom[base[vertex]] = true;
+            blossom[base[mate]] = true;
+            parent[vertex] = Some(child);
+            child = mate;
+            vertex = parent[mate].unwrap();
+        }
+    }
+
+    fn flip(&mut self, parent: &[Option<usize>], mut vertex: usize) {
+        loop {
+            let previous = parent[vertex].unwrap();
+            let next = self.mate[previous];
+            self.mate[vertex] = Some(previous);
+            self.mate[previous] = Some(vertex);
+            match next {
+                Some(next) => vertex = next,
+                None => break
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::IndexedRandom, Rng, SeedableRng};
+
+    use super::*;
+
+    fn player(index: u128) -> Uuid {
+        Uuid::from_u128(index)
+    }
+
+    fn result(first: u128, second: u128, first_wins: i32, second_wins: i32) -> MatchResult {
+        MatchResult {
+            first_player: player(first),
+            second_player: player(second),
+            first_player_wins: first_wins,
+            second_player_wins: second_wins
+        }
+    }
+
+    /// Pairing by trying every pairing in order of preference.
+    fn pair_exhaustively(players: &[Uuid], records: &HashMap<Uuid, SwissRecord>) -> Option<Vec<(Uuid, Uuid)>> {
+        let Some((&player, rest)) = players.split_first() else {
+            return Some(vec![]);
+        };
+        let record = &records[&player];
+        let group_size = rest.iter().take_while(|other| records[*other].points == record.points).count();
+        let ideal = group_size.div_ceil(2).saturating_sub(1);
+        for opponent in rest[ideal..group_size].iter().chain(rest[..ideal].iter().rev()).chain(rest[group_size..].iter()).copied() {
+            if record.played(opponent) {
+                continue;
+            }
+            let remaining = rest.iter().copied().filter(|other| *other != opponent).collect::<Vec<Uuid>>();
+            if let Some(mut pairs) = pair_exhaustively(&remaining, records) {
+                pairs.insert(0, (player, opponent));
+                return Some(pairs);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn tiebreaks_sum_opponent_points() {
+        // 1 beat 2 and drew with 3, 2 beat 3, 4 got a bye and lost to 3
+        let players = (1..=4).map(player).collect::<Vec<Uuid>>();
+        let results = [result(1, 2, 2, 0), result(1, 3, 1, 1), result(2, 3, 2, 1), result(3, 4, 2, 0)];
+        let records = super::records(&players, &results, &[player(4)]);
+        assert_eq!(records[&player(1)].points, 3);
+        assert_eq!(records[&player(3)].points, 3);
+        assert_eq!(records[&player(4)].points, 2);
+        assert_eq!(records[&player(4)].byes, 1);
+
+        // opponents of 1 have 1 and 1.5 points, the win over 2 counts fully and the draw with 3 counts by half
+        assert_eq!(tiebreaks(player(1), &records), (2.5, 1.0 + 0.75));
+        assert_eq!(tiebreaks(player(3), &records), (1.5 + 1.0 + 1.0, 0.75 + 1.0));
+        assert_eq!(tiebreaks(player(4), &records), (1.5, 0.0));
+        assert_eq!(order(&players, &records), vec![player(3), player(1), player(2), player(4)]);
+    }
+
+    #[test]
+    fn rematches_are_avoided() {
+        // 1 and 2 won their first round matches against 3 and 4
+        let players = (1..=4).map(player).collect::<Vec<Uuid>>();
+        let results = [result(1, 3, 2, 0), result(2, 4, 2, 0)];
+        let records = super::records(&players, &results, &[]);
+        let pairing = pair(&players, &records).unwrap();
+        assert_eq!(pairing, RoundPairing { pairs: vec![(player(1), player(2)), (player(3), player(4))], bye: None });
+
+        // winners already met as well, so 1 has to go down to the other score group
+        let results = [result(1, 3, 2, 0), result(2, 4, 2, 0), result(1, 2, 2, 0), result(3, 4, 2, 0)];
+        let records = super::records(&players, &results, &[]);
+        let pairing = pair(&order(&players, &records), &records).unwrap();
+        assert_eq!(pairing.pairs, vec![(player(1), player(4)), (player(2), player(3))]);
+
+        let results = [results[0], results[1], results[2], results[3], result(1, 4, 2, 0), result(2, 3, 2, 0)];
+        let records = super::records(&players, &results, &[]);
+        assert_eq!(pair(&players, &records), None);
+    }
+
+    #[test]
+    fn lowest_player_without_bye_sits_out() {
+        let players = (1..=5).map(player).collect::<Vec<Uuid>>();
+        let records = super::records(&players, &[], &[]);
+        assert_eq!(pair(&players, &records).unwrap().bye, Some(player(5)));
+
+        let records = super::records(&players, &[result(1, 2, 2, 0), result(3, 4, 2, 0)], &[player(5)]);
+        let pairing = pair(&players, &records).unwrap();
+        assert_eq!(pairing.bye, Some(player(4)));
+        assert_eq!(pairing.pairs.len(), 2);
+        assert!(pairing.pairs.iter().all(|(first, second)| !records[first].played(*second)));
+    }
+
+    #[test]
+    fn pairs_match_exhaustive_search() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..300 {
+            let count = rng.random_range(2..=10);
+            let players = (1..=count as u128).map(player).collect::<Vec<Uuid>>();
+            let mut results = vec![];
+            for _ in 0..rng.random_range(0..count * 2) {
+                let mut pair = players.choose_multiple(&mut rng, 2);
+                let (first, second) = (*pair.next().unwrap(), *pair.next().unwrap());
+                results.push(MatchResult { first_player: first, second_player: second, first_player_wins: rng.random_range(0..3), second_player_wins: rng.random_range(0..3) });
+            }
+            let records = super::records(&players, &results, &[]);
+            let ordered = order(&players, &records);
+            let players = if count % 2 == 0 { ordered } else { ordered[1..].to_vec() };
+            assert_eq!(pair_players(&players, &records), pair_exhaustively(&players, &records));
+        }
+    }
+
+    #[test]
+    fn large_rounds_are_paired() {
+        let players = (1..=256).map(player).collect::<Vec<Uuid>>();
+        let mut results = vec![];
+        for round in 1..=8 {
+            let records = super::records(&players, &results, &[]);
+            let pairing = pair(&order(&players, &records), &records).unwrap();
+            assert_eq!(pairing.pairs.len(), 128, "round {}", round);
+            results.extend(pairing.pairs.into_iter().map(|(first, second)| MatchResult { first_player: first, second_player: second, first_player_wins: 2, second_player_wins: 1 }));
+        }
+    }
+}
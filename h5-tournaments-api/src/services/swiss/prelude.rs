@@ -0,0 +1,3 @@
+pub use super::service::SwissService;
+pub use super::models::swiss_pairing::SwissPairingModel;
+pub use super::types::SwissStandingsEntryModel;
\ No newline at end of file
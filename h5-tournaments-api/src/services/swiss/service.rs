@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{prelude::DateTimeWithTimeZone, ActiveModelTrait, ActiveValue::{NotSet, Set}, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait};
+use uuid::Uuid;
+
+use crate::services::{rating::seeding_ratings, standings::ranking::MatchResult, tournament::{error::Error, models::{game_builder::{self, GameResult}, match_structure, participant, tournament::{self, TournamentModel}, user}}};
+
+use super::{models::swiss_pairing::{self, SwissPairingModel}, pairing, types::SwissStandingsEntryModel};
+
+#[derive(Clone)]
+pub struct SwissService;
+
+/// Everything played in swiss rounds of tournament so far.
+struct SwissRounds {
+    pairings: Vec<SwissPairingModel>,
+    results: Vec<MatchResult>,
+    byes: Vec<Uuid>,
+    // Matches of pairings without reported games
+    unplayed: usize
+}
+
+impl SwissService {
+    pub async fn get_pairings(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        round: Option<i32>
+    ) -> Result<Vec<SwissPairingModel>, Error> {
+        let mut query = swiss_pairing::Entity::find()
+            .filter(swiss_pairing::Column::TournamentId.eq(tournament_id));
+        if let Some(round) = round {
+            query = query.filter(swiss_pairing::Column::Round.eq(round));
+        }
+        Ok(query
+            .order_by_asc(swiss_pairing::Column::Round)
+            .order_by_asc(swiss_pairing::Column::Board)
+            .all(db)
+            .await?)
+    }
+
+    /// Pairs the next swiss round of all tournament participants and creates its matches.
+    /// Every match of previous round must have a reported game, matches deleted by organizer are considered not played.
+    pub async fn pair_next_round(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        deadline: Option<DateTimeWithTimeZone>
+    ) -> Result<Vec<SwissPairingModel>, Error> {
+        let transaction = db.begin().await?;
+        let tournament = tournament::Entity::find_by_id(tournament_id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let rounds = load_rounds(&transaction, tournament_id).await?;
+        let round = rounds.pairings.last().map_or(0, |pairing| pairing.round) + 1;
+        if rounds.unplayed > 0 {
+            return Err(Error::Validation(format!("{} matches of round {} aren't played yet", rounds.unplayed, round - 1)));
+        }
+
+        let participants = participant::Entity::find()
+            .select_only()
+            .column(participant::Column::UserId)
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .into_tuple::<Uuid>()
+            .all(&transaction)
+            .await?;
+        if participants.len() < 2 {
+            return Err(Error::Validation(format!("At least 2 participants are required for swiss rounds, got {}", participants.len())));
+        }
+        // nobody can avoid a rematch once everyone played everyone else
+        if round as usize > participants.len() - 1 + participants.len() % 2 {
+            return Err(Error::Validation(format!("All {} rounds possible with {} participants are already played", round - 1, participants.len())));
+        }
+
+        let players = seeded(&transaction, &tournament, participants).await?;
+        let records = pairing::records(&players, &rounds.results, &rounds.byes);
+        let standings = pairing::order(&players, &records);
+        let round_pairing = pairing::pair(&standings, &records)
+            .ok_or_else(|| Error::Validation(format!("Every pairing of round {} would repeat an already played match", round)))?;
+
+        let mut pairings = vec![];
+        for (first_player, second_player) in round_pairing.pairs {
+            let match_model = match_structure::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tournament_id: Set(tournament_id),
+                // swiss matches aren't created by discord messages, bot attaches reports to them later
+                message_id: Set(None),
+                first_player: Set(first_player),
+                second_player: Set(second_player),
+                challonge_id: Set(None),
+                report_link: Set(None),
+                round: Set(Some(round)),
+                deadline: Set(deadline),
+                stage: Set(tournament.stage),
+                created_at: NotSet
+            }.insert(&transaction).await?;
+            pairings.push((first_player, Some(second_player), Some(match_model.id)));
+        }
+        if let Some(player) = round_pairing.bye {
+            pairings.push((player, None, None));
+        }
+
+        let pairings = pairings.into_iter()
+            .enumerate()
+            .map(|(index, (first_player, second_player, match_id))| SwissPairingModel {
+                id: Uuid::new_v4(),
+                tournament_id,
+                round,
+                board: index as i32 + 1,
+                first_player,
+                second_player,
+                match_id
+            })
+            .collect::<Vec<SwissPairingModel>>();
+        swiss_pairing::Entity::insert_many(pairings.iter().cloned().map(swiss_pairing::ActiveModel::from))
+            .exec(&transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(pairings)
+    }
+
+    /// Swiss table of tournament participants built from reported games of swiss rounds.
+    pub async fn get_standings(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid
+    ) -> Result<Vec<SwissStandingsEntryModel>, Error> {
+        let tournament = tournament::Entity::find_by_id(tournament_id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let mut users = participant::Entity::find()
+            .find_also_related(user::Entity)
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(_, user)| user.map(|user| (user.id, user)))
+            .collect::<HashMap<Uuid, user::Model>>();
+
+        let rounds = load_rounds(db, tournament_id).await?;
+        let players = seeded(db, &tournament, users.keys().copied().collect()).await?;
+        let records = pairing::records(&players, &rounds.results, &rounds.byes);
+
+        let mut entries = vec![];
+        for user_id in pairing::order(&players, &records) {
+            let record = &records[&user_id];
+            let (buchholz, sonneborn_berger) = pairing::tiebreaks(user_id, &records);
+            entries.push(SwissStandingsEntryModel {
+                position: entries.len() as i32 + 1,
+                user: users.remove(&user_id).unwrap(),
+                points: record.points as f64 / 2.0,
+                matches: record.matches,
+                match_wins: record.match_wins,
+                match_draws: record.match_draws,
+                match_losses: record.match_losses,
+                byes: record.byes,
+                buchholz,
+                sonneborn_berger
+            });
+        }
+        Ok(entries)
+    }
+}
+
+async fn load_rounds<C: ConnectionTrait>(db: &C, tournament_id: Uuid) -> Result<SwissRounds, Error> {
+    let pairings = swiss_pairing::Entity::find()
+        .filter(swiss_pairing::Column::TournamentId.eq(tournament_id))
+        .order_by_asc(swiss_pairing::Column::Round)
+        .order_by_asc(swiss_pairing::Column::Board)
+        .all(db)
+        .await?;
+    let match_ids = pairings.iter().filter_map(|pairing| pairing.match_id).collect::<Vec<Uuid>>();
+
+    let matches = match_structure::Entity::find()
+        .filter(match_structure::Column::Id.is_in(match_ids.clone()))
+        .all(db)
+        .await?;
+    let mut scores = HashMap::<Uuid, (i32, i32)>::new();
+    for game in game_builder::Entity::find()
+        .filter(game_builder::Column::MatchId.is_in(match_ids))
+        .filter(game_builder::Column::Result.ne(GameResult::NotSelected))
+        .all(db)
+        .await? {
+        let score = scores.entry(game.match_id).or_default();
+        if game.result == GameResult::FirstPlayerWon {
+            score.0 += 1;
+        } else {
+            score.1 += 1;
+        }
+    }
+
+    // players of reported match may be swapped relative to pairing, results are read in match order
+    let mut results = vec![];
+    let mut played = HashSet::new();
+    for match_model in matches {
+        if let Some(&(first_player_wins, second_player_wins)) = scores.get(&match_model.id) {
+            played.insert(match_model.id);
+            results.push(MatchResult {
+                first_player: match_model.first_player,
+                second_player: match_model.second_player,
+                first_player_wins,
+                second_player_wins
+            });
+        }
+    }
+
+    let byes = pairings.iter()
+        .filter(|pairing| pairing.second_player.is_none())
+        .map(|pairing| pairing.first_player)
+        .collect();
+    let last_round = pairings.last().map(|pairing| pairing.round);
+    let unplayed = pairings.iter()
+        .filter(|pairing| Some(pairing.round) == last_round)
+        .filter(|pairing| pairing.match_id.is_some_and(|match_id| !played.contains(&match_id)))
+        .count();
+    Ok(SwissRounds { pairings, results, byes, unplayed })
+}
+
+/// Orders players by rating in tournament's mod, unrated players go last.
+/// Seeding only matters for the first round and for players equal in all of tiebreakers.
+async fn seeded<C: ConnectionTrait>(db: &C, tournament: &TournamentModel, mut players: Vec<Uuid>) -> Result<Vec<Uuid>, Error> {
+    let ratings = seeding_ratings(db, players.iter().copied(), tournament.mod_type).await?;
+    players.sort_by(|first, second| {
+        let first_rating = ratings.get(first).copied().unwrap_or(f64::MIN);
+        let second_rating = ratings.get(second).copied().unwrap_or(f64::MIN);
+        second_rating.total_cmp(&first_rating).then(first.cmp(second))
+    });
+    Ok(players)
+}
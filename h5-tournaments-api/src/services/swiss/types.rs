@@ -0,0 +1,19 @@
+use crate::services::tournament::models::user::UserModel;
+
+/// Row of swiss table. Match is won by player who won more of its games, matches with equal count of wins are draws.
+#[derive(Debug, Clone, PartialEq, async_graphql::SimpleObject)]
+pub struct SwissStandingsEntryModel {
+    pub position: i32,
+    pub user: UserModel,
+    /// One point for a won match or a bye, half of point for a draw
+    pub points: f64,
+    pub matches: i32,
+    pub match_wins: i32,
+    pub match_draws: i32,
+    pub match_losses: i32,
+    pub byes: i32,
+    /// Sum of points of all opponents
+    pub buchholz: f64,
+    /// Sum of points of beaten opponents and half of points of drawn ones
+    pub sonneborn_berger: f64
+}
@@ -1,7 +1,119 @@
-use derive_more::derive::From;
-use strum::Display;
+use async_graphql::ErrorExtensions;
+use axum::{http::StatusCode, response::{IntoResponse, Response}, Json};
+use derive_more::derive::Display;
+use sea_orm::{DbErr, SqlErr};
+use serde::Serialize;
 
-#[derive(From, Debug, Display)]
+#[derive(Debug, Display)]
 pub enum Error {
+    /// Requested entity doesn't exist
+    #[display("Not found: {_0}")]
+    NotFound(String),
+    /// Entity can't be created or changed because it collides with existing data
+    #[display("Conflict: {_0}")]
+    Conflict(String),
+    /// Incoming data is malformed
+    #[display("Validation failed: {_0}")]
+    Validation(String),
+    /// Some external service failed to process request
+    #[display("Upstream failure: {_0}")]
+    Upstream(String),
+    /// Request has no valid api token but requires one
+    #[display("Unauthenticated: {_0}")]
+    Unauthenticated(String),
+    /// Api token of request has no permission for the action
+    #[display("Forbidden: {_0}")]
+    Forbidden(String),
+    /// Api failed to process request because of its own bug
+    #[display("Internal error: {_0}")]
+    Internal(String),
+    #[display("Database error: {_0}")]
+    Database(DbErr),
+    #[display("Sqlx error: {_0}")]
     SqlxError(sqlx::Error)
-}
\ No newline at end of file
+}
+
+impl Error {
+    /// Stable code of this error, sent in `extensions.code` of graphql errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::NotFound(_) => "NOT_FOUND",
+            Error::Conflict(_) => "CONFLICT",
+            Error::Validation(_) => "VALIDATION",
+            Error::Upstream(_) => "UPSTREAM",
+            Error::Unauthenticated(_) => "UNAUTHENTICATED",
+            Error::Forbidden(_) => "FORBIDDEN",
+            Error::Internal(_) => "INTERNAL",
+            Error::Database(_) | Error::SqlxError(_) => "DATABASE"
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Error::NotFound(_) => StatusCode::NOT_FOUND,
+            Error::Conflict(_) => StatusCode::CONFLICT,
+            Error::Validation(_) => StatusCode::BAD_REQUEST,
+            Error::Upstream(_) => StatusCode::BAD_GATEWAY,
+            Error::Unauthenticated(_) => StatusCode::UNAUTHORIZED,
+            Error::Forbidden(_) => StatusCode::FORBIDDEN,
+            Error::Internal(_) | Error::Database(_) | Error::SqlxError(_) => StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+impl From<sqlx::Error> for Error {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => Error::NotFound("No rows returned by query".to_string()),
+            sqlx::Error::Database(ref db_error) if db_error.is_unique_violation() => Error::Conflict(db_error.message().to_string()),
+            sqlx::Error::Database(ref db_error) if db_error.is_foreign_key_violation() => Error::Validation(db_error.message().to_string()),
+            error => Error::SqlxError(error)
+        }
+    }
+}
+
+impl From<DbErr> for Error {
+    fn from(error: DbErr) -> Self {
+        match error.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(message)) => Error::Conflict(message),
+            Some(SqlErr::ForeignKeyConstraintViolation(message)) => Error::Validation(message),
+            _ => match error {
+                DbErr::RecordNotFound(message) => Error::NotFound(message),
+                error => Error::Database(error)
+            }
+        }
+    }
+}
+
+impl ErrorExtensions for Error {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, extensions| extensions.set("code", self.code()))
+    }
+}
+
+/// Body of failed responses of rest routes.
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", &self);
+        }
+        let body = ErrorBody {
+            code: self.code(),
+            message: self.to_string()
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Parses discord snowflake ids that are sent to api as strings.
+pub(crate) fn parse_discord_id(value: &str, field: &str) -> Result<i64, Error> {
+    value.parse::<i64>()
+        .map_err(|_| Error::Validation(format!("{} must be a numeric discord id, got {}", field, value)))
+}
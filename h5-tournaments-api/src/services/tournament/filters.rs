@@ -0,0 +1,83 @@
+use uuid::Uuid;
+
+use super::{models::{game_builder::GameResult, tournament::{GameType, TournamentStage}}, types::ModId};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+pub enum TournamentsOrderField {
+    #[default]
+    Name,
+    Stage
+}
+
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct TournamentsFilter {
+    /// Case insensitive part of tournament's name
+    pub name: Option<String>,
+    pub stage: Option<TournamentStage>,
+    pub mod_type: Option<ModId>,
+    pub game_type: Option<GameType>,
+    pub organizer: Option<Uuid>
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+pub enum UsersOrderField {
+    #[default]
+    Nickname,
+    DiscordNick
+}
+
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct UsersFilter {
+    /// Case insensitive beginning of user's nickname
+    pub nickname_prefix: Option<String>,
+    /// Only participants of this tournament
+    pub tournament_id: Option<Uuid>
+}
+
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct MatchesFilter {
+    pub tournament_id: Option<Uuid>,
+    /// Matches where this user is either of players
+    pub user_id: Option<Uuid>,
+    /// Matches that have or don't have report link
+    pub reported: Option<bool>
+}
+
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct GamesFilter {
+    pub tournament_id: Option<Uuid>,
+    pub match_id: Option<Uuid>,
+    /// Games where either of players picked this race
+    pub race: Option<i32>,
+    /// Games where either of players picked this hero
+    pub hero: Option<i32>,
+    pub result: Option<GameResult>
+}
+
+#[derive(Debug, Default, Clone, async_graphql::InputObject)]
+pub struct ParticipantsFilter {
+    pub tournament_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub group: Option<i32>
+}
+
+/// Pattern for `ILIKE` that matches given text literally.
+pub(crate) fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+impl From<SortDirection> for sea_orm::Order {
+    fn from(direction: SortDirection) -> Self {
+        match direction {
+            SortDirection::Asc => sea_orm::Order::Asc,
+            SortDirection::Desc => sea_orm::Order::Desc
+        }
+    }
+}
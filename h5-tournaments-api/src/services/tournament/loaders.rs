@@ -0,0 +1,128 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use super::{error::Error, models::{game_builder::{self, GameModel}, heroes::{self, HeroesModel}, match_structure::{self, MatchModel}, participant::{self, TournamentParticipantModel}, tournament::{self, TournamentModel}, user::{self, UserModel}}, types::ModId};
+
+// Loaders are registered in schema without cache, so they only batch lookups made while resolving a single request.
+
+pub struct UserLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<Uuid> for UserLoader {
+    type Value = UserModel;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let users = user::Entity::find()
+            .filter(user::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        Ok(users.into_iter().map(|user| (user.id, user)).collect())
+    }
+}
+
+pub struct TournamentLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<Uuid> for TournamentLoader {
+    type Value = TournamentModel;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let tournaments = tournament::Entity::find()
+            .filter(tournament::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        Ok(tournaments.into_iter().map(|tournament| (tournament.id, tournament)).collect())
+    }
+}
+
+pub struct MatchLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<Uuid> for MatchLoader {
+    type Value = MatchModel;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let matches = match_structure::Entity::find()
+            .filter(match_structure::Column::Id.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        Ok(matches.into_iter().map(|match_model| (match_model.id, match_model)).collect())
+    }
+}
+
+/// Loads games of matches by match ids.
+pub struct GamesLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<Uuid> for GamesLoader {
+    type Value = Vec<GameModel>;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let games = game_builder::Entity::find()
+            .filter(game_builder::Column::MatchId.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        let mut games_by_match = HashMap::<Uuid, Vec<GameModel>>::new();
+        for game in games {
+            games_by_match.entry(game.match_id).or_default().push(game);
+        }
+        Ok(games_by_match)
+    }
+}
+
+/// Loads participants of tournaments by tournament ids.
+pub struct ParticipantsLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<Uuid> for ParticipantsLoader {
+    type Value = Vec<TournamentParticipantModel>;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let participants = participant::Entity::find()
+            .filter(participant::Column::TournamentId.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        let mut participants_by_tournament = HashMap::<Uuid, Vec<TournamentParticipantModel>>::new();
+        for participant in participants {
+            participants_by_tournament.entry(participant.tournament_id).or_default().push(participant);
+        }
+        Ok(participants_by_tournament)
+    }
+}
+
+/// Loads heroes catalogues of mods.
+pub struct HeroesLoader {
+    pub db: DatabaseConnection
+}
+
+impl Loader<ModId> for HeroesLoader {
+    type Value = HeroesModel;
+    type Error = Arc<Error>;
+
+    async fn load(&self, keys: &[ModId]) -> Result<HashMap<ModId, Self::Value>, Self::Error> {
+        let catalogues = heroes::Entity::find()
+            .filter(heroes::Column::ModType.is_in(keys.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|error| Arc::new(Error::from(error)))?;
+        Ok(catalogues.into_iter().map(|catalogue| (catalogue.mod_type, catalogue)).collect())
+    }
+}
@@ -1,5 +1,7 @@
 pub(self) mod service;
-pub(self) mod error;
+pub(crate) mod error;
 pub(crate) mod prelude;
-pub(self) mod types;
+pub(crate) mod types;
+pub(crate) mod loaders;
+pub(crate) mod filters;
 pub mod models;
\ No newline at end of file
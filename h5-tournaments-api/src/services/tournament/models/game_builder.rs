@@ -1,8 +1,13 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
 use sea_orm::prelude::*;
 
+use crate::services::tournament::loaders::{HeroesLoader, MatchLoader, TournamentLoader};
+
+use super::{heroes::HeroNew, match_structure::MatchModel};
+
 pub type GameModel = Model;
 
-#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum GameResult {
     NotSelected = 0,
@@ -10,7 +15,7 @@ pub enum GameResult {
     SecondPlayerWon = 2
 }
 
-#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum GameOutcome {
     FinalBattleVictory = 0,
@@ -18,7 +23,7 @@ pub enum GameOutcome {
     OpponentSurrender = 2
 }
 
-#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum BargainsColor {
     NotSelected = 0,
@@ -39,7 +44,9 @@ pub struct Model {
     pub bargains_color: Option<BargainsColor>,
     pub bargains_amount: Option<i32>,
     pub result: GameResult,
-    pub outcome: GameOutcome
+    pub outcome: GameOutcome,
+    // Unknown for games reported before dates were tracked
+    pub created_at: Option<DateTimeWithTimeZone>
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
@@ -107,6 +114,39 @@ impl GameModel {
     async fn outcome(&self) -> GameOutcome {
         self.outcome
     }
+
+    async fn created_at(&self) -> Option<DateTimeWithTimeZone> {
+        self.created_at
+    }
+
+    async fn match_data<'a>(&self, context: &Context<'a>) -> Result<Option<MatchModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<MatchLoader>>()?;
+        loader.load_one(self.match_id).await.map_err(|error| error.extend())
+    }
+
+    /// Hero from catalogue of the mod game's tournament is played in
+    async fn first_player_hero_data<'a>(&self, context: &Context<'a>) -> Result<Option<HeroNew>, async_graphql::Error> {
+        load_hero(context, self.match_id, self.first_player_hero).await
+    }
+
+    /// Hero from catalogue of the mod game's tournament is played in
+    async fn second_player_hero_data<'a>(&self, context: &Context<'a>) -> Result<Option<HeroNew>, async_graphql::Error> {
+        load_hero(context, self.match_id, self.second_player_hero).await
+    }
+}
+
+async fn load_hero(context: &Context<'_>, match_id: Uuid, hero: Option<i32>) -> Result<Option<HeroNew>, async_graphql::Error> {
+    let Some(hero) = hero else {
+        return Ok(None);
+    };
+    let Some(match_model) = context.data::<DataLoader<MatchLoader>>()?.load_one(match_id).await.map_err(|error| error.extend())? else {
+        return Ok(None);
+    };
+    let Some(tournament) = context.data::<DataLoader<TournamentLoader>>()?.load_one(match_model.tournament_id).await.map_err(|error| error.extend())? else {
+        return Ok(None);
+    };
+    let catalogue = context.data::<DataLoader<HeroesLoader>>()?.load_one(tournament.mod_type).await.map_err(|error| error.extend())?;
+    Ok(catalogue.and_then(|catalogue| catalogue.heroes.entities.into_iter().find(|catalogue_hero| catalogue_hero.id == hero)))
 }
 
 #[derive(Debug, async_graphql::InputObject)]
@@ -0,0 +1,40 @@
+use sea_orm::prelude::*;
+
+use crate::prelude::ModId;
+
+pub type GameModModel = Model;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "mods")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: ModId,
+    pub name: String,
+    pub version: Option<String>,
+    pub active: bool
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl GameModModel {
+    async fn id(&self) -> ModId {
+        self.id
+    }
+
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    /// Inactive mods are kept for history, new tournaments can't be created for them
+    async fn active(&self) -> bool {
+        self.active
+    }
+}
@@ -1,7 +1,9 @@
 use sea_orm::{prelude::*, FromJsonQueryResult};
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::ModType;
+use crate::prelude::ModId;
+
+use super::race::{DisplayNames, LocalizedNameModel};
 
 pub type HeroesModel = Model;
 
@@ -9,7 +11,12 @@ pub type HeroesModel = Model;
 pub struct HeroNew {
     pub id: i32,
     pub race: i32,
-    pub name: String
+    pub name: String,
+    // Both are missing in catalogues created before they were introduced
+    #[serde(default)]
+    pub display_names: DisplayNames,
+    #[serde(default)]
+    pub name_variants: Vec<String>
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
@@ -22,8 +29,9 @@ pub struct HeroesNew {
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub mod_type: ModType,
-    pub heroes: HeroesNew
+    pub mod_type: ModId,
+    pub heroes: HeroesNew,
+    pub version: i32
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -44,6 +52,14 @@ impl HeroNew {
     async fn race(&self) -> i32 {
         self.race
     }
+
+    async fn display_names(&self) -> Vec<LocalizedNameModel> {
+        self.display_names.to_models()
+    }
+
+    async fn name_variants(&self) -> &Vec<String> {
+        &self.name_variants
+    }
 }
 
 #[async_graphql::Object]
@@ -59,11 +75,16 @@ impl HeroesModel {
         self.id
     }
 
-    async fn mod_type(&self) -> ModType {
+    async fn mod_type(&self) -> ModId {
         self.mod_type
     }
 
     async fn heroes(&self) -> &HeroesNew {
         &self.heroes
     }
+
+    /// Increased on every change of heroes of this mod or of races
+    async fn version(&self) -> i32 {
+        self.version
+    }
 }
\ No newline at end of file
@@ -1,5 +1,10 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
 use sea_orm::prelude::*;
 
+use crate::services::tournament::loaders::{GamesLoader, TournamentLoader, UserLoader};
+
+use super::tournament::TournamentStage;
+
 pub type MatchModel = Model;
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -8,23 +13,36 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     pub tournament_id: Uuid,
-    // Message that invoked creation of this match
-    pub message_id: i64,
+    // Message that invoked creation of this match, None for matches created ahead of time until they are reported
+    pub message_id: Option<i64>,
     pub first_player: Uuid,
     pub second_player: Uuid,
-    pub challonge_id: String,
-    pub report_link: Option<String>
+    // None for matches that aren't tracked on challonge
+    pub challonge_id: Option<String>,
+    pub report_link: Option<String>,
+    // Set for matches created ahead of time for group stage rounds
+    pub round: Option<i32>,
+    pub deadline: Option<DateTimeWithTimeZone>,
+    // Stage tournament was at when match was created or reported
+    pub stage: Option<TournamentStage>,
+    // Moment match was reported, matches created ahead of time get it once they are reported
+    pub created_at: DateTimeWithTimeZone
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
 pub enum Relation {
-    Game
+    Game,
+    Tournament
 }
 
 impl RelationTrait for Relation {
     fn def(&self) -> RelationDef {
         match self {
-            Self::Game => Entity::has_many(super::game_builder::Entity).into()
+            Self::Game => Entity::has_many(super::game_builder::Entity).into(),
+            Self::Tournament => Entity::belongs_to(super::tournament::Entity)
+                .from(Column::TournamentId)
+                .to(super::tournament::Column::Id)
+                .into()
         }
     }
 }
@@ -35,6 +53,12 @@ impl Related<super::game_builder::Entity> for Entity {
     }
 }
 
+impl Related<super::tournament::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tournament.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
 
 #[async_graphql::Object]
@@ -47,7 +71,8 @@ impl MatchModel {
         self.tournament_id
     }
 
-    async fn message(&self) -> i64 {
+    /// Null for matches created ahead of time that aren't reported yet
+    async fn message(&self) -> Option<i64> {
         self.message_id
     }
 
@@ -59,11 +84,52 @@ impl MatchModel {
         self.second_player
     }
 
-    async fn challonge(&self) -> String {
+    /// Null for matches that aren't tracked on challonge
+    async fn challonge(&self) -> Option<String> {
         self.challonge_id.clone()
     }
 
     async fn report_link(&self) -> Option<String> {
         self.report_link.clone()
     }
+
+    /// Round of group stage match was created for
+    async fn round(&self) -> Option<i32> {
+        self.round
+    }
+
+    /// Moment match was reported, or created if it isn't reported yet
+    async fn created_at(&self) -> DateTimeWithTimeZone {
+        self.created_at
+    }
+
+    /// Moment match should be played before
+    async fn deadline(&self) -> Option<DateTimeWithTimeZone> {
+        self.deadline
+    }
+
+    /// Stage of tournament match was played at
+    async fn stage(&self) -> Option<TournamentStage> {
+        self.stage
+    }
+
+    async fn first_player_data<'a>(&self, context: &Context<'a>) -> Result<Option<super::user::UserModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<UserLoader>>()?;
+        loader.load_one(self.first_player).await.map_err(|error| error.extend())
+    }
+
+    async fn second_player_data<'a>(&self, context: &Context<'a>) -> Result<Option<super::user::UserModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<UserLoader>>()?;
+        loader.load_one(self.second_player).await.map_err(|error| error.extend())
+    }
+
+    async fn tournament_data<'a>(&self, context: &Context<'a>) -> Result<Option<super::tournament::TournamentModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<TournamentLoader>>()?;
+        loader.load_one(self.tournament_id).await.map_err(|error| error.extend())
+    }
+
+    async fn games<'a>(&self, context: &Context<'a>) -> Result<Vec<super::game_builder::GameModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<GamesLoader>>()?;
+        Ok(loader.load_one(self.id).await.map_err(|error| error.extend())?.unwrap_or_default())
+    }
 }
\ No newline at end of file
@@ -8,4 +8,6 @@ pub(crate) mod match_structure;
 pub(crate) mod game_builder;
 pub(crate) mod tournament_builder;
 pub(crate) mod organizer;
-pub(crate) mod heroes;
\ No newline at end of file
+pub(crate) mod heroes;pub(crate) mod game_mod;
+
+pub(crate) mod tournament_stage_change;
\ No newline at end of file
@@ -1,5 +1,8 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
 use sea_orm::prelude::*;
 
+use crate::services::tournament::loaders::{TournamentLoader, UserLoader};
+
 pub type TournamentParticipantModel = Model;
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -58,4 +61,14 @@ impl TournamentParticipantModel {
     async fn challonge(&self) -> Option<String> {
         self.challonge_id.clone()
     }
+
+    async fn user_data<'a>(&self, context: &Context<'a>) -> Result<Option<super::user::UserModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<UserLoader>>()?;
+        loader.load_one(self.user_id).await.map_err(|error| error.extend())
+    }
+
+    async fn tournament_data<'a>(&self, context: &Context<'a>) -> Result<Option<super::tournament::TournamentModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<TournamentLoader>>()?;
+        loader.load_one(self.tournament_id).await.map_err(|error| error.extend())
+    }
 }
\ No newline at end of file
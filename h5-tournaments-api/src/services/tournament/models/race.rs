@@ -1,13 +1,43 @@
-use sea_orm::prelude::*;
+use std::collections::BTreeMap;
+
+use sea_orm::{prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::ModId;
 
 pub type RaceModel = Model;
 
+/// Names of catalogue entry shown to users, keyed by locale like `ru` or `en`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct DisplayNames(pub BTreeMap<String, String>);
+
+/// Alternative spellings of catalogue entry accepted by report parser.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct NameVariantList(pub Vec<String>);
+
+#[derive(Clone, Debug, PartialEq, Eq, async_graphql::SimpleObject)]
+pub struct LocalizedNameModel {
+    pub locale: String,
+    pub name: String
+}
+
+impl DisplayNames {
+    pub fn to_models(&self) -> Vec<LocalizedNameModel> {
+        self.0.iter()
+            .map(|(locale, name)| LocalizedNameModel { locale: locale.clone(), name: name.clone() })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "races")]
 pub struct Model {
-    #[sea_orm(primary_key)]
+    #[sea_orm(primary_key, auto_increment = false)]
     pub id: i32,
-    pub name: String
+    pub name: String,
+    pub mod_id: Option<ModId>,
+    pub display_names: DisplayNames,
+    pub name_variants: NameVariantList
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -24,4 +54,17 @@ impl RaceModel {
     async fn name(&self) -> String {
         self.name.clone()
     }
-}
\ No newline at end of file
+
+    /// Mod race belongs to, races without it are shared by all mods
+    async fn mod_id(&self) -> Option<ModId> {
+        self.mod_id
+    }
+
+    async fn display_names(&self) -> Vec<LocalizedNameModel> {
+        self.display_names.to_models()
+    }
+
+    async fn name_variants(&self) -> &Vec<String> {
+        &self.name_variants.0
+    }
+}
@@ -1,18 +1,61 @@
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions};
 use sea_orm::prelude::*;
 
-use crate::prelude::ModType;
+use crate::{prelude::{ModId, TournamentService}, services::tournament::loaders::ParticipantsLoader};
+
+use super::tournament_stage_change::TournamentStageChangeModel;
 
 pub type TournamentModel = Model;
 
-#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+/// Lifecycle of tournament, stages go in order of declaration and are numbered the same way,
+/// so tournaments ordered by stage follow their lifecycle.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum TournamentStage {
+    /// Tournament was created before stages were tracked, none of stage checks apply to it
     Unknown = 0,
-    GroupStage = 1,
-    PlayOff = 2
+    Draft = 1,
+    Registration = 2,
+    CheckIn = 3,
+    GroupStage = 4,
+    PlayOff = 5,
+    Finished = 6,
+    Archived = 7
+}
+
+impl TournamentStage {
+    /// Stages tournament can move to from this one. Group stage is skipped by tournaments without groups
+    /// and play-off by ones decided in groups. Tournaments of unknown stage can only be put to the stage
+    /// they are actually at, they have to finish before being archived like any other tournament.
+    pub fn next_stages(self) -> &'static [TournamentStage] {
+        match self {
+            TournamentStage::Unknown => &[
+                TournamentStage::Registration,
+                TournamentStage::CheckIn,
+                TournamentStage::GroupStage,
+                TournamentStage::PlayOff,
+                TournamentStage::Finished
+            ],
+            TournamentStage::Draft => &[TournamentStage::Registration],
+            TournamentStage::Registration => &[TournamentStage::CheckIn],
+            TournamentStage::CheckIn => &[TournamentStage::GroupStage, TournamentStage::PlayOff],
+            TournamentStage::GroupStage => &[TournamentStage::PlayOff, TournamentStage::Finished],
+            TournamentStage::PlayOff => &[TournamentStage::Finished],
+            TournamentStage::Finished => &[TournamentStage::Archived],
+            TournamentStage::Archived => &[]
+        }
+    }
+
+    pub fn allows_registration(self) -> bool {
+        matches!(self, TournamentStage::Unknown | TournamentStage::Registration)
+    }
+
+    pub fn allows_reports(self) -> bool {
+        matches!(self, TournamentStage::Unknown | TournamentStage::GroupStage | TournamentStage::PlayOff)
+    }
 }
 
-#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum)]
+#[derive(Debug, EnumIter, DeriveActiveEnum, Clone, Copy, PartialEq, Eq, async_graphql::Enum, serde::Serialize, serde::Deserialize)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum GameType {
     Rmg = 1,
@@ -25,10 +68,10 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     pub operator_id: Uuid,
-    pub channel_id: i64,
+    pub channel_id: Option<i64>,
     pub name: String,
     pub stage: Option<TournamentStage>,
-    pub register_channel: i64,
+    pub register_channel: Option<i64>,
     pub with_bargains: bool,
     pub with_bargains_color: bool,
     pub with_foreign_heroes: bool,
@@ -36,7 +79,7 @@ pub struct Model {
     pub challonge_id: Option<String>,
     pub organizer: Uuid,
     pub game_type: GameType,
-    pub mod_type: ModType
+    pub mod_type: ModId
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -54,7 +97,8 @@ impl TournamentModel {
         self.operator_id
     }
 
-    async fn channel(&self) -> i64 {
+    /// Empty for tournaments migrated from legacy tables
+    async fn channel(&self) -> Option<i64> {
         self.channel_id
     }
 
@@ -66,7 +110,8 @@ impl TournamentModel {
         self.stage
     }
 
-    async fn register_channel(&self) -> i64 {
+    /// Empty for tournaments migrated from legacy tables
+    async fn register_channel(&self) -> Option<i64> {
         self.register_channel
     }
 
@@ -98,7 +143,19 @@ impl TournamentModel {
         self.game_type
     }
 
-    async fn mod_type(&self) -> ModType {
+    async fn mod_type(&self) -> ModId {
         self.mod_type
     }
+
+    /// Stages tournament went through, oldest first
+    async fn stage_changes<'a>(&self, context: &Context<'a>) -> Result<Vec<TournamentStageChangeModel>, async_graphql::Error> {
+        let service = context.data::<TournamentService>()?;
+        let db = context.data::<DatabaseConnection>()?;
+        service.get_stage_changes(db, self.id).await.map_err(|error| error.extend())
+    }
+
+    async fn participants<'a>(&self, context: &Context<'a>) -> Result<Vec<super::participant::TournamentParticipantModel>, async_graphql::Error> {
+        let loader = context.data::<DataLoader<ParticipantsLoader>>()?;
+        Ok(loader.load_one(self.id).await.map_err(|error| error.extend())?.unwrap_or_default())
+    }
 }
\ No newline at end of file
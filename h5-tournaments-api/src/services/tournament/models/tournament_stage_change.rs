@@ -0,0 +1,55 @@
+use sea_orm::prelude::*;
+
+use super::tournament::TournamentStage;
+
+pub type TournamentStageChangeModel = Model;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tournament_stage_changes")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    // None for the change made when tournament was created
+    pub previous_stage: Option<TournamentStage>,
+    pub stage: TournamentStage,
+    pub changed_at: DateTimeWithTimeZone
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Tournament
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Tournament => Entity::belongs_to(super::tournament::Entity)
+                .from(Column::TournamentId)
+                .to(super::tournament::Column::Id)
+                .into()
+        }
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[async_graphql::Object]
+impl TournamentStageChangeModel {
+    async fn tournament(&self) -> Uuid {
+        self.tournament_id
+    }
+
+    /// Null for the stage tournament was created at
+    async fn previous_stage(&self) -> Option<TournamentStage> {
+        self.previous_stage
+    }
+
+    async fn stage(&self) -> TournamentStage {
+        self.stage
+    }
+
+    async fn changed_at(&self) -> DateTimeWithTimeZone {
+        self.changed_at
+    }
+}
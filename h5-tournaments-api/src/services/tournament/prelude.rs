@@ -1,3 +1,5 @@
 pub use super::error::Error;
 pub use super::service::{TournamentService, LegacyTournamentService};
-pub use super::types::{Hero, Race, Tournament, Match, Game, ModType, GameResult, BargainsColor, BargainsColorModel, GameResultModel, TempMessageModel};
\ No newline at end of file
+pub use super::types::{Hero, Race, Tournament, Match, Game, ModId, GameResult, BargainsColor, BargainsColorModel, GameResultModel, GroupMatchesInput, TempMessageModel};
+pub use super::loaders::{UserLoader, TournamentLoader, MatchLoader, GamesLoader, ParticipantsLoader, HeroesLoader};
+pub use super::filters::{SortDirection, TournamentsOrderField, TournamentsFilter, UsersOrderField, UsersFilter, MatchesFilter, GamesFilter, ParticipantsFilter};
@@ -1,12 +1,14 @@
-use sea_orm::{sea_query::{expr, OnConflict, SimpleExpr}, ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, Related, Set, TransactionTrait};
+use std::{collections::{BTreeMap, HashSet}, time::{Duration, SystemTime}};
+
+use sea_orm::{prelude::{DateTimeUtc, DateTimeWithTimeZone}, sea_query::{expr, extension::postgres::PgExpr, OnConflict, Query}, ActiveEnum, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType, ModelTrait, PaginatorTrait, NotSet, SqlxPostgresConnector, QueryFilter, QueryOrder, QuerySelect, Related, RelationTrait, Set, TransactionTrait};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::{graphql::mutation::UpdateParticipant, routes::models::MatchRegistrationForm};
+use crate::{graphql::mutation::UpdateParticipant, routes::models::MatchRegistrationForm, services::{bracket::{engine, models::bracket::BracketFormat}, pagination::{fetch_page, Cursor, Keyset, Page, PageArguments}}};
 
 use self::{game_builder::GameResult, match_structure::MatchModel, tournament::TournamentModel, user::{Column, Entity, UserModel}};
 
-use super::{models::{game_builder::{self, BargainsColor, CreateGameModel, GameModel, GameOutcome}, hero::{self, HeroModel}, heroes::{self, HeroesModel}, match_structure, operator::{self, TournamentOperatorModel}, organizer::{self, OrganizerModel}, participant, tournament::{self, GameType}, tournament_builder::{self, TournamentBuilderModel, TournamentEditState}, user::{self, UserBulkUpdatePayload}}, types::{Game, Hero, Match, ModType, Race, TempMessageModel, Tournament}};
+use super::{error::{parse_discord_id, Error}, filters::{escape_like, GamesFilter, MatchesFilter, ParticipantsFilter, SortDirection, TournamentsFilter, TournamentsOrderField, UsersFilter, UsersOrderField}, models::{game_builder::{self, BargainsColor, CreateGameModel, GameModel, GameOutcome}, game_mod, hero::{self, HeroModel}, heroes::{self, HeroesModel}, match_structure, operator::{self, TournamentOperatorModel}, organizer::{self, OrganizerModel}, participant::{self, TournamentParticipantModel}, tournament::{self, GameType, TournamentStage}, tournament_builder::{self, TournamentBuilderModel, TournamentEditState}, tournament_stage_change::{self, TournamentStageChangeModel}, user::{self, UserBulkUpdatePayload}}, types::{Game, GroupMatchesInput, Hero, Match, ModId, Race, TempMessageModel, Tournament}};
 
 #[derive(Clone)]
 pub struct LegacyTournamentService {
@@ -76,7 +78,7 @@ impl LegacyTournamentService {
             },
             Err(error) => {
                 tracing::error!("Sqlx: failed to fetch tournament with id {}: {}", id, error.to_string());
-                Err(super::error::Error::SqlxError(error))
+                Err(super::error::Error::from(error))
             }
         }
 
@@ -84,8 +86,9 @@ impl LegacyTournamentService {
     }
 
     pub async fn load_races(&self) -> Result<Vec<Race>, super::error::Error> {
+        // legacy clients expect variants wrapped into an object, catalogue keeps them as plain list
         let races_data: Vec<Race> = sqlx::query_as(r#"
-                SELECT * FROM races;
+                SELECT id, name AS actual_name, jsonb_build_object('variants', name_variants) AS name_variants FROM races ORDER BY id;
             "#)
             .fetch_all(&self.pool)
             .await?;
@@ -93,7 +96,12 @@ impl LegacyTournamentService {
         Ok(races_data)
     }
 
-    pub async fn load_heroes_for_mod(&self, mod_type: ModType) -> Result<Vec<Hero>, super::error::Error> {
+    pub async fn load_heroes_for_mod(&self, mod_type: ModId) -> Result<Vec<Hero>, super::error::Error> {
+        let db = SqlxPostgresConnector::from_sqlx_postgres_pool(self.pool.clone());
+        if game_mod::Entity::find_by_id(mod_type).one(&db).await?.is_none() {
+            return Err(Error::Validation(format!("Unknown mod type {}", mod_type)));
+        }
+
         let heroes_data: Result<Vec<Hero>, sqlx::Error> = sqlx::query_as(r#"
                 SELECT * FROM heroes WHERE mod_type=0 OR mod_type=$1;
             "#)
@@ -107,7 +115,7 @@ impl LegacyTournamentService {
             },
             Err(error) => {
                 tracing::error!("Sqlx: failed to fetch heroes: {}", error.to_string());
-                Err(super::error::Error::SqlxError(error))
+                Err(super::error::Error::from(error))
             }
         }
     }
@@ -132,7 +140,7 @@ impl LegacyTournamentService {
             },
             Err(error) => {
                 tracing::error!("Failed to insert match: {}", error.to_string());
-                Err(super::error::Error::SqlxError(error))
+                Err(super::error::Error::from(error))
             }
         }
     }
@@ -312,7 +320,7 @@ impl TournamentService {
         discord_id: u64,
         //confirm_register: bool
         discord_nick: String
-    ) -> Result<UserModel, DbErr> {
+    ) -> Result<UserModel, Error> {
         let id = Uuid::new_v4();
         let on_conflict = OnConflict::column(Column::DiscordId)
             .update_column(Column::Nickname)
@@ -337,23 +345,21 @@ impl TournamentService {
         id: Uuid,
         nickname: Option<String>,
         registered: Option<bool>
-    ) -> Result<(), String> {
-        let current_user = user::Entity::find_by_id(id).one(db).await.unwrap();
-        if let Some(current_user) = current_user {
+    ) -> Result<(), Error> {
+        let current_user = user::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No user found with id {}", id)))?;
 
-            let mut user_to_update: user::ActiveModel = current_user.into();
+        let mut user_to_update: user::ActiveModel = current_user.into();
 
-            if let Some(nickname) = nickname {
-                user_to_update.nickname = Set(nickname);
-            }
-
-            if let Some(registered) = registered {
-                user_to_update.registered_manually = Set(registered);
-            }
+        if let Some(nickname) = nickname {
+            user_to_update.nickname = Set(nickname);
+        }
 
-            user_to_update.update(db).await.unwrap();
+        if let Some(registered) = registered {
+            user_to_update.registered_manually = Set(registered);
         }
 
+        user_to_update.update(db).await?;
         Ok(())
     }
 
@@ -362,27 +368,12 @@ impl TournamentService {
         db: &DatabaseConnection,
         id: Option<Uuid>,
         server_id: Option<i64>
-    ) -> Result<Option<TournamentOperatorModel>, DbErr> {
+    ) -> Result<Option<TournamentOperatorModel>, Error> {
         let conditions = Condition::all()
-            .add_option(if id.is_some() {
-                Some(expr::Expr::col(operator::Column::Id).eq(id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if server_id.is_some() {
-                Some(expr::Expr::col(operator::Column::ServerId).eq(server_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
+            .add_option(id.map(|id| expr::Expr::col(operator::Column::Id).eq(id)))
+            .add_option(server_id.map(|server_id| expr::Expr::col(operator::Column::ServerId).eq(server_id)));
 
-        match operator::Entity::find().filter(conditions).one(db).await {
-            Ok(operator) => {
-                Ok(operator)
-            },
-            Err(error) => {
-                Err(error)
-            }
-        }
+        Ok(operator::Entity::find().filter(conditions).one(db).await?)
     }
 
     pub async fn create_tournament(
@@ -397,19 +388,24 @@ impl TournamentService {
         role_id: String,
         organizer: Uuid,
         game_type: GameType,
-        mod_type: ModType
-    ) -> Result<String, String> {
+        mod_type: ModId
+    ) -> Result<String, Error> {
         let id = Uuid::new_v4();
-        let channel_id = i64::from_str_radix(&reports_channel_id, 10).unwrap();
-        let register_channel = i64::from_str_radix(&register_channel_id, 10).unwrap();
-        let role = i64::from_str_radix(&role_id, 10).unwrap();
+        let channel_id = parse_discord_id(&reports_channel_id, "channel_id")?;
+        let register_channel = parse_discord_id(&register_channel_id, "register_channel")?;
+        let role = parse_discord_id(&role_id, "role")?;
+        match game_mod::Entity::find_by_id(mod_type).one(db).await? {
+            Some(game_mod) if game_mod.active => {},
+            Some(game_mod) => return Err(Error::Validation(format!("Mod {} is no longer active", game_mod.name))),
+            None => return Err(Error::Validation(format!("No mod found with id {}", mod_type)))
+        }
         let tournament_to_insert = tournament::ActiveModel {
             id: Set(id),
             operator_id: Set(operator_id),
-            channel_id: Set(channel_id),
+            channel_id: Set(Some(channel_id)),
             name: Set(name.clone()),
-            stage: Set(Some(tournament::TournamentStage::Unknown)),
-            register_channel: Set(register_channel),
+            stage: Set(Some(TournamentStage::Draft)),
+            register_channel: Set(Some(register_channel)),
             with_bargains: Set(use_bargains),
             with_bargains_color: Set(use_bargains_color),
             with_foreign_heroes: Set(use_foreign_heroes),
@@ -420,48 +416,77 @@ impl TournamentService {
             mod_type: Set(mod_type)
         };
 
-        let res = tournament_to_insert.insert(db).await;
-
-        match res {
-            Ok(_model) => {
-                Ok(format!("Tournament {} created with id {}", &name, &id))
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        let transaction = db.begin().await?;
+        tournament_to_insert.insert(&transaction).await?;
+        tournament_stage_change::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tournament_id: Set(id),
+            previous_stage: Set(None),
+            stage: Set(TournamentStage::Draft),
+            changed_at: NotSet
+        }.insert(&transaction).await?;
+        transaction.commit().await?;
+        Ok(format!("Tournament {} created with id {}", &name, &id))
     }
 
+    /// Stage can only move forward along tournament lifecycle, every change of it is recorded.
     pub async fn update_tournament(
         &self,
         db: &DatabaseConnection,
         id: Uuid,
-        stage: Option<tournament::TournamentStage>,
+        stage: Option<TournamentStage>,
         challonge_id: Option<String>
-    ) -> Result<(), String> {
-        let current_tournament = tournament::Entity::find_by_id(id).one(db).await.unwrap();
-        if let Some(current_tournament) = current_tournament {
-
-            let mut tournament_to_update: tournament::ActiveModel = current_tournament.into();
-
-            if let Some(stage) = stage {
-                tournament_to_update.stage = Set(Some(stage));
-            }
-
-            if let Some(challonge_id) = challonge_id {
-                tournament_to_update.challonge_id = Set(Some(challonge_id));
+    ) -> Result<TournamentModel, Error> {
+        let transaction = db.begin().await?;
+        let current_tournament = tournament::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", id)))?;
+        let current_stage = current_tournament.stage;
+
+        let mut tournament_to_update: tournament::ActiveModel = current_tournament.into();
+
+        if let Some(stage) = stage {
+            let previous_stage = current_stage.unwrap_or(TournamentStage::Unknown);
+            if !previous_stage.next_stages().contains(&stage) {
+                return Err(Error::Validation(format!("Tournament can't move from {:?} to {:?} stage", previous_stage, stage)));
             }
+            tournament_to_update.stage = Set(Some(stage));
+            tournament_stage_change::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tournament_id: Set(id),
+                previous_stage: Set(current_stage),
+                stage: Set(stage),
+                changed_at: NotSet
+            }.insert(&transaction).await?;
+        }
 
-            tournament_to_update.update(db).await.unwrap();
+        if let Some(challonge_id) = challonge_id {
+            tournament_to_update.challonge_id = Set(Some(challonge_id));
         }
 
-        Ok(())
+        let tournament = tournament_to_update.update(&transaction).await?;
+        transaction.commit().await?;
+        Ok(tournament)
+    }
+
+    pub async fn get_stage_changes(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid
+    ) -> Result<Vec<TournamentStageChangeModel>, Error> {
+        Ok(tournament_stage_change::Entity::find()
+            .filter(tournament_stage_change::Column::TournamentId.eq(tournament_id))
+            .order_by_asc(tournament_stage_change::Column::ChangedAt)
+            .all(db)
+            .await?)
     }
 
     pub async fn get_tournaments(
         &self,
         db: &DatabaseConnection
-    ) -> Result<Vec<TournamentModel>, DbErr> {
+    ) -> Result<Vec<TournamentModel>, Error> {
         Ok(tournament::Entity::find().all(db).await?)
     }
 
@@ -471,38 +496,20 @@ impl TournamentService {
         id: Option<Uuid>,
         reports_channel_id: Option<String>,
         register_channel_id: Option<String>
-    ) -> Result<Option<TournamentModel>, String> {
-        let conditions = Condition::all()
-            .add_option( if id.is_some() { 
-                Some(expr::Expr::col(tournament::Column::Id).eq(id.unwrap())) 
-            } else { 
-                None::<SimpleExpr> 
-            })
-            .add_option( if reports_channel_id.is_some() { 
-                Some(expr::Expr::col(tournament::Column::ChannelId).eq(i64::from_str_radix(&reports_channel_id.unwrap(), 10).unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option( if register_channel_id.is_some() {
-                Some(expr::Expr::col(tournament::Column::RegisterChannel).eq(i64::from_str_radix(&register_channel_id.unwrap(), 10).unwrap()))
-            } else {
-                None::<SimpleExpr>
-            }
-        );
+    ) -> Result<Option<TournamentModel>, Error> {
+        let reports_channel_id = reports_channel_id
+            .map(|channel| parse_discord_id(&channel, "reports_channel_id"))
+            .transpose()?;
+        let register_channel_id = register_channel_id
+            .map(|channel| parse_discord_id(&channel, "register_channel_id"))
+            .transpose()?;
 
-        let res = tournament::Entity::find()
-            .filter(conditions)
-            .one(db)
-            .await;
+        let conditions = Condition::all()
+            .add_option(id.map(|id| expr::Expr::col(tournament::Column::Id).eq(id)))
+            .add_option(reports_channel_id.map(|channel| expr::Expr::col(tournament::Column::ChannelId).eq(channel)))
+            .add_option(register_channel_id.map(|channel| expr::Expr::col(tournament::Column::RegisterChannel).eq(channel)));
 
-        match res {
-            Ok(tournament) => {
-                Ok(tournament)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(tournament::Entity::find().filter(conditions).one(db).await?)
     }
 
     pub async fn get_user(
@@ -510,33 +517,16 @@ impl TournamentService {
         db: &DatabaseConnection,
         id: Option<Uuid>,
         discord_id: Option<String>
-    ) -> Result<Option<UserModel>, String> {
+    ) -> Result<Option<UserModel>, Error> {
+        let discord_id = discord_id
+            .map(|discord_id| parse_discord_id(&discord_id, "discord_id"))
+            .transpose()?;
 
         let conditions = Condition::all()
-            .add_option(if id.is_some() { 
-                Some(expr::Expr::col(user::Column::Id).eq(id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if discord_id.is_some() {
-                Some(expr::Expr::col(user::Column::DiscordId).eq(i64::from_str_radix(&discord_id.unwrap(), 10).unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
+            .add_option(id.map(|id| expr::Expr::col(user::Column::Id).eq(id)))
+            .add_option(discord_id.map(|discord_id| expr::Expr::col(user::Column::DiscordId).eq(discord_id)));
 
-        let res = user::Entity::find()
-            .filter(conditions)
-            .one(db)
-            .await;
-
-        match res {
-            Ok(user) => {
-                Ok(user)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(user::Entity::find().filter(conditions).one(db).await?)
     }
 
     pub async fn create_match(
@@ -546,24 +536,131 @@ impl TournamentService {
         message: i64,
         first_player: Uuid,
         second_player: Uuid,
-        challonge_id: String
-    ) -> Result<Uuid, DbErr> {
-        if let Some(existing_match) = match_structure::Entity::find().filter(match_structure::Column::ChallongeId.eq(&challonge_id)).one(db).await? {
-            Ok(existing_match.id)
-        } else {
-            let id = Uuid::new_v4();
-            let match_to_create = match_structure::ActiveModel {
-                id: Set(id),
-                tournament_id: Set(tournament_id),
-                message_id: Set(message),
-                first_player: Set(first_player),
-                second_player: Set(second_player),
-                challonge_id: Set(challonge_id),
-                report_link: Set(None)
-            };
-            match_to_create.insert(db).await?;
-            Ok(id)
+        challonge_id: Option<String>
+    ) -> Result<Uuid, Error> {
+        let stage = ensure_stage(db, tournament_id, TournamentStage::allows_reports, "Matches can't be reported").await?;
+        if let Some(challonge_id) = &challonge_id
+            && let Some(existing_match) = match_structure::Entity::find().filter(match_structure::Column::ChallongeId.eq(challonge_id)).one(db).await? {
+            return Ok(existing_match.id);
+        }
+
+        // matches created ahead of time have no message until they are reported
+        let scheduled_match = match_structure::Entity::find()
+            .filter(match_structure::Column::TournamentId.eq(tournament_id))
+            .filter(match_structure::Column::MessageId.is_null())
+            .filter(
+                Condition::any()
+                    .add(Condition::all().add(match_structure::Column::FirstPlayer.eq(first_player)).add(match_structure::Column::SecondPlayer.eq(second_player)))
+                    .add(Condition::all().add(match_structure::Column::FirstPlayer.eq(second_player)).add(match_structure::Column::SecondPlayer.eq(first_player)))
+            )
+            .order_by_asc(match_structure::Column::Round)
+            .one(db)
+            .await?;
+        if let Some(scheduled_match) = scheduled_match {
+            let id = scheduled_match.id;
+            // games are reported relative to players order of the report
+            let mut match_to_update: match_structure::ActiveModel = scheduled_match.into();
+            match_to_update.message_id = Set(Some(message));
+            // scheduled matches take their place among matches once they are played
+            match_to_update.created_at = Set(DateTimeWithTimeZone::from(DateTimeUtc::from(SystemTime::now())));
+            match_to_update.first_player = Set(first_player);
+            match_to_update.second_player = Set(second_player);
+            match_to_update.stage = Set(stage);
+            if challonge_id.is_some() {
+                match_to_update.challonge_id = Set(challonge_id);
+            }
+            match_to_update.update(db).await?;
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4();
+        let match_to_create = match_structure::ActiveModel {
+            id: Set(id),
+            tournament_id: Set(tournament_id),
+            message_id: Set(Some(message)),
+            first_player: Set(first_player),
+            second_player: Set(second_player),
+            challonge_id: Set(challonge_id),
+            report_link: Set(None),
+            round: Set(None),
+            deadline: Set(None),
+            stage: Set(stage),
+            created_at: NotSet
+        };
+        match_to_create.insert(db).await?;
+        Ok(id)
+    }
+
+    /// Creates round robin matches of tournament groups ahead of time, reports are attached to them by `create_match`.
+    /// Pairs of players that already have a match in tournament are skipped, so generation can be repeated after groups change.
+    pub async fn generate_group_matches(
+        &self,
+        db: &DatabaseConnection,
+        tournament_id: Uuid,
+        input: GroupMatchesInput
+    ) -> Result<Vec<MatchModel>, Error> {
+        if input.round_interval_days < 1 {
+            return Err(Error::Validation("Rounds must be at least one day apart".to_string()));
         }
+
+        let transaction = db.begin().await?;
+        let tournament = tournament::Entity::find_by_id(tournament_id)
+            .lock_exclusive()
+            .one(&transaction)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+
+        let mut groups = BTreeMap::<i32, Vec<Uuid>>::new();
+        for participant in participant::Entity::find()
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .order_by_asc(participant::Column::UserId)
+            .all(&transaction)
+            .await? {
+            if input.group.is_none_or(|group| participant.group_number == group) {
+                groups.entry(participant.group_number).or_default().push(participant.user_id);
+            }
+        }
+
+        let mut played_pairs = match_structure::Entity::find()
+            .filter(match_structure::Column::TournamentId.eq(tournament_id))
+            .all(&transaction)
+            .await?
+            .into_iter()
+            .map(|match_model| (match_model.first_player.min(match_model.second_player), match_model.first_player.max(match_model.second_player)))
+            .collect::<HashSet<(Uuid, Uuid)>>();
+
+        let mut matches_to_create = vec![];
+        for players in groups.into_values() {
+            for planned in engine::plan(BracketFormat::RoundRobin, &players) {
+                let (Some(first_player), Some(second_player)) = (planned.first_player, planned.second_player) else {
+                    continue;
+                };
+                if !played_pairs.insert((first_player.min(second_player), first_player.max(second_player))) {
+                    continue;
+                }
+                let deadline = input.first_deadline.map(|deadline| deadline + Duration::from_secs(24 * 60 * 60 * (input.round_interval_days * (planned.round - 1)) as u64));
+                matches_to_create.push(match_structure::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tournament_id: Set(tournament_id),
+                    message_id: Set(None),
+                    first_player: Set(first_player),
+                    second_player: Set(second_player),
+                    challonge_id: Set(None),
+                    report_link: Set(None),
+                    round: Set(Some(planned.round)),
+                    deadline: Set(deadline),
+                    stage: Set(tournament.stage),
+                    created_at: NotSet
+                });
+            }
+        }
+
+        let mut created_matches = vec![];
+        for match_to_create in matches_to_create {
+            created_matches.push(match_to_create.insert(&transaction).await?);
+        }
+        transaction.commit().await?;
+        Ok(created_matches)
     }
 
     pub async fn update_match(
@@ -571,33 +668,21 @@ impl TournamentService {
         db: &DatabaseConnection,
         id: Uuid,
         report_link: String
-    ) -> Result<(), DbErr> {
-        if let Some(current_match) = match_structure::Entity::find_by_id(id).one(db).await? {
-            let mut match_to_update: match_structure::ActiveModel = current_match.into();
-            match_to_update.report_link = Set(Some(report_link));
-            match_to_update.update(db).await?;
-        }
-        Ok(())
+    ) -> Result<MatchModel, Error> {
+        let current_match = match_structure::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No match found with id {}", id)))?;
+
+        let mut match_to_update: match_structure::ActiveModel = current_match.into();
+        match_to_update.report_link = Set(Some(report_link));
+        Ok(match_to_update.update(db).await?)
     }
 
     pub async fn get_match(
         &self,
         db: &DatabaseConnection,
         id: Uuid,
-    ) -> Result<Option<MatchModel>, String> {
-        let res = match_structure::Entity::find()
-            .filter(match_structure::Column::Id.eq(id))
-            .one(db)
-            .await;
-
-        match res {
-            Ok(model) => {
-                Ok(model)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+    ) -> Result<Option<MatchModel>, Error> {
+        Ok(match_structure::Entity::find_by_id(id).one(db).await?)
     }
 
     pub async fn get_matches(
@@ -605,15 +690,13 @@ impl TournamentService {
         db: &DatabaseConnection,
         tournament_id: Uuid,
         user_id: Option<Uuid>
-    ) -> Result<Vec<MatchModel>, DbErr> {
+    ) -> Result<Vec<MatchModel>, Error> {
         let conditions = Condition::all()
             .add(expr::Expr::col(match_structure::Column::TournamentId).eq(tournament_id))
-            .add_option(if user_id.is_some() {
-                Some(expr::Expr::col(match_structure::Column::FirstPlayer).eq(user_id.unwrap())
-                    .or(expr::Expr::col(match_structure::Column::SecondPlayer).eq(user_id.unwrap())))
-            } else {
-                None::<SimpleExpr>
-            });
+            .add_option(user_id.map(|user_id| {
+                expr::Expr::col(match_structure::Column::FirstPlayer).eq(user_id)
+                    .or(expr::Expr::col(match_structure::Column::SecondPlayer).eq(user_id))
+            }));
         
         let matches = match_structure::Entity::find().filter(conditions).all(db).await?;
         Ok(matches)
@@ -623,7 +706,7 @@ impl TournamentService {
         &self, 
         db: &DatabaseConnection,
         tournament_id: Uuid
-    ) -> Result<Vec<UserModel>, DbErr> {
+    ) -> Result<Vec<UserModel>, Error> {
         let users = participant::Entity::find_related()
             .filter(participant::Column::TournamentId.eq(tournament_id))
             .all(db)
@@ -636,8 +719,21 @@ impl TournamentService {
         &self,
         db: &DatabaseConnection,
         games: Vec<CreateGameModel>
-    ) -> Result<(), String> {
-        let transaction = db.begin().await.unwrap();
+    ) -> Result<Vec<GameModel>, Error> {
+        let tournament_ids = match_structure::Entity::find()
+            .select_only()
+            .column(match_structure::Column::TournamentId)
+            .filter(match_structure::Column::Id.is_in(games.iter().map(|game| game.match_id).collect::<HashSet<Uuid>>()))
+            .distinct()
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await?;
+        for tournament_id in tournament_ids {
+            ensure_stage(db, tournament_id, TournamentStage::allows_reports, "Games can't be reported").await?;
+        }
+
+        let transaction = db.begin().await?;
+        let mut created_games = vec![];
         for game in games {
             let id = Uuid::new_v4();
             let game_to_insert = game_builder::ActiveModel {
@@ -650,19 +746,13 @@ impl TournamentService {
                 result: Set(game.result),
                 bargains_color: Set(game.bargains_color),
                 bargains_amount: Set(game.bargains_amount),
-                outcome: Set(if game.outcome.is_some() { game.outcome.unwrap() } else { GameOutcome::FinalBattleVictory })
+                outcome: Set(game.outcome.unwrap_or(GameOutcome::FinalBattleVictory)),
+                created_at: NotSet
             };
-            game_to_insert.insert(db).await.unwrap();
-        }
-        let res = transaction.commit().await;
-        match res {
-            Ok(_res) => {
-                Ok(())
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
+            created_games.push(game_to_insert.insert(&transaction).await?);
         }
+        transaction.commit().await?;
+        Ok(created_games)
     }
 
     pub async fn update_game(
@@ -677,52 +767,38 @@ impl TournamentService {
         bargains_amount: Option<i32>,
         result: Option<GameResult>,
         outcome: Option<GameOutcome>
-    ) -> Result<String, String> {
-        let current_game = game_builder::Entity::find()
-            .filter(game_builder::Column::Id.eq(id))
-            .one(db)
-            .await.unwrap();
+    ) -> Result<String, Error> {
+        let game = game_builder::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No game found with id {}", id)))?;
 
-        if let Some(game) = current_game {
-            let mut game_to_update: game_builder::ActiveModel = game.into();
-            if let Some(first_player_race) = first_player_race {
-                game_to_update.first_player_race = Set(Some(first_player_race));
-            }
-            if let Some(first_player_hero) = first_player_hero {
-                game_to_update.first_player_hero = Set(Some(first_player_hero));
-            }
-            if let Some(second_player_race) = second_player_race {
-                game_to_update.second_player_race = Set(Some(second_player_race));
-            }
-            if let Some(second_player_hero) = second_player_hero {
-                game_to_update.second_player_hero = Set(Some(second_player_hero));
-            }
-            if let Some(bargains_color) = bargains_color {
-                game_to_update.bargains_color = Set(Some(bargains_color));
-            }
-            if let Some(bargains_amount) = bargains_amount {
-                game_to_update.bargains_amount = Set(Some(bargains_amount));
-            }
-            if let Some(result) = result {
-                game_to_update.result = Set(result);
-            }
-            if let Some(outcome) = outcome {
-                game_to_update.outcome = Set(outcome);
-            }
-
-            let res = game_to_update.update(db).await;
-            match res {
-                Ok(_success) => {
-                    Ok("Game updated successfully".to_string())
-                },
-                Err(error) => {
-                    Err(error.to_string())
-                }
-            }
+        let mut game_to_update: game_builder::ActiveModel = game.into();
+        if let Some(first_player_race) = first_player_race {
+            game_to_update.first_player_race = Set(Some(first_player_race));
         }
-        else {
-            Err("Failed to find game".to_string())
+        if let Some(first_player_hero) = first_player_hero {
+            game_to_update.first_player_hero = Set(Some(first_player_hero));
+        }
+        if let Some(second_player_race) = second_player_race {
+            game_to_update.second_player_race = Set(Some(second_player_race));
+        }
+        if let Some(second_player_hero) = second_player_hero {
+            game_to_update.second_player_hero = Set(Some(second_player_hero));
+        }
+        if let Some(bargains_color) = bargains_color {
+            game_to_update.bargains_color = Set(Some(bargains_color));
+        }
+        if let Some(bargains_amount) = bargains_amount {
+            game_to_update.bargains_amount = Set(Some(bargains_amount));
+        }
+        if let Some(result) = result {
+            game_to_update.result = Set(result);
+        }
+        if let Some(outcome) = outcome {
+            game_to_update.outcome = Set(outcome);
         }
+
+        game_to_update.update(db).await?;
+        Ok("Game updated successfully".to_string())
     }
 
     // pub async fn get_game(
@@ -754,47 +830,23 @@ impl TournamentService {
         &self,
         db: &DatabaseConnection,
         race: i32
-    ) -> Result<Vec<HeroModel>, String> {
-        let res = hero::Entity::find()
-            .filter(hero::Column::Race.eq(race))
-            .all(db)
-            .await;
-
-        match res {
-            Ok(heroes) => {
-                Ok(heroes)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+    ) -> Result<Vec<HeroModel>, Error> {
+        Ok(hero::Entity::find().filter(hero::Column::Race.eq(race)).all(db).await?)
     }
 
     pub async fn get_hero(
         &self,
         db: &DatabaseConnection,
         id: i32
-    ) -> Result<Option<HeroModel>, String> {
-        let res = hero::Entity::find()
-            .filter(hero::Column::Id.eq(id))
-            .one(db)
-            .await;
-
-        match res {
-            Ok(hero) => {
-                Ok(hero)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+    ) -> Result<Option<HeroModel>, Error> {
+        Ok(hero::Entity::find_by_id(id).one(db).await?)
     }
 
     pub async fn get_games(
         &self,
         db: &DatabaseConnection,
         match_id: Uuid
-    ) -> Result<Vec<GameModel>, DbErr> {
+    ) -> Result<Vec<GameModel>, Error> {
         let games = game_builder::Entity::find()
             .filter(game_builder::Column::MatchId.eq(match_id))
             .all(db)
@@ -807,24 +859,16 @@ impl TournamentService {
         db: &DatabaseConnection,
         tournament_id: Uuid,
         group: i32 
-    ) -> Result<Vec<UserModel>, String> {
-        let res = participant::Entity::find_related()
+    ) -> Result<Vec<UserModel>, Error> {
+        let users = participant::Entity::find_related()
             .filter(
                 Condition::all()
                     .add(participant::Column::TournamentId.eq(tournament_id))
                     .add(participant::Column::GroupNumber.eq(group))
             )
             .all(db)
-            .await;
-            
-        match res {
-            Ok(users) => {
-                Ok(users)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+            .await?;
+        Ok(users)
     }
 
     pub async fn get_participant(
@@ -833,36 +877,13 @@ impl TournamentService {
         user_id: Option<Uuid>,
         tournament_id: Option<Uuid>,
         challonge_id: Option<String>
-    ) -> Result<Option<participant::Model>, String> {
+    ) -> Result<Option<participant::Model>, Error> {
         let conditions = Condition::all()
-            .add_option(if user_id.is_some() {
-                Some(expr::Expr::col(participant::Column::UserId).eq(user_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if tournament_id.is_some() {
-                Some(expr::Expr::col(participant::Column::TournamentId).eq(tournament_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if challonge_id.is_some() {
-                Some(expr::Expr::col(participant::Column::ChallongeId).eq(challonge_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
-        let res = participant::Entity::find()
-            .filter(conditions)
-            .one(db)
-            .await;
+            .add_option(user_id.map(|user_id| expr::Expr::col(participant::Column::UserId).eq(user_id)))
+            .add_option(tournament_id.map(|tournament_id| expr::Expr::col(participant::Column::TournamentId).eq(tournament_id)))
+            .add_option(challonge_id.map(|challonge_id| expr::Expr::col(participant::Column::ChallongeId).eq(challonge_id)));
 
-        match res {
-            Ok(participant) => {
-                Ok(participant)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(participant::Entity::find().filter(conditions).one(db).await?)
     }
 
     pub async fn create_participant(
@@ -871,7 +892,8 @@ impl TournamentService {
         tournament_id: Uuid,
         user_id: Uuid,
         challonge_id: String
-    ) -> Result<u64, DbErr> {
+    ) -> Result<(TournamentParticipantModel, u64), Error> {
+        ensure_stage(db, tournament_id, TournamentStage::allows_registration, "Registration is closed").await?;
         let participant_to_insert = participant::ActiveModel {
             id: Set(Uuid::new_v4()),
             tournament_id: Set(tournament_id),
@@ -880,10 +902,10 @@ impl TournamentService {
             challonge_id: Set(Some(challonge_id))
         };
 
-        participant_to_insert.insert(db).await?;
+        let participant = participant_to_insert.insert(db).await?;
         let count = participant::Entity::find()
             .filter(participant::Column::TournamentId.eq(tournament_id)).count(db).await?;
-        Ok(count)
+        Ok((participant, count))
     }
 
     pub async fn update_participant(
@@ -892,23 +914,21 @@ impl TournamentService {
         id: Uuid,
         group: Option<i32>,
         challonge_id: Option<String>
-    ) -> Result<(), String> {
-        let current_participant = participant::Entity::find_by_id(id).one(db).await.unwrap();
-        if let Some(current_participant) = current_participant {
+    ) -> Result<(), Error> {
+        let current_participant = participant::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No participant found with id {}", id)))?;
 
-            let mut participant_to_update: participant::ActiveModel = current_participant.into();
-
-            if let Some(group) = group {
-                participant_to_update.group_number = Set(group);
-            }
+        let mut participant_to_update: participant::ActiveModel = current_participant.into();
 
-            if let Some(challonge_id) = challonge_id {
-                participant_to_update.challonge_id = Set(Some(challonge_id));
-            }
+        if let Some(group) = group {
+            participant_to_update.group_number = Set(group);
+        }
 
-            participant_to_update.update(db).await.unwrap();
+        if let Some(challonge_id) = challonge_id {
+            participant_to_update.challonge_id = Set(Some(challonge_id));
         }
 
+        participant_to_update.update(db).await?;
         Ok(())
     }
 
@@ -919,38 +939,25 @@ impl TournamentService {
         id: Option<Uuid>,
         user_id: Option<Uuid>,
         challonge_id: Option<String>
-    ) -> Result<u64, DbErr> {
+    ) -> Result<(TournamentParticipantModel, u64), Error> {
         let conditions = Condition::all()
             .add(participant::Column::TournamentId.eq(tournament_id))
-            .add_option(if id.is_some() {
-                Some(expr::Expr::col(participant::Column::Id).eq(id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if user_id.is_some() {
-                Some(expr::Expr::col(participant::Column::UserId).eq(user_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if challonge_id.is_some() {
-                Some(expr::Expr::col(participant::Column::ChallongeId).eq(challonge_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
-        let participant_to_delete = participant::Entity::find()
+            .add_option(id.map(|id| expr::Expr::col(participant::Column::Id).eq(id)))
+            .add_option(user_id.map(|user_id| expr::Expr::col(participant::Column::UserId).eq(user_id)))
+            .add_option(challonge_id.map(|challonge_id| expr::Expr::col(participant::Column::ChallongeId).eq(challonge_id)));
+
+        let model_to_delete = participant::Entity::find()
             .filter(conditions)
             .one(db)
+            .await?
+            .ok_or_else(|| Error::NotFound("No participant to delete found".to_string()))?;
+
+        model_to_delete.clone().delete(db).await?;
+        let count = participant::Entity::find()
+            .filter(participant::Column::TournamentId.eq(tournament_id))
+            .count(db)
             .await?;
-        if let Some(model_to_delete) = participant_to_delete {
-            model_to_delete.delete(db).await?;
-            let count = participant::Entity::find()
-                .filter(participant::Column::TournamentId.eq(tournament_id))
-                .count(db)
-                .await?;
-            Ok(count)
-        } else {
-            Err(DbErr::RecordNotFound("No participant to delete found".to_string()))
-        }
+        Ok((model_to_delete, count))
     }
 
     pub async fn create_organizer(
@@ -958,24 +965,17 @@ impl TournamentService {
         db: &DatabaseConnection,
         discord_id: String,
         challonge_key: String
-    ) -> Result<Uuid, String> {
+    ) -> Result<Uuid, Error> {
         let id = Uuid::new_v4();
 
         let model = organizer::ActiveModel {
             id: Set(id),
-            discord_id: Set(i64::from_str_radix(&discord_id, 10).unwrap()),
+            discord_id: Set(parse_discord_id(&discord_id, "discord_id")?),
             challonge_api_key: Set(challonge_key)
         };
 
-        let res = model.insert(db).await;
-        match res {
-            Ok( _res) => {
-                Ok(id)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        model.insert(db).await?;
+        Ok(id)
     }
 
     pub async fn get_organizer(
@@ -984,49 +984,25 @@ impl TournamentService {
         id: Option<Uuid>,
         discord_id: Option<i64>,
         challonge_key: Option<String>
-    ) -> Result<Option<OrganizerModel>, String> {
+    ) -> Result<Option<OrganizerModel>, Error> {
         let condition = Condition::all()
-            .add_option(if id.is_some() {
-                Some(expr::Expr::col(organizer::Column::Id).eq(id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if discord_id.is_some() {
-                Some(expr::Expr::col(organizer::Column::DiscordId).eq(discord_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if challonge_key.is_some() {
-                Some(expr::Expr::col(organizer::Column::ChallongeApiKey).eq(challonge_key.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
+            .add_option(id.map(|id| expr::Expr::col(organizer::Column::Id).eq(id)))
+            .add_option(discord_id.map(|discord_id| expr::Expr::col(organizer::Column::DiscordId).eq(discord_id)))
+            .add_option(challonge_key.map(|challonge_key| expr::Expr::col(organizer::Column::ChallongeApiKey).eq(challonge_key)));
 
-        let res = organizer::Entity::find()
-            .filter(condition)
-            .one(db)
-            .await;
-
-        match res {
-            Ok(model) => {
-                Ok(model)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(organizer::Entity::find().filter(condition).one(db).await?)
     }
 
     pub async fn create_tournament_builder(
         &self,
         db: &DatabaseConnection,
         message_id: String
-    ) -> Result<TournamentBuilderModel, String> {
+    ) -> Result<TournamentBuilderModel, Error> {
         let id = Uuid::new_v4();
 
         let model = tournament_builder::ActiveModel {
             id: Set(id),
-            message_id: Set(i64::from_str_radix(&message_id, 10).unwrap()),
+            message_id: Set(parse_discord_id(&message_id, "message_id")?),
             name: Set(None),
             edit_state: Set(Some(TournamentEditState::NotSelected)),
             register_channel: Set(None),
@@ -1037,15 +1013,7 @@ impl TournamentService {
             use_foreign_heroes: Set(None)
         };
 
-        let res = model.insert(db).await;
-        match res {
-            Ok(res) => {
-                Ok(res)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(model.insert(db).await?)
     }
 
     pub async fn get_tournament_builder(
@@ -1053,32 +1021,12 @@ impl TournamentService {
         db: &DatabaseConnection,
         id: Option<Uuid>,
         message_id: Option<i64>
-    ) -> Result<Option<TournamentBuilderModel>, String> {
+    ) -> Result<Option<TournamentBuilderModel>, Error> {
         let condition = Condition::all()
-            .add_option(if id.is_some() {
-                Some(expr::Expr::col(tournament_builder::Column::Id).eq(id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            })
-            .add_option(if message_id.is_some() {
-                Some(expr::Expr::col(tournament_builder::Column::MessageId).eq(message_id.unwrap()))
-            } else {
-                None::<SimpleExpr>
-            });
-
-        let res = tournament_builder::Entity::find()
-            .filter(condition)
-            .one(db)
-            .await;
+            .add_option(id.map(|id| expr::Expr::col(tournament_builder::Column::Id).eq(id)))
+            .add_option(message_id.map(|message_id| expr::Expr::col(tournament_builder::Column::MessageId).eq(message_id)));
 
-        match res {
-            Ok(model) => {
-                Ok(model)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+        Ok(tournament_builder::Entity::find().filter(condition).one(db).await?)
     }
 
     pub async fn update_tournament_builder(
@@ -1093,142 +1041,106 @@ impl TournamentService {
         use_bargains: Option<bool>,
         use_bargains_color: Option<bool>,
         use_foreign_heroes: Option<bool>
-    ) -> Result<TournamentBuilderModel, String> {
-        let current_model = tournament_builder::Entity::find_by_id(id).one(db).await.unwrap();
-        if let Some(model) = current_model {
+    ) -> Result<TournamentBuilderModel, Error> {
+        let model = tournament_builder::Entity::find_by_id(id).one(db).await?
+            .ok_or_else(|| Error::NotFound(format!("No tournament_builder model found with id {}", id)))?;
 
-            let mut model_to_update: tournament_builder::ActiveModel = model.into();
+        let mut model_to_update: tournament_builder::ActiveModel = model.into();
 
-            if let Some(name) = name {
-                model_to_update.name = Set(Some(name))
-            }
-
-            if let Some(state) = state {
-                model_to_update.edit_state = Set(Some(state))
-            }
+        if let Some(name) = name {
+            model_to_update.name = Set(Some(name))
+        }
 
-            if let Some(register_channel) = register_channel {
-                model_to_update.register_channel = Set(Some(i64::from_str_radix(&register_channel, 10).unwrap()));
-            }
+        if let Some(state) = state {
+            model_to_update.edit_state = Set(Some(state))
+        }
 
-            if let Some(reports_channel) = reports_channel {
-                model_to_update.reports_channel = Set(Some(i64::from_str_radix(&reports_channel, 10).unwrap()));
-            }
+        if let Some(register_channel) = register_channel {
+            model_to_update.register_channel = Set(Some(parse_discord_id(&register_channel, "register_channel")?));
+        }
 
-            if let Some(role) = role {
-                model_to_update.role = Set(Some(i64::from_str_radix(&role, 10).unwrap()));
-            }
+        if let Some(reports_channel) = reports_channel {
+            model_to_update.reports_channel = Set(Some(parse_discord_id(&reports_channel, "reports_channel")?));
+        }
 
-            if let Some(use_bargains) = use_bargains {
-                model_to_update.use_bargains = Set(Some(use_bargains));
-            }
+        if let Some(role) = role {
+            model_to_update.role = Set(Some(parse_discord_id(&role, "role")?));
+        }
 
-            if let Some(use_bargains_color) = use_bargains_color {
-                model_to_update.use_bargains_color = Set(Some(use_bargains_color));
-            }
+        if let Some(use_bargains) = use_bargains {
+            model_to_update.use_bargains = Set(Some(use_bargains));
+        }
 
-            if let Some(use_foreign_heroes) = use_foreign_heroes {
-                model_to_update.use_foreign_heroes = Set(Some(use_foreign_heroes));
-            }
+        if let Some(use_bargains_color) = use_bargains_color {
+            model_to_update.use_bargains_color = Set(Some(use_bargains_color));
+        }
 
-            let res = model_to_update.update(db).await;
-            match res {
-                Ok(updated_model) => {
-                    Ok(updated_model)
-                },
-                Err(error) => {
-                    Err(error.to_string())
-                }
-            }
-        } else {
-            Err(format!("No tournament_builder model found with id {}", id))
+        if let Some(use_foreign_heroes) = use_foreign_heroes {
+            model_to_update.use_foreign_heroes = Set(Some(use_foreign_heroes));
         }
+
+        Ok(model_to_update.update(db).await?)
     }
 
-    pub async fn get_tournaments_by_organizer(&self, db: &DatabaseConnection, organizer: Uuid) -> Result<Vec<TournamentModel>, String> {
-        let res = tournament::Entity::find()
+    pub async fn get_tournaments_by_organizer(&self, db: &DatabaseConnection, organizer: Uuid) -> Result<Vec<TournamentModel>, Error> {
+        let models = tournament::Entity::find()
             .filter(tournament::Column::Organizer.eq(organizer))
             .all(db)
-            .await;
-
-        match res {
-            Ok(models) => {
-                Ok(models)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+            .await?;
+        Ok(models)
     }
 
-    pub async fn get_users_by_tournament(&self, db: &DatabaseConnection, tournament_id: Uuid) -> Result<Vec<UserModel>, String> {
-        let res = user::Entity::find()
+    pub async fn get_users_by_tournament(&self, db: &DatabaseConnection, tournament_id: Uuid) -> Result<Vec<UserModel>, Error> {
+        let models = user::Entity::find()
             .inner_join(participant::Entity)
             .filter(participant::Column::TournamentId.eq(tournament_id))
             .all(db)
-            .await;
-
-        match res {
-            Ok(models) => {
-                Ok(models)
-            },
-            Err(error) => {
-                Err(error.to_string())
-            }
-        }
+            .await?;
+        Ok(models)
     }
 
-    pub async fn participants_bulk_update(&self, db: &DatabaseConnection, data: Vec<UpdateParticipant>) -> Result<(), String> {
-        let transaction = db.begin().await.unwrap();
+    pub async fn participants_bulk_update(&self, db: &DatabaseConnection, data: Vec<UpdateParticipant>) -> Result<(), Error> {
+        let transaction = db.begin().await?;
         for update_data in data {
             let current_model = participant::Entity::find()
                 .filter(participant::Column::TournamentId.eq(update_data.tournament_id))
                 .filter(participant::Column::UserId.eq(update_data.user_id))
-                .one(db)
-                .await.unwrap();
+                .one(&transaction)
+                .await?;
             if let Some(model) = current_model {
                 let mut model_to_update: participant::ActiveModel = model.into();
-                model_to_update.challonge_id = Set(Some(update_data.challonge_id));
-                model_to_update.update(db).await.unwrap();
-            }
-        }
-        let res = transaction.commit().await;
-        match res {
-            Ok(res) => {
-                tracing::info!("Participants were updated");
-                Ok(())
-            },
-            Err(error) => {
-                Err(error.to_string())
+                if let Some(challonge_id) = update_data.challonge_id {
+                    model_to_update.challonge_id = Set(Some(challonge_id));
+                }
+                if let Some(group_number) = update_data.group_number {
+                    model_to_update.group_number = Set(group_number);
+                }
+                model_to_update.update(&transaction).await?;
             }
         }
+        transaction.commit().await?;
+        tracing::info!("Participants were updated");
+        Ok(())
     }
 
-    pub async fn users_bulk_update(&self, db: &DatabaseConnection, data: Vec<UserBulkUpdatePayload>) -> Result<(), String> {
-        let transaction = db.begin().await.unwrap();
+    pub async fn users_bulk_update(&self, db: &DatabaseConnection, data: Vec<UserBulkUpdatePayload>) -> Result<(), Error> {
+        let transaction = db.begin().await?;
         for update_data in data {
-            let current_model = user::Entity::find().filter(user::Column::Id.eq(update_data.id)).one(db).await.unwrap();
+            let current_model = user::Entity::find_by_id(update_data.id).one(&transaction).await?;
             if let Some(model) = current_model {
                 let mut model_to_update: user::ActiveModel = model.into();
                 if let Some(discord_nick) = update_data.discord_nick {
                     model_to_update.discord_nick = Set(discord_nick);
                 }
-                model_to_update.update(db).await.unwrap();
-            }
-        }
-        let res = transaction.commit().await;
-        match res {
-            Ok(res) => {
-                tracing::info!("Users were updated");
-                Ok(())
-            },
-            Err(error) => {
-                Err(error.to_string())
+                model_to_update.update(&transaction).await?;
             }
         }
+        transaction.commit().await?;
+        tracing::info!("Users were updated");
+        Ok(())
     }
 
-    pub async fn get_games_count(&self, db: &DatabaseConnection, match_id: Uuid) -> Result<u64, DbErr> {
+    pub async fn get_games_count(&self, db: &DatabaseConnection, match_id: Uuid) -> Result<u64, Error> {
         let count = game_builder::Entity::find()
             .filter(game_builder::Column::MatchId.eq(match_id))
             .count(db)
@@ -1236,23 +1148,188 @@ impl TournamentService {
         Ok(count)
     }
 
-    pub async fn get_heroes_new(&self, db: &DatabaseConnection, mod_type: ModType) -> Result<HeroesModel, DbErr> {
-        match heroes::Entity::find()
+    pub async fn get_heroes_new(&self, db: &DatabaseConnection, mod_type: ModId) -> Result<HeroesModel, Error> {
+        heroes::Entity::find()
             .filter(heroes::Column::ModType.eq(mod_type))
             .one(db)
-            .await? 
-        { Some(model) => {
-            Ok(model)
-        } _ => {
-            Err(DbErr::RecordNotFound(format!("No heroes found for mod {:?}", mod_type)))
-        }}
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No heroes found for mod {:?}", mod_type)))
     }
 
-    pub async fn get_all_games(&self, db: &DatabaseConnection, tournament_id: Uuid) -> Result<Vec<GameModel>, DbErr> {
-        let games = match_structure::Entity::find_related()
+    pub async fn get_all_games(&self, db: &DatabaseConnection, tournament_id: Uuid) -> Result<Vec<GameModel>, Error> {
+        let games = <match_structure::Entity as Related<game_builder::Entity>>::find_related()
             .filter(match_structure::Column::TournamentId.eq(tournament_id))
             .all(db)
             .await?;
         Ok(games)
     } 
-}
\ No newline at end of file
+
+    pub async fn get_tournaments_page(
+        &self,
+        db: &DatabaseConnection,
+        filter: TournamentsFilter,
+        order_by: TournamentsOrderField,
+        direction: SortDirection,
+        page: PageArguments
+    ) -> Result<Page<TournamentModel>, Error> {
+        let conditions = Condition::all()
+            .add_option(filter.name.map(|name| expr::Expr::col(tournament::Column::Name).ilike(format!("%{}%", escape_like(&name)))))
+            .add_option(filter.stage.map(|stage| tournament::Column::Stage.eq(stage)))
+            .add_option(filter.mod_type.map(|mod_type| tournament::Column::ModType.eq(mod_type)))
+            .add_option(filter.game_type.map(|game_type| tournament::Column::GameType.eq(game_type)))
+            .add_option(filter.organizer.map(|organizer| tournament::Column::Organizer.eq(organizer)));
+
+        let order_column = match order_by {
+            TournamentsOrderField::Name => tournament::Column::Name,
+            TournamentsOrderField::Stage => tournament::Column::Stage
+        };
+        let keyset = Keyset {
+            columns: vec![order_column, tournament::Column::Id],
+            direction,
+            key: |tournament: &TournamentModel| Cursor(vec![
+                match order_by {
+                    TournamentsOrderField::Name => tournament.name.clone().into(),
+                    TournamentsOrderField::Stage => tournament.stage.map(|stage| stage.to_value()).into()
+                },
+                tournament.id.into()
+            ])
+        };
+
+        fetch_page(db, tournament::Entity::find().filter(conditions), keyset, page).await
+    }
+
+    pub async fn get_users_page(
+        &self,
+        db: &DatabaseConnection,
+        filter: UsersFilter,
+        order_by: UsersOrderField,
+        direction: SortDirection,
+        page: PageArguments
+    ) -> Result<Page<UserModel>, Error> {
+        let conditions = Condition::all()
+            .add_option(filter.nickname_prefix.map(|prefix| expr::Expr::col(user::Column::Nickname).ilike(format!("{}%", escape_like(&prefix)))))
+            .add_option(filter.tournament_id.map(|tournament_id| user::Column::Id.in_subquery(
+                Query::select()
+                    .column(participant::Column::UserId)
+                    .from(participant::Entity)
+                    .and_where(participant::Column::TournamentId.eq(tournament_id))
+                    .to_owned()
+            )));
+
+        let order_column = match order_by {
+            UsersOrderField::Nickname => user::Column::Nickname,
+            UsersOrderField::DiscordNick => user::Column::DiscordNick
+        };
+        let keyset = Keyset {
+            columns: vec![order_column, user::Column::Id],
+            direction,
+            key: |user: &UserModel| Cursor(vec![
+                match order_by {
+                    UsersOrderField::Nickname => user.nickname.clone().into(),
+                    UsersOrderField::DiscordNick => user.discord_nick.clone().into()
+                },
+                user.id.into()
+            ])
+        };
+
+        fetch_page(db, user::Entity::find().filter(conditions), keyset, page).await
+    }
+
+    /// Matches are ordered by the time they were reported, matches created ahead of time are placed by the time
+    /// they were created until they are reported.
+    pub async fn get_matches_page(
+        &self,
+        db: &DatabaseConnection,
+        filter: MatchesFilter,
+        direction: SortDirection,
+        page: PageArguments
+    ) -> Result<Page<MatchModel>, Error> {
+        let conditions = Condition::all()
+            .add_option(filter.tournament_id.map(|tournament_id| match_structure::Column::TournamentId.eq(tournament_id)))
+            .add_option(filter.user_id.map(|user_id| {
+                expr::Expr::col(match_structure::Column::FirstPlayer).eq(user_id)
+                    .or(expr::Expr::col(match_structure::Column::SecondPlayer).eq(user_id))
+            }))
+            .add_option(filter.reported.map(|reported| if reported {
+                match_structure::Column::ReportLink.is_not_null()
+            } else {
+                match_structure::Column::ReportLink.is_null()
+            }));
+
+        let keyset = Keyset {
+            columns: vec![match_structure::Column::CreatedAt, match_structure::Column::Id],
+            direction,
+            key: |match_model: &MatchModel| Cursor(vec![match_model.created_at.into(), match_model.id.into()])
+        };
+
+        fetch_page(db, match_structure::Entity::find().filter(conditions), keyset, page).await
+    }
+
+    /// Games are ordered by the time they were reported, games without known date go last.
+    pub async fn get_games_page(
+        &self,
+        db: &DatabaseConnection,
+        filter: GamesFilter,
+        direction: SortDirection,
+        page: PageArguments
+    ) -> Result<Page<GameModel>, Error> {
+        let conditions = Condition::all()
+            .add_option(filter.tournament_id.map(|tournament_id| match_structure::Column::TournamentId.eq(tournament_id)))
+            .add_option(filter.match_id.map(|match_id| game_builder::Column::MatchId.eq(match_id)))
+            .add_option(filter.race.map(|race| Condition::any()
+                .add(game_builder::Column::FirstPlayerRace.eq(race))
+                .add(game_builder::Column::SecondPlayerRace.eq(race))))
+            .add_option(filter.hero.map(|hero| Condition::any()
+                .add(game_builder::Column::FirstPlayerHero.eq(hero))
+                .add(game_builder::Column::SecondPlayerHero.eq(hero))))
+            .add_option(filter.result.map(|result| game_builder::Column::Result.eq(result)));
+
+        let select = game_builder::Entity::find()
+            .join(JoinType::InnerJoin, game_builder::Relation::Match.def())
+            .filter(conditions);
+        let keyset = Keyset {
+            columns: vec![game_builder::Column::CreatedAt, game_builder::Column::Id],
+            direction,
+            key: |game: &GameModel| Cursor(vec![game.created_at.into(), game.id.into()])
+        };
+
+        fetch_page(db, select, keyset, page).await
+    }
+
+    pub async fn get_participants_page(
+        &self,
+        db: &DatabaseConnection,
+        filter: ParticipantsFilter,
+        direction: SortDirection,
+        page: PageArguments
+    ) -> Result<Page<TournamentParticipantModel>, Error> {
+        let conditions = Condition::all()
+            .add_option(filter.tournament_id.map(|tournament_id| participant::Column::TournamentId.eq(tournament_id)))
+            .add_option(filter.user_id.map(|user_id| participant::Column::UserId.eq(user_id)))
+            .add_option(filter.group.map(|group| participant::Column::GroupNumber.eq(group)));
+
+        let keyset = Keyset {
+            columns: vec![participant::Column::GroupNumber, participant::Column::Id],
+            direction,
+            key: |participant: &TournamentParticipantModel| Cursor(vec![participant.group_number.into(), participant.id.into()])
+        };
+
+        fetch_page(db, participant::Entity::find().filter(conditions), keyset, page).await
+    }
+}
+
+/// Fails unless stage of tournament allows the action, `action` describes refusal.
+async fn ensure_stage<C: ConnectionTrait>(
+    db: &C,
+    tournament_id: Uuid,
+    allows: fn(TournamentStage) -> bool,
+    action: &str
+) -> Result<Option<TournamentStage>, Error> {
+    let tournament = tournament::Entity::find_by_id(tournament_id).one(db).await?
+        .ok_or_else(|| Error::NotFound(format!("No tournament found with id {}", tournament_id)))?;
+    let stage = tournament.stage.unwrap_or(TournamentStage::Unknown);
+    if !allows(stage) {
+        return Err(Error::Validation(format!("{} at {:?} stage of tournament", action, stage)));
+    }
+    Ok(tournament.stage)
+}
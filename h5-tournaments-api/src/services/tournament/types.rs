@@ -1,16 +1,39 @@
-use sea_orm::{DeriveActiveEnum, EnumIter};
+use sea_orm::{prelude::DateTimeWithTimeZone, DbErr, DeriveValueType, TryFromU64};
 use serde::{Deserialize, Serialize};
 use sqlx::types::Json;
 use strum::{Display, EnumString, FromRepr};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, EnumIter, FromRepr, EnumString, Display, Clone, Copy, PartialEq, Eq, Hash, Default, sqlx::Type, async_graphql::Enum, DeriveActiveEnum)]
-#[sea_orm(rs_type = "i32", db_type = "Integer")]
-#[repr(i16)]
-pub enum ModType {
-    #[default]
-    Universe = 0,
-    Hrta = 1
+/// Id of modification tournament is played in, known mods are listed in `mods` table.
+/// Columns of tournaments, heroes catalogues and ratings keep their `mod_type` name.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, sqlx::Type, DeriveValueType, derive_more::Display)]
+#[serde(transparent)]
+#[sqlx(transparent)]
+pub struct ModId(pub i32);
+
+async_graphql::scalar!(ModId, "ModId", "Id of modification from mods registry");
+
+impl ModId {
+    /// Mod tournaments were played in before mods registry appeared
+    pub const UNIVERSE: ModId = ModId(0);
+}
+
+impl TryFromU64 for ModId {
+    fn try_from_u64(n: u64) -> Result<Self, DbErr> {
+        i32::try_from_u64(n).map(ModId)
+    }
+}
+
+/// Schedule of group stage matches, every group plays a round robin.
+#[derive(Debug, Clone, async_graphql::InputObject)]
+pub struct GroupMatchesInput {
+    /// Group to create matches for, matches of all groups are created if not set
+    pub group: Option<i32>,
+    /// Deadline of the first round, matches have no deadlines if not set
+    pub first_deadline: Option<DateTimeWithTimeZone>,
+    /// Days between deadlines of consecutive rounds
+    #[graphql(default = 7)]
+    pub round_interval_days: i32
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
 use crate::{
-    builders::types::GameBuilderState, graphql::queries::get_heroes_query::GetHeroesQueryHeroesNewHeroesEntities, services::{
+    builders::types::GameBuilderState, graphql::queries::{get_heroes_query::GetHeroesQueryHeroesNewHeroesEntities, get_tournament_query::TournamentStage}, services::{
         challonge::{service::ChallongeService, types::{ChallongeMatchData, ChallongeTournamentState}},
         h5_tournaments::{
             payloads::{GetOrganizerPayload, GetParticipantPayload},
@@ -40,7 +40,13 @@ pub async fn collect_match_creation_data(
     let challonge_tournament = challonge_service.get_challonge_tournament(
         &organizer.challonge, tournament_data.challonge_id.as_ref().unwrap()).await?;
     let state = ChallongeTournamentState::from_str(&challonge_tournament.attributes.state)?;
-    if state == ChallongeTournamentState::Pending {
+    // tournaments created before stages were tracked still rely on challonge state,
+    // others are moved to the stage challonge tournament is at
+    let started = match &tournament_data.stage {
+        None | Some(TournamentStage::UNKNOWN) => state != ChallongeTournamentState::Pending,
+        Some(stage) => tournaments_service.follow_challonge_state(tournament_data.id, stage, &state).await?
+    };
+    if !started {
         interaction.create_response(context, CreateInteractionResponse::Message(
             CreateInteractionResponseMessage::new()
                 .ephemeral(true)
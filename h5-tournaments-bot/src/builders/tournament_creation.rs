@@ -14,10 +14,8 @@ use crate::{
         },
     }, types::payloads::GetTournament
 };
-use h5_tournaments_api::prelude::ModType;
 /// Contains all methods to build discord elements for tournaments creation and administration.
 use poise::serenity_prelude::*;
-use strum::IntoEnumIterator;
 use tokio::sync::{RwLock, RwLockReadGuard};
 use uuid::Uuid;
 
@@ -27,9 +25,13 @@ use super::types::{TournamentBuildState, TournamentBuilder};
 pub async fn build_tournament_creation_interface(
     interaction: &ComponentInteraction,
     context: &Context,
-    tournament_builders: &RwLock<HashMap<u64, RwLock<TournamentBuilder>>>
+    tournament_builders: &RwLock<HashMap<u64, RwLock<TournamentBuilder>>>,
+    tournaments_service: &H5TournamentsService
 ) -> Result<(), crate::Error> {
-    let builder = RwLock::new(TournamentBuilder::default());
+    let builder = RwLock::new(TournamentBuilder {
+        mods: tournaments_service.get_mods().await?,
+        ..Default::default()
+    });
     let builder_locked = builder.read().await;
     let base_interface = build_base_interface(&builder_locked).await;
     let current_interface = build_current_state_interface(&builder_locked).await;
@@ -158,9 +160,13 @@ async fn build_base_data_interface(
             CreateButton::new("enter_tournament_name_button").style(ButtonStyle::Primary).label("Указать название турнира")
         ]),
         CreateActionRow::SelectMenu(CreateSelectMenu::new("tournament_mod_type_selector", CreateSelectMenuKind::String { options: Vec::from_iter(
-            ModType::iter().map(|m| {
-                CreateSelectMenuOption::new(m.to_string(), m.to_string())
-                    .default_selection(builder.mod_type.is_some() && *builder.mod_type.as_ref().unwrap() == m)
+            builder.mods.iter().map(|m| {
+                let label = match &m.version {
+                    Some(version) => format!("{} {}", m.name, version),
+                    None => m.name.clone()
+                };
+                CreateSelectMenuOption::new(label, m.id.to_string())
+                    .default_selection(builder.mod_type == Some(m.id))
             })
         )}).placeholder("Укажите мод, на основе которого проводится турнир")),
         CreateActionRow::SelectMenu(CreateSelectMenu::new("tournament_game_type_selector", CreateSelectMenuKind::String { options: vec![
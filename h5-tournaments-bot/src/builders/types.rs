@@ -1,9 +1,8 @@
-use h5_tournaments_api::prelude::ModType;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString, FromRepr};
 use uuid::Uuid;
 
-use crate::{graphql::queries::get_heroes_query::GetHeroesQueryHeroesNewHeroesEntities, services::challonge::types::ChallongeTournamentState};
+use crate::{graphql::queries::{get_heroes_query::GetHeroesQueryHeroesNewHeroesEntities, get_mods_query::GetModsQueryMods}, services::challonge::types::ChallongeTournamentState};
 
 #[derive(Serialize, Deserialize)]
 pub struct OpponentDataPayload {
@@ -119,7 +118,9 @@ pub struct TournamentBuilder {
     pub organizer: Option<Uuid>,
     pub edit_state: TournamentBuildState, 
     pub game_type: Option<GameType>,
-    pub mod_type: Option<ModType>,
+    pub mod_type: Option<i32>,
+    /// Mods available for selection, loaded when builder is created
+    pub mods: Vec<GetModsQueryMods>,
     pub register_channel: Option<u64>,
     pub reports_channel: Option<u64>,
     pub role: Option<u64>,
@@ -177,6 +177,7 @@ impl MainEventHandler {
                     interaction,
                     context,
                     &self.tournament_builders,
+                    &self.tournaments_service
                 )
                 .await?;
             },
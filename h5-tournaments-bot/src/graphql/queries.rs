@@ -7,6 +7,8 @@ use graphql_client::GraphQLQuery;
 use crate::builders::{self, types::GameType};
 
 type UUID = uuid::Uuid;
+/// Mods are registered in api, so only their ids are known here
+pub type ModId = i32;
 
 #[derive(Debug, PartialEq, Eq)]
 #[repr(i16)]
@@ -156,6 +158,14 @@ pub struct UpdateUser;
 )]
 pub struct GetHeroesQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/queries/get_mods.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetModsQuery;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/graphql/schema.json",
@@ -352,16 +362,6 @@ impl From<get_tournament_query::GameType> for GameType {
     }
 }
 
-impl Into<get_heroes_query::ModType> for h5_tournaments_api::prelude::ModType {
-    fn into(self) -> get_heroes_query::ModType {
-        match self {
-            h5_tournaments_api::prelude::ModType::Hrta => get_heroes_query::ModType::HRTA,
-            h5_tournaments_api::prelude::ModType::Universe => get_heroes_query::ModType::UNIVERSE,
-            _=> get_heroes_query::ModType::UNIVERSE
-        }
-    }
-}
-
 impl Into<create_games_bulk::GameOutcome> for crate::builders::types::GameOutcome {
     fn into(self) -> create_games_bulk::GameOutcome {
         match self {
@@ -3,11 +3,12 @@ use std::{collections::HashMap, str::FromStr};
 use crate::{
     builders::{self, tournament_creation::rebuild_tournament_creation_interface, types::{GameType, TournamentBuildState, TournamentBuilder}},
     event_handler::LocalSyncBuilder,
-    graphql::queries::update_participants_bulk::UpdateParticipant,
+    graphql::queries::{get_tournament_query, update_participants_bulk::UpdateParticipant, update_tournament},
     services::{
         challonge::{
             payloads::ChallongeParticipantAttributes,
             service::ChallongeService,
+            types::ChallongeTournamentState,
         },
         h5_tournaments::{
             payloads::{
@@ -18,7 +19,6 @@ use crate::{
     },
     types::payloads::GetTournament,
 };
-use h5_tournaments_api::prelude::ModType;
 use poise::serenity_prelude::*;
 use strum::{Display, EnumString};
 use tokio::sync::RwLock;
@@ -200,7 +200,11 @@ pub async fn process_tournament_mod_type_selection(
     let builders_locked = tournament_builders.read().await;
     if let Some(builder) = builders_locked.get(&message) {
         let mut builder_locked = builder.write().await;
-        builder_locked.mod_type = Some(ModType::from_str(&selected_value)?);
+        let mod_type = selected_value.parse::<i32>()?;
+        if !builder_locked.mods.iter().any(|m| m.id == mod_type) {
+            return Err(crate::Error::from(format!("Mod {} isn't available for new tournaments", mod_type)));
+        }
+        builder_locked.mod_type = Some(mod_type);
         let response_message = rebuild_tournament_creation_interface(&builder_locked.downgrade()).await;
         interaction.create_response(context, CreateInteractionResponse::UpdateMessage(response_message)).await?;
     }
@@ -559,13 +563,24 @@ pub async fn start_synchronization(
             )).await?;
         } else {
             let challonge_id = sync_builder.challonge_id.as_ref().unwrap();
-            let discord_id = sync_builder.discord_id.as_ref().unwrap();
+            let tournament_id = Uuid::from_str(sync_builder.discord_id.as_ref().unwrap())?;
             service
                 .update_tournament(
-                    UpdateTournamentPayload::new(Uuid::from_str(discord_id)?)
+                    UpdateTournamentPayload::new(tournament_id)
                         .with_challonge_id(challonge_id.clone()),
                 )
                 .await?;
+            // registration needs challonge tournament, so it's opened once new tournament is synchronized
+            let tournament = service.get_tournament_data(GetTournament::default().with_id(tournament_id)).await?;
+            if let Some(tournament) = tournament
+                && let Some(get_tournament_query::TournamentStage::DRAFT) = tournament.stage {
+                service
+                    .update_tournament(
+                        UpdateTournamentPayload::new(tournament_id)
+                            .with_stage(update_tournament::TournamentStage::REGISTRATION),
+                    )
+                    .await?;
+            }
             interaction
                 .create_response(
                     context,
@@ -653,6 +668,16 @@ pub async fn start_participants_syncronization(
                 tournaments_service
                     .update_participants_bulk(participants_to_update)
                     .await?;
+                // tournament that is already started on challonge moves to the stage it's at there
+                if let Some(stage) = &tournament_data.stage {
+                    let challonge_tournament = challonge_service
+                        .get_challonge_tournament(&organizer.challonge, tournament_data.challonge_id.as_ref().unwrap())
+                        .await?;
+                    let state = ChallongeTournamentState::from_str(&challonge_tournament.attributes.state)?;
+                    tournaments_service
+                        .follow_challonge_state(tournament_data.id, stage, &state)
+                        .await?;
+                }
                 interaction.create_response(context, CreateInteractionResponse::Message(
                 CreateInteractionResponseMessage::new()
                     .ephemeral(true)
@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::{
     graphql::queries::{
-        get_tournament_query::GetTournamentQueryTournament, get_user_query::GetUserQueryUser,
+        get_tournament_query::{GetTournamentQueryTournament, TournamentStage}, get_user_query::GetUserQueryUser,
     },
     services::{
         challonge::{
@@ -51,7 +51,13 @@ pub async fn try_register_in_tournament(
     let challonge_tournament = challonge_service
         .get_challonge_tournament(&organizer.challonge, &tournament.challonge_id.as_ref().unwrap()).await?;
     let state = ChallongeTournamentState::from_str(&challonge_tournament.attributes.state)?;
-    if state != ChallongeTournamentState::Pending {
+    // tournaments created before stages were tracked still rely on challonge state
+    let registration_open = match &tournament.stage {
+        None | Some(TournamentStage::UNKNOWN) => state == ChallongeTournamentState::Pending,
+        Some(TournamentStage::REGISTRATION) => true,
+        Some(_) => false
+    };
+    if !registration_open {
         interaction.create_response(context, CreateInteractionResponse::Message(
             CreateInteractionResponseMessage::new()
                 .ephemeral(true)
@@ -214,12 +220,11 @@ pub async fn try_remove_registration(
                     guild
                         .edit_member(context, user.id, EditMember::new().roles(roles_to_update))
                         .await?;
-                    let output_channel = ChannelId::new(tournament.register_channel as u64);
                     let unregister_message = CreateMessage::new().content(format!(
                         "<@{}> снялся с турнира! Текущее число регистраций: **{}**",
                         system_user.discord_id as u64, count
                     ));
-                    output_channel
+                    channel
                         .send_message(context, unregister_message)
                         .await?;
                     interaction
@@ -26,7 +26,7 @@ use crate::{
             service::H5TournamentsService,
         },
     },
-    types::payloads::{GetMatch, GetTournament, GetUser, UpdateMatch},
+    types::payloads::{GetMatch, GetTournament, UpdateMatch},
 };
 
 pub async fn select_opponent(
@@ -120,14 +120,14 @@ pub async fn finish_match_creation(
             )
             .await?;
 
-        let heroes = tournaments_service
-            .get_heroes(h5_tournaments_api::prelude::ModType::Universe)
-            .await?;
-
         let tournament_data = tournaments_service.get_tournament_data(
             GetTournament::default().with_id(builder_locked.tournament_id)
         ).await?.unwrap();
 
+        let heroes = tournaments_service
+            .get_heroes(tournament_data.mod_type)
+            .await?;
+
         let container = GameBuilderContainer {
             match_id: created_match_id,
             tournament_id: builder_locked.tournament_id,
@@ -343,14 +343,8 @@ async fn generate_report_final_message(
         .get_match(container.match_id)
         .await?
         .unwrap();
-    let first_user = tournaments_service
-        .get_user(GetUser::default().with_id(match_data.first_player))
-        .await?
-        .unwrap();
-    let second_user = tournaments_service
-        .get_user(GetUser::default().with_id(match_data.second_player))
-        .await?
-        .unwrap();
+    let first_user = match_data.first_player_data.unwrap();
+    let second_user = match_data.second_player_data.unwrap();
     let games = container
         .builders
         .iter()
@@ -475,6 +469,11 @@ async fn generate_report_final_message(
         .update_match(container.match_id, link)
         .await?;
 
+    // matches created by api ahead of time aren't tracked on challonge
+    let Some(challonge_match) = match_data.challonge else {
+        return Ok(());
+    };
+
     let first_participant = tournaments_service
         .get_participant(
             GetParticipantPayload::default()
@@ -497,7 +496,6 @@ async fn generate_report_final_message(
             "User {} isn't found in tournament {}",
             &second_user.nickname, &tournament_data.name
         )))?;
-    let challonge_match = match_data.challonge;
     let challonge_tournament = tournament_data.challonge_id.unwrap();
     let organizer = tournaments_service
         .get_organizer(GetOrganizerPayload::default().with_id(tournament_data.organizer))
@@ -1,4 +1,3 @@
-use h5_tournaments_api::prelude::ModType;
 use uuid::Uuid;
 
 use crate::{builders::{self, types::GameType}, graphql::queries::{
@@ -190,7 +189,7 @@ pub struct CreateTournamentPayload {
     pub role: String,
     pub organizer: Uuid,
     pub game_type: GameType,
-    pub mod_type: ModType
+    pub mod_type: i32
 }
 
 impl Into<create_tournament_mutation::GameType> for builders::types::GameType {
@@ -202,15 +201,6 @@ impl Into<create_tournament_mutation::GameType> for builders::types::GameType {
     }
 }
 
-impl Into<create_tournament_mutation::ModType> for ModType {
-    fn into(self) -> create_tournament_mutation::ModType {
-        match self {
-            ModType::Universe => create_tournament_mutation::ModType::UNIVERSE,
-            ModType::Hrta => create_tournament_mutation::ModType::HRTA
-        }
-    }
-}
-
 impl From<CreateTournamentPayload> for create_tournament_mutation::Variables {
     fn from(value: CreateTournamentPayload) -> Self {
         create_tournament_mutation::Variables {
@@ -224,7 +214,7 @@ impl From<CreateTournamentPayload> for create_tournament_mutation::Variables {
             role: value.role,
             organizer: value.organizer,
             game_type: value.game_type.into(),
-            mod_type: value.mod_type.into()
+            mod_type: value.mod_type
         }
     }
 }
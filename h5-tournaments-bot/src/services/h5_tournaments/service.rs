@@ -6,8 +6,8 @@ use poise::serenity_prelude::client;
 use uuid::Uuid;
 
 use crate::{
-    commands::TempMessageModel, graphql::queries::{
-        self, create_games_bulk::{self, CreateGameModel}, create_organizer, create_participant, create_tournament_builder::{self, CreateTournamentBuilderCreateTournamentBuilder}, create_tournament_mutation, create_user_mutation::{self, CreateUserMutationCreateUser, ResponseData}, delete_participant, games_count, get_hero_query::{self, GetHeroQueryHero}, get_heroes_query::{self, GetHeroesQueryHeroesNewHeroesEntities}, get_match_query::GetMatchQueryGetMatch, get_operator_data_query::{self, GetOperatorDataQueryOperator}, get_organizer::{self, GetOrganizerOrganizer}, get_participant::{self, GetParticipantParticipant}, get_tournament_builder::{self, GetTournamentBuilderTournamentBuilder}, get_tournament_query, get_tournament_users::{self, GetTournamentUsersTournamentUsers}, get_tournaments::{self, GetTournamentsTournaments}, get_user_query::{self, GetUserQueryUser}, update_match, update_participants_bulk::{self, UpdateParticipant}, update_tournament, update_tournament_builder::{self, UpdateTournamentBuilderUpdateTournamentBuilder}, update_user, update_users_bulk, CreateGamesBulk, CreateMatchMutation, CreateOrganizer, CreateParticipant, CreateTournamentBuilder, CreateTournamentMutation, CreateUserMutation, DeleteParticipant, GamesCount, GetHeroQuery, GetHeroesQuery, GetMatchQuery, GetOperatorDataQuery, GetOperatorSectionQuery, GetOrganizer, GetParticipant, GetTournamentBuilder, GetTournamentQuery, GetTournamentUsers, GetTournaments, GetUserQuery, GetUsersQuery, GetUsersResult, UpdateMatch, UpdateParticipantsBulk, UpdateTournament, UpdateTournamentBuilder, UpdateUser, UpdateUsersBulk
+    commands::TempMessageModel, services::challonge::types::ChallongeTournamentState, graphql::queries::{
+        self, create_games_bulk::{self, CreateGameModel}, create_organizer, create_participant, create_tournament_builder::{self, CreateTournamentBuilderCreateTournamentBuilder}, create_tournament_mutation, create_user_mutation::{self, CreateUserMutationCreateUser, ResponseData}, delete_participant, games_count, get_hero_query::{self, GetHeroQueryHero}, get_heroes_query::{self, GetHeroesQueryHeroesNewHeroesEntities}, get_match_query::GetMatchQueryGetMatch, get_mods_query::{self, GetModsQueryMods}, get_operator_data_query::{self, GetOperatorDataQueryOperator}, get_organizer::{self, GetOrganizerOrganizer}, get_participant::{self, GetParticipantParticipant}, get_tournament_builder::{self, GetTournamentBuilderTournamentBuilder}, get_tournament_query, get_tournament_users::{self, GetTournamentUsersTournamentUsers}, get_tournaments::{self, GetTournamentsTournaments}, get_user_query::{self, GetUserQueryUser}, update_match, update_participants_bulk::{self, UpdateParticipant}, update_tournament, update_tournament_builder::{self, UpdateTournamentBuilderUpdateTournamentBuilder}, update_user, update_users_bulk, CreateGamesBulk, CreateMatchMutation, CreateOrganizer, CreateParticipant, CreateTournamentBuilder, CreateTournamentMutation, CreateUserMutation, DeleteParticipant, GamesCount, GetHeroQuery, GetHeroesQuery, GetMatchQuery, GetModsQuery, GetOperatorDataQuery, ModId, GetOperatorSectionQuery, GetOrganizer, GetParticipant, GetTournamentBuilder, GetTournamentQuery, GetTournamentUsers, GetTournaments, GetUserQuery, GetUsersQuery, GetUsersResult, UpdateMatch, UpdateParticipantsBulk, UpdateTournament, UpdateTournamentBuilder, UpdateUser, UpdateUsersBulk
     }, parser::service::ParsedData, types::payloads::{GetMatch, GetTournament, GetUser}
 };
 
@@ -15,11 +15,71 @@ use super::payloads::{
     CreateOrganizerPayload, CreateParticipantPayload, CreateTournamentPayload, CreateUserPayload, DeleteParticipantPayload, GetOperatorPayload, GetOrganizerPayload, GetParticipantPayload, GetTournamentBuilderPayload, UpdateTournamentBuilderPayload, UpdateTournamentPayload
 };
 
+/// Stages between the current stage of tournament and the one its challonge state corresponds to.
+/// Tournaments that aren't started on challonge, are already finished or have unknown stage stay where they are.
+fn stages_to_follow(
+    stage: &get_tournament_query::TournamentStage,
+    state: &ChallongeTournamentState,
+) -> Vec<update_tournament::TournamentStage> {
+    use get_tournament_query::TournamentStage as Current;
+    use update_tournament::TournamentStage as Next;
+
+    let position = match stage {
+        Current::DRAFT => 0,
+        Current::REGISTRATION => 1,
+        Current::CHECK_IN => 2,
+        Current::GROUP_STAGE => 3,
+        Current::PLAY_OFF => 4,
+        _ => return vec![],
+    };
+    let target = match state {
+        ChallongeTournamentState::Pending => return vec![],
+        ChallongeTournamentState::GroupStagesUnderway => 3,
+        ChallongeTournamentState::GroupStagesFinalized
+        | ChallongeTournamentState::Underway
+        | ChallongeTournamentState::AwaitingReview => 4,
+        ChallongeTournamentState::Complete => 5,
+    };
+
+    let mut stages = vec![];
+    if position < 1 {
+        stages.push(Next::REGISTRATION);
+    }
+    if position < 2 {
+        stages.push(Next::CHECK_IN);
+    }
+    if target == 3 && position < 3 {
+        stages.push(Next::GROUP_STAGE);
+    }
+    if target >= 4 && position < 4 {
+        stages.push(Next::PLAY_OFF);
+    }
+    if target == 5 {
+        stages.push(Next::FINISHED);
+    }
+    stages
+}
+
 pub struct RaceNew {
     pub id: i64,
     pub name: String,
 }
 
+/// Creates http client that sends api token of the bot with every request if it is configured.
+fn build_client(token: Option<String>) -> reqwest::Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = token {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .expect("H5_TOURNAMENTS_TOKEN must be a valid header value");
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap()
+}
+
 pub struct H5TournamentsService {
     client: tokio::sync::RwLock<reqwest::Client>,
     url: String,
@@ -29,7 +89,7 @@ pub struct H5TournamentsService {
 impl H5TournamentsService {
     pub fn new(secrets: &shuttle_runtime::SecretStore) -> Self {
         H5TournamentsService {
-            client: tokio::sync::RwLock::new(reqwest::Client::new()),
+            client: tokio::sync::RwLock::new(build_client(secrets.get("H5_TOURNAMENTS_TOKEN"))),
             url: secrets.get("H5_TOURNAMENTS_URL").unwrap(),
             races: vec![
                 RaceNew {
@@ -435,7 +495,7 @@ impl H5TournamentsService {
             message: message as i64,
             first_player: first_player,
             second_player: second_player,
-            challonge_id: challonge_id,
+            challonge_id: Some(challonge_id),
         };
 
         let client = self.client.read().await;
@@ -645,9 +705,37 @@ impl H5TournamentsService {
     //     }
     // }
 
-    pub async fn get_heroes(&self, mod_type: h5_tournaments_api::prelude::ModType) -> Result<Vec<GetHeroesQueryHeroesNewHeroesEntities>, crate::Error> {
+    /// Mods new tournaments can be created for.
+    pub async fn get_mods(&self) -> Result<Vec<GetModsQueryMods>, crate::Error> {
         let client = self.client.read().await;
-        let query = GetHeroesQuery::build_query(get_heroes_query::Variables {mod_type: mod_type.into()});
+        let query = GetModsQuery::build_query(get_mods_query::Variables {});
+        let response = client.post(&self.url).json(&query).send().await;
+        match response {
+            Ok(response) => {
+                let result = response
+                    .json::<Response<queries::get_mods_query::ResponseData>>()
+                    .await;
+                match result {
+                    Ok(result) => {
+                        if let Some(data) = result.data {
+                            Ok(data.mods)
+                        } else {
+                            Err(crate::Error::from(
+                                "Unknown error: got successful response but incorrect data"
+                                    .to_string(),
+                            ))
+                        }
+                    }
+                    Err(json_error) => Err(crate::Error::from(json_error)),
+                }
+            }
+            Err(response_error) => Err(crate::Error::from(response_error)),
+        }
+    }
+
+    pub async fn get_heroes(&self, mod_type: ModId) -> Result<Vec<GetHeroesQueryHeroesNewHeroesEntities>, crate::Error> {
+        let client = self.client.read().await;
+        let query = GetHeroesQuery::build_query(get_heroes_query::Variables {mod_type});
         let response = client.post(&self.url).json(&query).send().await;
         match response {
             Ok(response) => {
@@ -1131,6 +1219,25 @@ impl H5TournamentsService {
         }
     }
 
+    /// Moves tournament through the stages it passed on challonge one by one, since only the next stages can be set.
+    /// Returns whether tournament ends up at a stage matches can be reported at.
+    pub async fn follow_challonge_state(
+        &self,
+        tournament_id: Uuid,
+        stage: &get_tournament_query::TournamentStage,
+        state: &ChallongeTournamentState,
+    ) -> Result<bool, crate::Error> {
+        let stages = stages_to_follow(stage, state);
+        let reports_allowed = match stages.last() {
+            Some(last) => matches!(last, update_tournament::TournamentStage::GROUP_STAGE | update_tournament::TournamentStage::PLAY_OFF),
+            None => matches!(stage, get_tournament_query::TournamentStage::GROUP_STAGE | get_tournament_query::TournamentStage::PLAY_OFF),
+        };
+        for next_stage in stages {
+            self.update_tournament(UpdateTournamentPayload::new(tournament_id).with_stage(next_stage)).await?;
+        }
+        Ok(reports_allowed)
+    }
+
     pub async fn get_tournament_users(
         &self,
         tournament_id: Uuid,
@@ -1320,3 +1427,68 @@ impl H5TournamentsService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graphql::queries::{get_tournament_query, update_tournament},
+        services::challonge::types::ChallongeTournamentState,
+    };
+
+    use super::stages_to_follow;
+
+    fn names(stages: Vec<update_tournament::TournamentStage>) -> Vec<String> {
+        stages.iter().map(|stage| format!("{:?}", stage)).collect()
+    }
+
+    #[test]
+    fn pending_tournaments_stay_where_they_are() {
+        use get_tournament_query::TournamentStage as Current;
+
+        for stage in [Current::DRAFT, Current::REGISTRATION, Current::CHECK_IN] {
+            assert!(stages_to_follow(&stage, &ChallongeTournamentState::Pending).is_empty());
+        }
+    }
+
+    #[test]
+    fn group_stage_is_only_entered_while_groups_are_played() {
+        use get_tournament_query::TournamentStage as Current;
+
+        assert_eq!(
+            names(stages_to_follow(&Current::DRAFT, &ChallongeTournamentState::GroupStagesUnderway)),
+            ["REGISTRATION", "CHECK_IN", "GROUP_STAGE"]
+        );
+        assert_eq!(
+            names(stages_to_follow(&Current::CHECK_IN, &ChallongeTournamentState::Underway)),
+            ["PLAY_OFF"]
+        );
+        assert_eq!(
+            names(stages_to_follow(&Current::GROUP_STAGE, &ChallongeTournamentState::GroupStagesFinalized)),
+            ["PLAY_OFF"]
+        );
+    }
+
+    #[test]
+    fn completed_tournaments_are_finished() {
+        use get_tournament_query::TournamentStage as Current;
+
+        assert_eq!(
+            names(stages_to_follow(&Current::PLAY_OFF, &ChallongeTournamentState::Complete)),
+            ["FINISHED"]
+        );
+        assert_eq!(
+            names(stages_to_follow(&Current::REGISTRATION, &ChallongeTournamentState::Complete)),
+            ["CHECK_IN", "PLAY_OFF", "FINISHED"]
+        );
+        assert!(stages_to_follow(&Current::PLAY_OFF, &ChallongeTournamentState::AwaitingReview).is_empty());
+    }
+
+    #[test]
+    fn finished_and_unknown_tournaments_stay_where_they_are() {
+        use get_tournament_query::TournamentStage as Current;
+
+        for stage in [Current::UNKNOWN, Current::FINISHED, Current::ARCHIVED] {
+            assert!(stages_to_follow(&stage, &ChallongeTournamentState::Complete).is_empty());
+        }
+    }
+}